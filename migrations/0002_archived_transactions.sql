@@ -0,0 +1,13 @@
+-- Archiv starych transakcii (presuvane archivacnou ulohou)
+CREATE TABLE IF NOT EXISTS archived_transactions (
+    id UUID PRIMARY KEY,
+    from_account UUID NOT NULL,
+    to_account UUID NOT NULL,
+    amount NUMERIC(15, 2) NOT NULL,
+    created_at TIMESTAMP,
+    archived_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_from ON archived_transactions(from_account);
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_to ON archived_transactions(to_account);
+CREATE INDEX IF NOT EXISTS idx_transactions_created_at ON transactions(created_at);
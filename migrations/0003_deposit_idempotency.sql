@@ -0,0 +1,10 @@
+-- Vklady sa zaznamenavaju ako transakcie bez odosielatela
+ALTER TABLE transactions ALTER COLUMN from_account DROP NOT NULL;
+ALTER TABLE archived_transactions ALTER COLUMN from_account DROP NOT NULL;
+
+-- Kluc idempotencie pre opakovane poziadavky
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS idempotency_key TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_idempotency
+    ON transactions (to_account, idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
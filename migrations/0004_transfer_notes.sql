@@ -0,0 +1,6 @@
+-- Sukromne poznamky odosielatela a prijemcu k prevodu
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS sender_note TEXT;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS receiver_note TEXT;
+
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS sender_note TEXT;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS receiver_note TEXT;
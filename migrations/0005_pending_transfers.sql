@@ -0,0 +1,15 @@
+-- Prevody cakajuce na potvrdenie (dvojkrokovy prevod)
+CREATE TABLE IF NOT EXISTS pending_transfers (
+    id UUID PRIMARY KEY,
+    token TEXT UNIQUE NOT NULL,
+    from_account UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    to_account UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    amount NUMERIC(15, 2) NOT NULL,
+    sender_note TEXT,
+    receiver_note TEXT,
+    -- pending | confirmed | expired | cancelled
+    status TEXT NOT NULL DEFAULT 'pending',
+    transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP NOT NULL
+);
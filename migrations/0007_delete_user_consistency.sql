@@ -0,0 +1,13 @@
+-- Zmazanie uctu zachova historiu protistrany - odkaz na zmazany ucet sa nastavi na NULL
+ALTER TABLE transactions ALTER COLUMN to_account DROP NOT NULL;
+ALTER TABLE archived_transactions ALTER COLUMN to_account DROP NOT NULL;
+
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_from_account_fkey;
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_to_account_fkey;
+
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_from_account_fkey
+    FOREIGN KEY (from_account) REFERENCES accounts(id) ON DELETE SET NULL;
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_to_account_fkey
+    FOREIGN KEY (to_account) REFERENCES accounts(id) ON DELETE SET NULL;
@@ -0,0 +1,5 @@
+-- Mena uctu (ISO 4217) a stav uctu
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS currency CHAR(3) NOT NULL DEFAULT 'EUR';
+
+-- active | frozen | closed
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active';
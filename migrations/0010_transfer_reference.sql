@@ -0,0 +1,8 @@
+-- Citatelne referencne cislo prevodu (napr. TRX-2024-000001)
+CREATE SEQUENCE IF NOT EXISTS transfer_reference_seq;
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS reference TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_reference ON transactions (reference);
+
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS reference TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_archived_transactions_reference ON archived_transactions (reference);
@@ -0,0 +1,24 @@
+-- Zaokruhlovaci ucet - zvysky po zaokruhleni uroku/poplatkov na centy (podla meny)
+CREATE TABLE IF NOT EXISTS rounding_account (
+    currency CHAR(3) PRIMARY KEY,
+    balance NUMERIC(30, 10) NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Zaznam o pripisani uroku (jeden riadok na menu v ramci behu)
+CREATE TABLE IF NOT EXISTS interest_runs (
+    id UUID PRIMARY KEY,
+    run_id UUID NOT NULL,
+    currency CHAR(3) NOT NULL,
+    rate NUMERIC(20, 10) NOT NULL,
+    accounts INTEGER NOT NULL,
+    -- presny urok pred zaokruhlenim
+    total_interest NUMERIC(30, 10) NOT NULL,
+    -- urok skutocne pripisany na ucty (centy)
+    total_credited NUMERIC(15, 2) NOT NULL,
+    -- total_interest - total_credited, presunute na rounding_account
+    rounding_remainder NUMERIC(30, 10) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_interest_runs_run_id ON interest_runs(run_id);
@@ -0,0 +1,3 @@
+-- Citatelne cislo uctu s kontrolnymi cislicami (napr. BK12345678903)
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS account_number TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_account_number ON accounts (account_number);
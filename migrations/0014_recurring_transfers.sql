@@ -0,0 +1,21 @@
+-- Trvale prikazy (opakovane prevody)
+CREATE TABLE IF NOT EXISTS recurring_transfers (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    from_account UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    to_account UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    amount NUMERIC(15, 2) NOT NULL,
+    sender_note TEXT,
+    receiver_note TEXT,
+    -- daily | weekly | monthly
+    frequency TEXT NOT NULL,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_run_at TIMESTAMPTZ,
+    last_transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL,
+    -- kod chyby posledneho behu (napr. INSUFFICIENT_FUNDS), NULL = uspech
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurring_transfers_due ON recurring_transfers(next_run_at) WHERE active;
@@ -0,0 +1,11 @@
+-- Stitky (kategorie) transakcii, kazdy ucastnik transakcie ma vlastne stitky
+-- Bez cudzieho kluca na transactions, aby stitky prezili archivaciu
+CREATE TABLE IF NOT EXISTS transaction_tags (
+    transaction_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (transaction_id, user_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_tags_user_tag ON transaction_tags(user_id, tag);
@@ -0,0 +1,22 @@
+-- Nastavenia notifikacii pouzivatela (chybajuci riadok = vsetko zapnute)
+CREATE TABLE IF NOT EXISTS notification_prefs (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    incoming_transfer BOOLEAN NOT NULL DEFAULT TRUE,
+    large_withdrawal BOOLEAN NOT NULL DEFAULT TRUE,
+    new_device_login BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Odoslane notifikacie (schranka pouzivatela)
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- incoming_transfer | large_withdrawal | new_device_login
+    event TEXT NOT NULL,
+    transaction_id UUID,
+    amount NUMERIC(15, 2),
+    detail TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user ON notifications(user_id, created_at);
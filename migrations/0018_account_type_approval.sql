@@ -0,0 +1,4 @@
+-- Typ uctu: checking | savings | business
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS account_type TEXT NOT NULL DEFAULT 'checking';
+
+-- Stav 'pending' = ucet caka na schvalenie administratorom (pozri ACCOUNT_TYPES_REQUIRING_APPROVAL)
@@ -0,0 +1,5 @@
+-- Hranica nizkeho zostatku uctu (NULL = bez upozornenia)
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS low_balance_threshold NUMERIC(15, 2);
+
+-- Notifikacia low_balance sa da vypnut ako ostatne udalosti
+ALTER TABLE notification_prefs ADD COLUMN IF NOT EXISTS low_balance BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,6 @@
+-- Cas, od ktoreho je vklad k dispozicii (NULL = hned); velke vklady su blokovane
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS available_at TIMESTAMPTZ;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS available_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_held_deposits
+    ON transactions(to_account, available_at) WHERE available_at IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Audit opravy zostatkov podla autoritativneho zdroja (POST /admin/reconcile)
+CREATE TABLE IF NOT EXISTS reconciliations (
+    id UUID PRIMARY KEY,
+    -- kto opravu vykonal (hlavicka X-Admin-Actor) a odkial
+    actor TEXT,
+    ip TEXT,
+    user_agent TEXT,
+    forced BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS reconciliation_items (
+    reconciliation_id UUID NOT NULL REFERENCES reconciliations(id),
+    account_id UUID NOT NULL,
+    previous_balance NUMERIC(15, 2) NOT NULL,
+    expected_balance NUMERIC(15, 2) NOT NULL,
+    -- vyrovnavacia transakcia (NULL ak zostatok uz sedel)
+    transaction_id UUID,
+    PRIMARY KEY (reconciliation_id, account_id)
+);
+
+-- Vyrovnavacie transakcie: kladna oprava from_account = NULL, zaporna to_account = NULL
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS reconciliation_id UUID;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS reconciliation_id UUID;
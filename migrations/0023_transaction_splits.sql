@@ -0,0 +1,8 @@
+-- Rozdelenie sumy prevodu do kategorii (napr. 60 najom / 40 energie)
+-- Bez cudzieho kluca na transactions, aby rozdelenie prezilo archivaciu
+CREATE TABLE IF NOT EXISTS transaction_splits (
+    transaction_id UUID NOT NULL,
+    category TEXT NOT NULL,
+    amount NUMERIC(15, 2) NOT NULL CHECK (amount > 0),
+    PRIMARY KEY (transaction_id, category)
+);
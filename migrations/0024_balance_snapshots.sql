@@ -0,0 +1,12 @@
+-- Kontrolne body zostatkov pre vypisy: zostatok uctu zo vsetkych transakcii pred as_of
+CREATE TABLE IF NOT EXISTS balance_snapshots (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    as_of TIMESTAMPTZ NOT NULL,
+    balance NUMERIC(15, 2) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (account_id, as_of)
+);
+
+-- Pohyby uctu za obdobie (prehravanie od kontrolneho bodu)
+CREATE INDEX IF NOT EXISTS idx_transactions_from_created ON transactions(from_account, created_at);
+CREATE INDEX IF NOT EXISTS idx_transactions_to_created ON transactions(to_account, created_at);
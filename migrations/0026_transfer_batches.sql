@@ -0,0 +1,20 @@
+-- Hromadne prevody: transakcie jednej davky zdielaju spolocne batch_id
+CREATE TABLE IF NOT EXISTS transfer_batches (
+    id UUID PRIMARY KEY,
+    from_account UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- cas stornovania celej davky (NULL = davka plati)
+    reversed_at TIMESTAMPTZ
+);
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS batch_id UUID;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS batch_id UUID;
+
+-- Storno transakcia odkazuje na povodnu transakciu davky
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS reversal_of UUID;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS reversal_of UUID;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_batch
+    ON transactions(batch_id) WHERE batch_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_batch
+    ON archived_transactions(batch_id) WHERE batch_id IS NOT NULL;
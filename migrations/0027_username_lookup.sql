@@ -0,0 +1,2 @@
+-- Vyhladanie pouzivatela podla mena bez ohladu na velkost pismen (GET /users/by-username/:username)
+CREATE INDEX IF NOT EXISTS idx_users_username_lower ON users (LOWER(username));
@@ -0,0 +1,7 @@
+-- Otvorenie uctu: prvy zaznam uctu (vklad pociatocneho zostatku, aj nulovy)
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS account_opening BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS account_opening BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Kazdy ucet ma najviac jeden zaznam o otvoreni
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_account_opening
+    ON transactions(to_account) WHERE account_opening;
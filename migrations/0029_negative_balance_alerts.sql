@@ -0,0 +1,13 @@
+-- Upozornenia pre administratora: zostatok uctu klesol pod nulu
+CREATE TABLE IF NOT EXISTS negative_balance_alerts (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- odpis, ktory zostatok dostal pod nulu
+    transaction_id UUID,
+    previous_balance NUMERIC(15, 2) NOT NULL,
+    balance NUMERIC(15, 2) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_negative_balance_alerts_created
+    ON negative_balance_alerts(created_at DESC);
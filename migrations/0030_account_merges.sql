@@ -0,0 +1,5 @@
+-- Zlucenie uctov: zatvoreny zdrojovy ucet odkazuje na ucet, do ktoreho bol zluceny
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS merged_into UUID REFERENCES accounts(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_accounts_merged_into
+    ON accounts(merged_into) WHERE merged_into IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Cistenie vyprsanych klucov idempotencie (IDEMPOTENCY_KEY_TTL_SECS)
+CREATE INDEX IF NOT EXISTS idx_transactions_idempotency_created
+    ON transactions(created_at) WHERE idempotency_key IS NOT NULL;
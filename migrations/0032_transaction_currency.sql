@@ -0,0 +1,12 @@
+-- Mena transakcie: mena uctu odosielatela, pri vklade mena uctu prijemcu
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS currency CHAR(3);
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS currency CHAR(3);
+
+-- Doplnenie existujucich transakcii (transakcie zmazanych uctov zostanu bez meny)
+UPDATE transactions t SET currency = a.currency
+FROM accounts a
+WHERE t.currency IS NULL AND a.id = COALESCE(t.from_account, t.to_account);
+
+UPDATE archived_transactions t SET currency = a.currency
+FROM accounts a
+WHERE t.currency IS NULL AND a.id = COALESCE(t.from_account, t.to_account);
@@ -0,0 +1,2 @@
+-- Hash PIN-u uctu pre velke prevody (NULL = PIN nie je nastaveny)
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS pin_hash TEXT;
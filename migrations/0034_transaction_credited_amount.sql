@@ -0,0 +1,4 @@
+-- Suma pripisana prijemcovi v mene jeho uctu (prevody medzi roznymi menami)
+-- NULL = pripisana suma sa rovna sume transakcie (rovnaka mena, vklady, starsie zaznamy)
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS credited_amount NUMERIC(15, 2);
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS credited_amount NUMERIC(15, 2);
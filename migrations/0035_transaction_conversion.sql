@@ -0,0 +1,6 @@
+-- Prepocet prevodu medzi roznymi menami: mena pripisanej sumy a pouzity kurz
+-- (mena odosielatela -> mena prijemcu); NULL pri prevode v rovnakej mene
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS credited_currency CHAR(3);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS rate NUMERIC;
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS credited_currency CHAR(3);
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS rate NUMERIC;
@@ -0,0 +1,12 @@
+-- Oddelene kluce idempotencie pre vklady a prevody
+-- Vklad (bez referencneho cisla) - kluc plati pre ucet prijemcu
+-- Prevod (s referencnym cislom) - kluc plati pre ucet odosielatela
+DROP INDEX IF EXISTS idx_transactions_idempotency;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_deposit_idempotency
+    ON transactions (to_account, idempotency_key)
+    WHERE idempotency_key IS NOT NULL AND reference IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_transfer_idempotency
+    ON transactions (from_account, idempotency_key)
+    WHERE idempotency_key IS NOT NULL AND reference IS NOT NULL;
@@ -0,0 +1,28 @@
+-- Zaznam o otvoreni pre ucty zalozene pred 0028_account_opening
+-- Suma je cast zostatku, ktoru historia transakcii nevysvetli (pociatocny vklad
+-- starych uctov), takze prehratie historie (vypisy, balance_at) sedi so zostatkom.
+INSERT INTO transactions (from_account, to_account, amount, currency, created_at, account_opening)
+SELECT NULL, a.id,
+       a.balance - COALESCE(m.credits, 0) + COALESCE(m.debits, 0),
+       a.currency,
+       COALESCE(LEAST(a.created_at, m.first_at), NOW()),
+       TRUE
+FROM accounts a
+LEFT JOIN LATERAL (
+    SELECT SUM(COALESCE(t.credited_amount, t.amount)) FILTER (WHERE t.to_account = a.id) AS credits,
+           SUM(t.amount) FILTER (WHERE t.from_account = a.id) AS debits,
+           MIN(t.created_at) AS first_at
+    FROM (
+        SELECT from_account, to_account, amount, credited_amount, created_at FROM transactions
+        UNION ALL
+        SELECT from_account, to_account, amount, credited_amount, created_at FROM archived_transactions
+    ) t
+    WHERE t.from_account = a.id OR t.to_account = a.id
+) m ON TRUE
+WHERE NOT EXISTS (SELECT 1 FROM transactions o WHERE o.to_account = a.id AND o.account_opening)
+  AND NOT EXISTS (
+      SELECT 1 FROM archived_transactions o WHERE o.to_account = a.id AND o.account_opening
+  );
+
+-- Kontrolne body zostatkov vznikli bez tychto zaznamov - dalsi beh ich vytvori znova
+DELETE FROM balance_snapshots;
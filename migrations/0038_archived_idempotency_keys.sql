@@ -0,0 +1,14 @@
+-- Kluce idempotencie sa pri archivacii prenasaju do archivu, aby opakovanie
+-- poziadavky po archivacii povodnej transakcie nevytvorilo novu
+ALTER TABLE archived_transactions ADD COLUMN IF NOT EXISTS idempotency_key TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_deposit_idempotency
+    ON archived_transactions (to_account, idempotency_key)
+    WHERE idempotency_key IS NOT NULL AND reference IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_transfer_idempotency
+    ON archived_transactions (from_account, idempotency_key)
+    WHERE idempotency_key IS NOT NULL AND reference IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_archived_transactions_idempotency_created
+    ON archived_transactions (created_at) WHERE idempotency_key IS NOT NULL;
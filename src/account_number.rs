@@ -0,0 +1,228 @@
+// account_number.rs
+use crate::error::BankError;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Schema kontrolnych cislic cisla uctu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckDigitScheme {
+    /// Luhnov algoritmus - 1 kontrolna cislica
+    #[default]
+    Luhn,
+    /// ISO 7064 MOD 97-10 (ako pri IBAN) - 2 kontrolne cislice
+    Mod97,
+}
+
+impl CheckDigitScheme {
+    /// Pocet kontrolnych cislic na konci cisla
+    pub fn check_len(&self) -> usize {
+        match self {
+            CheckDigitScheme::Luhn => 1,
+            CheckDigitScheme::Mod97 => 2,
+        }
+    }
+
+    /// Vypocita kontrolne cislice pre ciselnu cast bez nich
+    fn compute(&self, digits: &str) -> String {
+        match self {
+            CheckDigitScheme::Luhn => {
+                // Od prava sa zdvojnasobuje kazda druha cislica (pocitane s kontrolnou)
+                let sum: u32 = digits
+                    .chars()
+                    .rev()
+                    .filter_map(|c| c.to_digit(10))
+                    .enumerate()
+                    .map(|(i, d)| match i % 2 {
+                        0 if d * 2 > 9 => d * 2 - 9,
+                        0 => d * 2,
+                        _ => d,
+                    })
+                    .sum();
+                ((10 - sum % 10) % 10).to_string()
+            }
+            CheckDigitScheme::Mod97 => {
+                // Zvysok sa pocita postupne, cislo moze byt dlhsie ako u64
+                let remainder = digits
+                    .chars()
+                    .filter_map(|c| c.to_digit(10))
+                    .chain([0, 0])
+                    .fold(0u32, |acc, d| (acc * 10 + d) % 97);
+                format!("{:02}", 98 - remainder)
+            }
+        }
+    }
+}
+
+impl FromStr for CheckDigitScheme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "luhn" => Ok(CheckDigitScheme::Luhn),
+            "mod97" => Ok(CheckDigitScheme::Mod97),
+            _ => Err(format!("expected luhn or mod97, got '{value}'")),
+        }
+    }
+}
+
+/// Povoleny pocet cislic cisla uctu vratane kontrolnych
+pub const ACCOUNT_NUMBER_LENGTHS: RangeInclusive<usize> = 6..=30;
+
+/// Format cisla uctu
+///
+/// Nastavuje sa cez Config::account_numbers (ACCOUNT_NUMBER_PREFIX,
+/// ACCOUNT_NUMBER_LENGTH, ACCOUNT_NUMBER_CHECK).
+///
+/// Cislo uctu ma tvar `<prefix><cislice><kontrolne cislice>`, napr. BK12345678903.
+#[derive(Debug, Clone)]
+pub struct AccountNumberConfig {
+    /// Pismenova predpona (predvolene "BK")
+    pub prefix: String,
+    /// Pocet cislic vratane kontrolnych (predvolene 12)
+    pub length: usize,
+    /// Schema kontrolnych cislic (predvolene Luhn)
+    pub scheme: CheckDigitScheme,
+}
+
+impl Default for AccountNumberConfig {
+    fn default() -> Self {
+        Self {
+            prefix: "BK".to_string(),
+            length: 12,
+            scheme: CheckDigitScheme::default(),
+        }
+    }
+}
+
+impl AccountNumberConfig {
+    /// Vygeneruje nahodne cislo uctu s kontrolnymi cislicami
+    ///
+    /// # Poznamka
+    /// Unikatnost zarucuje az UNIQUE index v databaze - volajuci pri kolizii
+    /// vygeneruje nove cislo.
+    pub fn generate(&self) -> String {
+        let body: String = (0..self.length - self.scheme.check_len())
+            .map(|_| char::from(b'0' + (OsRng.next_u32() % 10) as u8))
+            .collect();
+        let check = self.scheme.compute(&body);
+        format!("{}{body}{check}", self.prefix)
+    }
+
+    /// Overi tvar a kontrolne cislice cisla uctu
+    ///
+    /// # Chyby
+    /// - BankError::InvalidAccountNumber: zla predpona, dlzka, znaky alebo kontrolne cislice
+    pub fn validate(&self, number: &str) -> Result<(), BankError> {
+        let digits = number
+            .strip_prefix(self.prefix.as_str())
+            .ok_or(BankError::InvalidAccountNumber)?;
+
+        if digits.len() != self.length || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(BankError::InvalidAccountNumber);
+        }
+
+        let (body, check) = digits.split_at(self.length - self.scheme.check_len());
+        if self.scheme.compute(body) != check {
+            return Err(BankError::InvalidAccountNumber);
+        }
+
+        Ok(())
+    }
+}
+
+/// Pocet znakov cisla uctu, ktore zostanu viditelne po maskovani
+pub const VISIBLE_SUFFIX_LEN: usize = 4;
+
+/// Zamaskuje cislo uctu okrem poslednych 4 znakov (napr. BK12345678903 -> *********8903)
+pub fn mask_account_number(number: &str) -> String {
+    let len = number.chars().count();
+    number
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i + VISIBLE_SUFFIX_LEN < len { '*' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(prefix: &str, length: usize, scheme: CheckDigitScheme) -> AccountNumberConfig {
+        AccountNumberConfig {
+            prefix: prefix.to_string(),
+            length,
+            scheme,
+        }
+    }
+
+    #[test]
+    fn generated_numbers_pass_validation() {
+        for config in [
+            AccountNumberConfig::default(),
+            numbers("XY", 6, CheckDigitScheme::Luhn),
+            numbers("IBAN", 30, CheckDigitScheme::Mod97),
+        ] {
+            for _ in 0..50 {
+                let number = config.generate();
+                assert!(number.starts_with(&config.prefix));
+                assert_eq!(number.len(), config.prefix.len() + config.length);
+                assert!(config.validate(&number).is_ok(), "{number}");
+            }
+        }
+    }
+
+    #[test]
+    fn luhn_check_digit_matches_reference_value() {
+        // Priklad z popisu Luhnovho algoritmu: 7992739871 -> 3
+        let config = numbers("BK", 11, CheckDigitScheme::Luhn);
+        assert!(config.validate("BK79927398713").is_ok());
+        assert!(config.validate("BK79927398710").is_err());
+    }
+
+    #[test]
+    fn malformed_numbers_are_rejected() {
+        for scheme in [CheckDigitScheme::Luhn, CheckDigitScheme::Mod97] {
+            let config = numbers("BK", 12, scheme);
+            let number = config.generate();
+            let digits = &number[2..];
+
+            // Kazda zmena jednej cislice sa odhali
+            for (i, c) in digits.char_indices() {
+                let other = char::from(b'0' + (c as u8 - b'0' + 1) % 10);
+                let mut changed = digits.to_string();
+                changed.replace_range(i..=i, &other.to_string());
+                assert!(config.validate(&format!("BK{changed}")).is_err());
+            }
+
+            for invalid in [
+                format!("XX{digits}"),
+                digits.to_string(),
+                format!("BK{}", &digits[1..]),
+                format!("BK{digits}0"),
+                format!("BK{}A", &digits[1..]),
+            ] {
+                assert!(
+                    matches!(
+                        config.validate(&invalid),
+                        Err(BankError::InvalidAccountNumber)
+                    ),
+                    "{invalid}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_check_digit_scheme() {
+        assert_eq!("luhn".parse(), Ok(CheckDigitScheme::Luhn));
+        assert_eq!(" MOD97 ".parse(), Ok(CheckDigitScheme::Mod97));
+        assert!("crc".parse::<CheckDigitScheme>().is_err());
+    }
+
+    #[test]
+    fn masks_all_but_last_four_characters() {
+        assert_eq!(mask_account_number("BK12345678903"), "*********8903");
+        assert_eq!(mask_account_number("903"), "903");
+    }
+}
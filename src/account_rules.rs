@@ -0,0 +1,118 @@
+// account_rules.rs
+use crate::models::AccountType;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Predvoleny pocet vyberov zo sporiaceho uctu za kalendarny mesiac
+pub const DEFAULT_SAVINGS_MONTHLY_WITHDRAWALS: u32 = 6;
+
+/// Pravidla odchadzajucich prevodov pre jeden typ uctu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountTypeRule {
+    /// O kolko moze zostatok klesnut pod nulu (0 = bez precerpania)
+    pub overdraft_limit: Decimal,
+    /// Najvyssi pocet odchadzajucich prevodov za kalendarny mesiac (UTC), None = bez limitu
+    pub monthly_withdrawals: Option<u32>,
+}
+
+impl AccountTypeRule {
+    /// Bez precerpania a bez limitu vyberov
+    pub const UNRESTRICTED: Self = Self {
+        overdraft_limit: Decimal::ZERO,
+        monthly_withdrawals: None,
+    };
+}
+
+/// Pravidla podla typu uctu, zapis `savings.monthly_withdrawals=3,checking.overdraft=500`
+///
+/// # Poznamka
+/// Nezadane hodnoty maju predvolbu: ziadny typ nema precerpanie, sporiaci ucet ma
+/// najviac 6 vyberov za mesiac (`savings.monthly_withdrawals=unlimited` limit zrusi),
+/// bezny a podnikatelsky ucet su bez limitu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountTypeRules {
+    checking: AccountTypeRule,
+    savings: AccountTypeRule,
+    business: AccountTypeRule,
+}
+
+impl Default for AccountTypeRules {
+    fn default() -> Self {
+        Self {
+            checking: AccountTypeRule::UNRESTRICTED,
+            savings: AccountTypeRule {
+                monthly_withdrawals: Some(DEFAULT_SAVINGS_MONTHLY_WITHDRAWALS),
+                ..AccountTypeRule::UNRESTRICTED
+            },
+            business: AccountTypeRule::UNRESTRICTED,
+        }
+    }
+}
+
+impl AccountTypeRules {
+    /// Pravidla pre dany typ uctu
+    pub fn for_type(&self, account_type: AccountType) -> AccountTypeRule {
+        match account_type {
+            AccountType::Checking => self.checking,
+            AccountType::Savings => self.savings,
+            AccountType::Business => self.business,
+        }
+    }
+
+    fn for_type_mut(&mut self, account_type: AccountType) -> &mut AccountTypeRule {
+        match account_type {
+            AccountType::Checking => &mut self.checking,
+            AccountType::Savings => &mut self.savings,
+            AccountType::Business => &mut self.business,
+        }
+    }
+}
+
+impl FromStr for AccountTypeRules {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::default();
+        for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+            let entry = entry.trim();
+            let invalid = || format!("expected <type>.<rule>=<value>, got '{entry}'");
+            let (key, setting) = entry.split_once('=').ok_or_else(invalid)?;
+            let (type_name, rule_name) = key.trim().split_once('.').ok_or_else(invalid)?;
+
+            let type_name = type_name.trim().to_lowercase();
+            let account_type = AccountType::ALL
+                .into_iter()
+                .find(|t| t.as_str() == type_name)
+                .ok_or_else(|| format!("unknown account type '{type_name}'"))?;
+            let rule = rules.for_type_mut(account_type);
+            let setting = setting.trim();
+
+            match rule_name.trim() {
+                "overdraft" => {
+                    rule.overdraft_limit = Decimal::from_str(setting)
+                        .ok()
+                        .filter(|limit| *limit >= Decimal::ZERO)
+                        .ok_or_else(|| {
+                            format!("overdraft must be a non-negative amount, got '{setting}'")
+                        })?;
+                }
+                "monthly_withdrawals" => {
+                    rule.monthly_withdrawals = match setting {
+                        "unlimited" => None,
+                        count => Some(count.parse().map_err(|_| {
+                            format!(
+                                "monthly_withdrawals must be a number or 'unlimited', got '{count}'"
+                            )
+                        })?),
+                    };
+                }
+                other => {
+                    return Err(format!(
+                        "unknown rule '{other}', expected overdraft or monthly_withdrawals"
+                    ));
+                }
+            }
+        }
+        Ok(rules)
+    }
+}
@@ -0,0 +1,92 @@
+// amount_bounds.rs
+use crate::currency::is_iso_4217;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Povoleny rozsah sumy jednej operacie, zapis `MIN..MAX` (jedna strana moze chybat)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AmountBounds {
+    /// Najmensia povolena suma (vratane), None = bez dolnej hranice
+    pub min: Option<Decimal>,
+    /// Najvacsia povolena suma (vratane), None = bez hornej hranice
+    pub max: Option<Decimal>,
+}
+
+impl AmountBounds {
+    /// Ci suma lezi v povolenom rozsahu
+    pub fn contains(&self, amount: Decimal) -> bool {
+        self.min.is_none_or(|min| amount >= min) && self.max.is_none_or(|max| amount <= max)
+    }
+}
+
+impl FromStr for AmountBounds {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected <min>..<max>, got '{value}'");
+        let (min, max) = value.trim().split_once("..").ok_or_else(invalid)?;
+        let bound = |text: &str| -> Result<Option<Decimal>, String> {
+            let text = text.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            match Decimal::from_str(text) {
+                Ok(amount) if amount > Decimal::ZERO => Ok(Some(amount)),
+                Ok(_) => Err(format!("bounds must be positive, got '{value}'")),
+                Err(_) => Err(invalid()),
+            }
+        };
+        let bounds = Self {
+            min: bound(min)?,
+            max: bound(max)?,
+        };
+        if let (Some(min), Some(max)) = (bounds.min, bounds.max)
+            && min > max
+        {
+            return Err(format!("min must not be greater than max, got '{value}'"));
+        }
+        Ok(bounds)
+    }
+}
+
+/// Rozsahy sum podla meny uctu, zapis `EUR=0.01..10000,CZK=1..250000`
+///
+/// Mena bez zaznamu nema ziadne obmedzenie (predvolene su povolene vsetky sumy).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CurrencyAmountBounds {
+    bounds: HashMap<String, AmountBounds>,
+}
+
+impl CurrencyAmountBounds {
+    /// Ci nie je nastaveny rozsah pre ziadnu menu
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Rozsah pre menu (ISO 4217, na velkosti pismen nezalezi), None = bez obmedzenia
+    pub fn for_currency(&self, currency: &str) -> Option<AmountBounds> {
+        self.bounds.get(&currency.trim().to_uppercase()).copied()
+    }
+}
+
+impl FromStr for CurrencyAmountBounds {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut bounds = HashMap::new();
+        for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+            let (currency, range) = entry.split_once('=').ok_or_else(|| {
+                format!("expected <currency>=<min>..<max>, got '{}'", entry.trim())
+            })?;
+            let currency = currency.trim().to_uppercase();
+            if !is_iso_4217(&currency) {
+                return Err(format!("'{currency}' is not an ISO 4217 currency code"));
+            }
+            if bounds.insert(currency.clone(), range.parse()?).is_some() {
+                return Err(format!("currency '{currency}' is listed more than once"));
+            }
+        }
+        Ok(Self { bounds })
+    }
+}
@@ -0,0 +1,205 @@
+// amount_format.rs
+use crate::error::BankError;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Najvyssi povoleny pocet desatinnych miest pri pevnom formate
+pub const MAX_AMOUNT_SCALE: u32 = 8;
+
+/// Polia odpovede, ktore obsahuju penazne sumy
+///
+/// Kurzy (`rate`) ani ine ciselne retazce (napr. cisla uctov) sa nemenia.
+const AMOUNT_FIELDS: &[&str] = &[
+    "amount",
+    "balance",
+    "available_balance",
+    "opening_balance",
+    "closing_balance",
+    "stored_balance",
+    "computed_balance",
+    "previous_balance",
+    "expected_balance",
+    "adjustment",
+    "difference",
+    "threshold",
+    "total",
+    "total_credits",
+    "total_debits",
+    "net_change",
+    "total_interest",
+    "total_credited",
+    "rounding_remainder",
+    "rounding_balance",
+];
+
+/// Tvar sumy v JSON odpovedi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmountStyle {
+    /// Retazec, napr. "1200.50" (predvolene)
+    #[default]
+    String,
+    /// Cislo, napr. 1200.5
+    Number,
+}
+
+impl FromStr for AmountStyle {
+    type Err = BankError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "string" => Ok(AmountStyle::String),
+            "number" => Ok(AmountStyle::Number),
+            _ => Err(BankError::InvalidAmountFormat),
+        }
+    }
+}
+
+/// Format sum v odpovedi pozadovany klientom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AmountFormat {
+    /// Retazec alebo cislo
+    pub style: AmountStyle,
+    /// Pevny pocet desatinnych miest (None = presnost ulozenej hodnoty)
+    pub scale: Option<u32>,
+}
+
+/// Query parametre formatu sum (`?amount_format=string&amount_scale=2`)
+#[derive(Debug, Default, Deserialize)]
+pub struct AmountFormatQuery {
+    pub amount_format: Option<String>,
+    pub amount_scale: Option<String>,
+}
+
+impl AmountFormat {
+    /// Urci format podla query parametrov alebo hlavicky Accept
+    ///
+    /// # Parametre
+    /// - query: parametre `amount_format` (string|number) a `amount_scale` (0-8)
+    /// - accept: hodnota hlavicky Accept, napr. `application/json; amounts=string; scale=2`
+    ///
+    /// # Navratova hodnota
+    /// None ak klient format nepozaduje (odpoved zostane bez zmeny).
+    /// Query parametre maju prednost pred hlavickou Accept.
+    ///
+    /// # Chyby
+    /// - BankError::InvalidAmountFormat: neznamy tvar alebo neplatny pocet desatinnych miest
+    pub fn from_request(
+        query: &AmountFormatQuery,
+        accept: Option<&str>,
+    ) -> Result<Option<Self>, BankError> {
+        if query.amount_format.is_some() || query.amount_scale.is_some() {
+            return Self::parse(
+                query.amount_format.as_deref(),
+                query.amount_scale.as_deref(),
+            )
+            .map(Some);
+        }
+
+        // Parametre media typu JSON v Accept, napr. "application/json; amounts=number"
+        let Some(params) = accept.and_then(|accept| {
+            accept
+                .split(',')
+                .map(|range| range.split(';').map(str::trim))
+                .find_map(|mut range| {
+                    let media = range.next()?.to_lowercase();
+                    (media == "application/json" || media == "*/*").then_some(range)
+                })
+        }) else {
+            return Ok(None);
+        };
+
+        let mut style = None;
+        let mut scale = None;
+        for param in params {
+            match param.split_once('=') {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("amounts") => {
+                    style = Some(value.trim().trim_matches('"'));
+                }
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("scale") => {
+                    scale = Some(value.trim().trim_matches('"'));
+                }
+                _ => {}
+            }
+        }
+
+        if style.is_none() && scale.is_none() {
+            return Ok(None);
+        }
+        Self::parse(style, scale).map(Some)
+    }
+
+    fn parse(style: Option<&str>, scale: Option<&str>) -> Result<Self, BankError> {
+        let style = style
+            .map(AmountStyle::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let scale = scale
+            .map(|s| s.trim().parse::<u32>())
+            .transpose()
+            .map_err(|_| BankError::InvalidAmountFormat)?;
+        if scale.is_some_and(|s| s > MAX_AMOUNT_SCALE) {
+            return Err(BankError::InvalidAmountFormat);
+        }
+        Ok(Self { style, scale })
+    }
+
+    /// Prepise vsetky sumy v JSON hodnote do pozadovaneho formatu
+    ///
+    /// # Poznamka
+    /// Pri pevnom pocte desatinnych miest sa suma zaokruhli (polovica od nuly)
+    /// a doplni nulami. Tvar Number pouziva JSON cislo (f64), takze koncove nuly
+    /// sa stratia a velmi velke sumy mozu stratit presnost - pre presne sumy
+    /// pouzite tvar String.
+    pub fn apply(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, field) in map.iter_mut() {
+                    match field {
+                        serde_json::Value::String(text)
+                            if AMOUNT_FIELDS.contains(&key.as_str()) =>
+                        {
+                            if let Ok(amount) = Decimal::from_str(text) {
+                                *field = self.render(amount);
+                            }
+                        }
+                        _ => self.apply(field),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.apply(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Zapis jednej sumy
+    fn render(&self, amount: Decimal) -> serde_json::Value {
+        let amount = match self.scale {
+            Some(scale) => {
+                let mut rounded =
+                    amount.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+                rounded.rescale(scale);
+                rounded
+            }
+            None => amount,
+        };
+
+        match self.style {
+            AmountStyle::String => serde_json::Value::String(amount.to_string()),
+            AmountStyle::Number if amount.fract().is_zero() => i64::try_from(amount)
+                .map(serde_json::Value::from)
+                .unwrap_or_else(|_| serde_json::Value::String(amount.to_string())),
+            AmountStyle::Number => amount
+                .to_string()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(serde_json::Value::Number)
+                .unwrap_or_else(|| serde_json::Value::String(amount.to_string())),
+        }
+    }
+}
@@ -0,0 +1,346 @@
+// archive.rs
+use crate::config::Config;
+use crate::crud::ensure_account_exists;
+use crate::error::BankError;
+use crate::models::{SortOrder, Transaction, TransactionPage, TransactionSearch};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use sqlx::query;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Nastavenia archivacie starych transakcii
+#[derive(Debug, Clone)]
+pub struct ArchivalConfig {
+    /// Transakcie starsie ako tento pocet dni sa presunu do archivu
+    pub retention_days: i64,
+    /// Maximalny pocet transakcii presunutych v jednej databazovej transakcii
+    pub batch_size: i64,
+    /// Interval medzi spusteniami archivacie
+    pub interval: Duration,
+}
+
+impl ArchivalConfig {
+    /// Prevezme nastavenia z konfiguracie
+    ///
+    /// # Konfiguracia
+    /// - Config::transaction_retention_days: doba uchovania (TRANSACTION_RETENTION_DAYS)
+    /// - Config::archive_batch_size: velkost davky (ARCHIVE_BATCH_SIZE)
+    /// - Config::archive_interval: interval spustenia (ARCHIVE_INTERVAL_SECS)
+    ///
+    /// # Navratova hodnota
+    /// Vracia None ak archivacia nie je nakonfigurovana
+    pub fn from_config(config: &Config) -> Option<Self> {
+        Some(Self {
+            retention_days: config.transaction_retention_days?,
+            batch_size: config.archive_batch_size,
+            interval: config.archive_interval,
+        })
+    }
+
+    /// Casova hranica - transakcie vytvorene pred nou sa archivuju
+    pub fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - ChronoDuration::days(self.retention_days)
+    }
+}
+
+/// Presunie transakcie starsie ako `cutoff` do tabulky archived_transactions
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - cutoff: casova hranica archivacie
+/// - batch_size: maximalny pocet riadkov v jednej davke
+///
+/// # Navratova hodnota
+/// Vracia celkovy pocet presunutych transakcii
+///
+/// # Poznamka
+/// Kazda davka bezi vo vlastnej databazovej transakcii, aby zamky
+/// netrvali dlho. Kluc idempotencie sa prenesie do archivu, opakovanie
+/// poziadavky sa tak rozpozna aj po archivacii. Riadky zamknute inou transakciou sa preskocia (SKIP LOCKED)
+/// a archivuju sa pri dalsom behu.
+pub async fn archive_transactions(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    batch_size: i64,
+) -> Result<u64, sqlx::Error> {
+    let mut total = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+
+        // Presun davky v jednom prikaze - DELETE ... RETURNING vlozi riadky do archivu
+        let moved = query!(
+            "WITH moved AS (
+                 DELETE FROM transactions
+                 WHERE id IN (
+                     SELECT id FROM transactions
+                     WHERE created_at < $1
+                     ORDER BY created_at
+                     LIMIT $2
+                     FOR UPDATE SKIP LOCKED
+                 )
+                 RETURNING id, reference, from_account, to_account, amount, credited_amount,
+                           credited_currency, rate, currency, created_at, sender_note,
+                           receiver_note, transfer_group_id, available_at, reconciliation_id,
+                           batch_id, reversal_of, account_opening, idempotency_key
+             )
+             INSERT INTO archived_transactions
+                 (id, reference, from_account, to_account, amount, credited_amount,
+                  credited_currency, rate, currency, created_at, sender_note, receiver_note,
+                  transfer_group_id, available_at, reconciliation_id, batch_id, reversal_of,
+                  account_opening, idempotency_key)
+             SELECT id, reference, from_account, to_account, amount, credited_amount,
+                    credited_currency, rate, currency, created_at, sender_note, receiver_note,
+                    transfer_group_id, available_at, reconciliation_id, batch_id, reversal_of,
+                    account_opening, idempotency_key
+             FROM moved",
+            cutoff,
+            batch_size
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        total += moved;
+
+        // Posledna (neuplna) davka - nic dalsie na archivaciu
+        if moved < batch_size as u64 {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Ziska historiu transakcii uctu vratane archivovanych
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - order: smer zoradenia podla created_at (pri rovnakom case podla id)
+///
+/// # Navratova hodnota
+/// Vracia aktivne aj archivovane transakcie zoradene podla created_at
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+pub async fn get_transaction_history_with_archive(
+    pool: &PgPool,
+    account_id: Uuid,
+    order: SortOrder,
+) -> Result<Vec<Transaction>, BankError> {
+    ensure_account_exists(pool, account_id).await?;
+
+    let rows = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account,
+                  amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at,
+                  CASE WHEN from_account = $1 THEN sender_note ELSE receiver_note END AS note
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM archived_transactions
+           ) t
+           WHERE from_account = $1 OR to_account = $1
+           ORDER BY CASE WHEN $2 THEN created_at END ASC, CASE WHEN $2 THEN id END ASC,
+                    created_at DESC, id DESC"#,
+        account_id,
+        order == SortOrder::Asc
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Konvertovanie riadkov z databazy na Transaction struktury
+    let transactions = rows
+        .into_iter()
+        .map(|row| Transaction {
+            id: row.id,
+            reference: row.reference,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            currency: row.currency,
+            credited_amount: row.credited_amount,
+            credited_currency: row.credited_currency,
+            rate: row.rate,
+            created_at: row.created_at,
+            note: row.note,
+            parties: None,
+            splits: Vec::new(),
+        })
+        .collect();
+    Ok(transactions)
+}
+
+/// Predvoleny pocet transakcii na stranke vyhladavania
+pub const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+/// Najvyssi pocet transakcii na stranke vyhladavania
+pub const MAX_SEARCH_LIMIT: i64 = 500;
+
+/// Vyhlada transakcie vsetkych uctov (administrator)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - search: filtre (ucet, casovy rozsah, minimalna suma, druh) a strankovanie
+///
+/// # Navratova hodnota
+/// Vracia TransactionPage s celkovym poctom vyhovujucich transakcii a jednou
+/// strankou transakcii zoradenych od najnovsich (vratane archivu)
+///
+/// # Poznamka
+/// Sukromne poznamky odosielatela a prijemcu sa nevracaju (note je vzdy None).
+/// Druh sa urcuje podla stran transakcie: prevod ma referencne cislo, vklad
+/// nema odosielatela, odpis ma odosielatela ale nie referencne cislo.
+///
+/// # Chyby
+/// - BankError::InvalidBody: limit mimo 1..=500, zaporny offset alebo from po to
+/// - BankError::InvalidAmount: zaporna minimalna suma
+pub async fn search_transactions(
+    pool: &PgPool,
+    search: &TransactionSearch,
+) -> Result<TransactionPage, BankError> {
+    let limit = search.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    let offset = search.offset.unwrap_or(0);
+
+    if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+        return Err(BankError::InvalidBody(format!(
+            "limit must be between 1 and {MAX_SEARCH_LIMIT}"
+        )));
+    }
+    if offset < 0 {
+        return Err(BankError::InvalidBody(
+            "offset must not be negative".to_string(),
+        ));
+    }
+    if let (Some(from), Some(to)) = (search.from, search.to)
+        && from > to
+    {
+        return Err(BankError::InvalidBody(
+            "from must not be after to".to_string(),
+        ));
+    }
+    if search.min_amount.is_some_and(|m| m < Decimal::ZERO) {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let kind = search.kind.map(|k| k.as_str());
+
+    let total = query!(
+        r#"SELECT COUNT(*) AS "total!"
+           FROM (
+               SELECT reference, from_account, to_account, amount, created_at, account_opening FROM transactions
+               UNION ALL
+               SELECT reference, from_account, to_account, amount, created_at, account_opening FROM archived_transactions
+           ) t
+           WHERE ($1::uuid IS NULL OR from_account = $1 OR to_account = $1)
+             AND ($2::timestamptz IS NULL OR created_at >= $2)
+             AND ($3::timestamptz IS NULL OR created_at < $3)
+             AND ($4::numeric IS NULL OR amount >= $4)
+             AND ($5::text IS NULL
+                  OR ($5 = 'transfer' AND reference IS NOT NULL)
+                  OR ($5 = 'deposit' AND reference IS NULL AND from_account IS NULL AND NOT account_opening)
+                  OR ($5 = 'opening' AND account_opening)
+                  OR ($5 = 'withdrawal' AND reference IS NULL AND from_account IS NOT NULL))"#,
+        search.account,
+        search.from,
+        search.to,
+        search.min_amount,
+        kind
+    )
+    .fetch_one(pool)
+    .await?
+    .total;
+
+    let rows = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account,
+                  amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at, account_opening FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at, account_opening FROM archived_transactions
+           ) t
+           WHERE ($1::uuid IS NULL OR from_account = $1 OR to_account = $1)
+             AND ($2::timestamptz IS NULL OR created_at >= $2)
+             AND ($3::timestamptz IS NULL OR created_at < $3)
+             AND ($4::numeric IS NULL OR amount >= $4)
+             AND ($5::text IS NULL
+                  OR ($5 = 'transfer' AND reference IS NOT NULL)
+                  OR ($5 = 'deposit' AND reference IS NULL AND from_account IS NULL AND NOT account_opening)
+                  OR ($5 = 'opening' AND account_opening)
+                  OR ($5 = 'withdrawal' AND reference IS NULL AND from_account IS NOT NULL))
+           ORDER BY created_at DESC, id
+           LIMIT $6 OFFSET $7"#,
+        search.account,
+        search.from,
+        search.to,
+        search.min_amount,
+        kind,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let transactions = rows
+        .into_iter()
+        .map(|row| Transaction {
+            id: row.id,
+            reference: row.reference,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            currency: row.currency,
+            credited_amount: row.credited_amount,
+            credited_currency: row.credited_currency,
+            rate: row.rate,
+            created_at: row.created_at,
+            note: None,
+            parties: None,
+            splits: Vec::new(),
+        })
+        .collect();
+
+    Ok(TransactionPage {
+        total,
+        limit,
+        offset,
+        transactions,
+    })
+}
+
+/// Spusti archivaciu na pozadi, ak je nakonfigurovana
+///
+/// Uloha bezi v nekonecnej slucke s intervalom z ArchivalConfig.
+/// Chyby sa len zapisu do logu, dalsi beh sa pokusi znovu.
+pub fn spawn_archival_job(config: &Config, pool: PgPool) {
+    let Some(config) = ArchivalConfig::from_config(config) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+
+        loop {
+            interval.tick().await;
+
+            match archive_transactions(&pool, config.cutoff(), config.batch_size).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Archived {count} transactions"),
+                Err(e) => tracing::error!("Transaction archival failed: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,201 @@
+// auth.rs
+use crate::account_number::mask_account_number;
+use crate::client_ip::ClientIp;
+use crate::error::BankError;
+use crate::models::{Counterparty, PubAccount};
+use crate::state::AppState;
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{header, request::Parts},
+};
+use std::convert::Infallible;
+use uuid::Uuid;
+
+/// Hlavicka s API klucom pouzivatela
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Hlavicka s administratorskym tokenom
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// Overi, ci poziadavka obsahuje platny administratorsky token (Config::admin_token)
+fn has_admin_token(parts: &Parts, state: &AppState) -> bool {
+    let expected = state
+        .config
+        .admin_token
+        .as_deref()
+        .filter(|t| !t.is_empty());
+    let provided = parts
+        .headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    matches!((expected, provided), (Some(expected), Some(provided)) if expected == provided)
+}
+
+/// Extractor overujuci administratorsky pristup
+///
+/// Poziadavka musi obsahovat hlavicku `X-Admin-Token` zhodnu s premennou
+/// prostredia ADMIN_TOKEN. Ak ADMIN_TOKEN nie je nastavena, administratorske
+/// endpointy su nedostupne.
+pub struct AdminGuard;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminGuard
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = BankError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if has_admin_token(parts, &AppState::from_ref(state)) {
+            Ok(AdminGuard)
+        } else {
+            Err(BankError::Forbidden)
+        }
+    }
+}
+
+/// Extractor prihlaseneho pouzivatela
+///
+/// Pouzivatel sa overuje API klucom v hlavicke `X-API-Key`.
+/// Chybajuci, neplatny alebo zruseny kluc vrati 401 UNAUTHORIZED.
+/// Kluc sa overuje cez `AppState::repo`, ak ho uz neoveril middleware rate_limit
+/// (vysledok v extensions poziadavky).
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    /// Identifikator prihlaseneho pouzivatela
+    pub user_id: Uuid,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = BankError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(auth) = parts.extensions.get::<AuthUser>() {
+            return Ok(*auth);
+        }
+
+        let key = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or(BankError::Unauthorized)?;
+
+        let user_id = AppState::from_ref(state)
+            .repo
+            .authenticate_api_key(key)
+            .await?;
+        Ok(AuthUser { user_id })
+    }
+}
+
+/// Extractor volajuceho pre verejne endpointy
+///
+/// Na rozdiel od AuthUser prihlasenie nevyzaduje - bez hlaviciek je volajuci
+/// anonymny. Platny `X-Admin-Token` ma prednost pred `X-API-Key`;
+/// neplatny API kluc vrati 401 UNAUTHORIZED.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Viewer {
+    /// Administrator (platny X-Admin-Token)
+    Admin,
+    /// Pouzivatel overeny API klucom
+    User(Uuid),
+    /// Volajuci bez prihlasenia
+    Anonymous,
+}
+
+impl Viewer {
+    /// Ci volajuci vidi plne udaje uctu daneho vlastnika
+    pub fn sees_full(&self, owner: Uuid) -> bool {
+        match self {
+            Viewer::Admin => true,
+            Viewer::User(user_id) => *user_id == owner,
+            Viewer::Anonymous => false,
+        }
+    }
+
+    /// Zamaskuje cisla uctov, ktore volajuci nevlastni (pozri mask_account_number)
+    pub fn redact_accounts(&self, accounts: &mut [PubAccount]) {
+        for account in accounts.iter_mut().filter(|a| !self.sees_full(a.user_id)) {
+            account.account_number = account.account_number.as_deref().map(mask_account_number);
+        }
+    }
+
+    /// Zamaskuje cisla uctov protistran, ktore volajuci nevlastni, a skryje ich vlastnika
+    pub fn redact_counterparties(&self, counterparties: &mut [Counterparty]) {
+        for counterparty in counterparties
+            .iter_mut()
+            .filter(|c| c.user_id.is_some_and(|owner| !self.sees_full(owner)))
+        {
+            counterparty.account_number = counterparty
+                .account_number
+                .as_deref()
+                .map(mask_account_number);
+            counterparty.user_id = None;
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Viewer
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = BankError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if has_admin_token(parts, &AppState::from_ref(state)) {
+            return Ok(Viewer::Admin);
+        }
+
+        if parts.headers.contains_key(API_KEY_HEADER) {
+            let AuthUser { user_id } = AuthUser::from_request_parts(parts, state).await?;
+            return Ok(Viewer::User(user_id));
+        }
+
+        Ok(Viewer::Anonymous)
+    }
+}
+
+/// Udaje o klientovi pre audit (IP adresa a user agent)
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    /// Adresa klienta (pozri ClientIp)
+    pub ip: Option<String>,
+    /// Hlavicka `User-Agent`
+    pub user_agent: Option<String>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ClientIp(ip) = ClientIp::from_request_parts(parts, state).await?;
+
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+
+        Ok(ClientInfo {
+            ip: ip.map(|ip| ip.to_string()),
+            user_agent,
+        })
+    }
+}
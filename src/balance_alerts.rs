@@ -0,0 +1,100 @@
+// balance_alerts.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::models::{NegativeBalanceAlert, NegativeBalanceAlertQuery};
+use crate::notifications::crosses_below;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, query};
+use uuid::Uuid;
+
+/// Predvoleny pocet upozorneni v zozname
+pub const DEFAULT_ALERT_LIMIT: i64 = 100;
+
+/// Najvyssi pocet upozorneni v zozname
+pub const MAX_ALERT_LIMIT: i64 = 500;
+
+/// Zaznamena upozornenie pre administratora, ak odpis dostal zostatok pod nulu
+///
+/// # Parametre
+/// - tx: databazova transakcia odpisu - pri jej stornovani sa upozornenie nezapise
+/// - config: konfiguracia (NEGATIVE_BALANCE_ALERTS=false upozornenia vypne)
+/// - account_id: odpisany ucet
+/// - previous_balance, balance: zostatok pred a po odpise
+/// - transaction_id: zaznam odpisu
+///
+/// # Navratova hodnota
+/// Vracia true, ak bolo upozornenie zaznamenane
+///
+/// # Poznamka
+/// Upozornuje sa len pri prechode z nezaporneho zostatku do zaporneho (crosses_below),
+/// dalsie odpisy uz zaporneho uctu nove upozornenie nevytvoria.
+pub async fn record_negative_balance(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    config: &Config,
+    account_id: Uuid,
+    previous_balance: Decimal,
+    balance: Decimal,
+    transaction_id: Option<Uuid>,
+) -> Result<bool, sqlx::Error> {
+    if !crosses_below(previous_balance, balance, Decimal::ZERO) || !config.negative_balance_alerts {
+        return Ok(false);
+    }
+
+    query!(
+        "INSERT INTO negative_balance_alerts (id, account_id, transaction_id, previous_balance, balance)
+         VALUES ($1, $2, $3, $4, $5)",
+        Uuid::new_v4(),
+        account_id,
+        transaction_id,
+        previous_balance,
+        balance
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(true)
+}
+
+/// Ziska upozornenia na zaporny zostatok od najnovsieho
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - query: volitelny filter uctu a limit
+///
+/// # Chyby
+/// - BankError::InvalidBody: limit mimo 1..=MAX_ALERT_LIMIT
+pub async fn list_negative_balance_alerts(
+    pool: &PgPool,
+    query: &NegativeBalanceAlertQuery,
+) -> Result<Vec<NegativeBalanceAlert>, BankError> {
+    let limit = query.limit.unwrap_or(DEFAULT_ALERT_LIMIT);
+    if !(1..=MAX_ALERT_LIMIT).contains(&limit) {
+        return Err(BankError::InvalidBody(format!(
+            "limit must be between 1 and {MAX_ALERT_LIMIT}"
+        )));
+    }
+
+    let rows = query!(
+        "SELECT id, account_id, transaction_id, previous_balance, balance, created_at
+         FROM negative_balance_alerts
+         WHERE ($1::uuid IS NULL OR account_id = $1)
+         ORDER BY created_at DESC, id
+         LIMIT $2",
+        query.account,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| NegativeBalanceAlert {
+            id: row.id,
+            account_id: row.account_id,
+            transaction_id: row.transaction_id,
+            previous_balance: row.previous_balance,
+            balance: row.balance,
+            created_at: row.created_at,
+        })
+        .collect())
+}
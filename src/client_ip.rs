@@ -0,0 +1,200 @@
+// client_ip.rs
+use crate::state::AppState;
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+/// Hlavicka s retazcom adries klienta a proxy serverov
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Rozsah adries (CIDR) doveryhodneho proxy servera, napr. 10.0.0.0/8
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedProxy {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl TrustedProxy {
+    /// Rozparsuje CIDR alebo samostatnu adresu (ta ma prefix /32, resp. /128)
+    pub fn parse(value: &str) -> Option<Self> {
+        let (address, prefix) = match value.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value.trim(), None),
+        };
+
+        let network: IpAddr = address.parse().ok()?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix {
+            Some(prefix) => prefix.parse().ok().filter(|len| *len <= max_len)?,
+            None => max_len,
+        };
+
+        Some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Ci adresa patri do rozsahu
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Urci skutocnu adresu klienta
+///
+/// # Parametre
+/// - peer: adresa TCP spojenia
+/// - forwarded_for: hodnota hlavicky X-Forwarded-For
+/// - trusted: doveryhodne proxy servery
+///
+/// # Navratova hodnota
+/// Ak spojenie neprislo od doveryhodnej proxy, vracia `peer` (hlavicku mohol
+/// podvrhnut klient). Inak prechadza X-Forwarded-For sprava dolava a vracia prvu
+/// adresu, ktora nie je doveryhodna proxy.
+pub fn resolve_client_ip(
+    peer: IpAddr,
+    forwarded_for: Option<&str>,
+    trusted: &[TrustedProxy],
+) -> IpAddr {
+    let is_trusted = |ip: IpAddr| trusted.iter().any(|proxy| proxy.contains(ip));
+
+    if !is_trusted(peer) {
+        return peer;
+    }
+
+    let chain: Vec<IpAddr> = forwarded_for
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|part| part.trim().parse().ok())
+        .collect();
+
+    chain
+        .iter()
+        .rev()
+        .copied()
+        .find(|ip| !is_trusted(*ip))
+        .or_else(|| chain.first().copied())
+        .unwrap_or(peer)
+}
+
+/// Extractor skutocnej adresy klienta (pozri resolve_client_ip)
+///
+/// None, ak server nebezi s `into_make_service_with_connect_info`.
+/// Doveryhodne proxy su z Config::trusted_proxies (TRUSTED_PROXIES).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(ConnectInfo(peer)) = parts.extensions.get::<ConnectInfo<SocketAddr>>() else {
+            return Ok(ClientIp(None));
+        };
+
+        let forwarded_for = parts
+            .headers
+            .get(FORWARDED_FOR_HEADER)
+            .and_then(|v| v.to_str().ok());
+
+        Ok(ClientIp(Some(resolve_client_ip(
+            peer.ip(),
+            forwarded_for,
+            &AppState::from_ref(state).config.trusted_proxies,
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn proxies(values: &[&str]) -> Vec<TrustedProxy> {
+        values
+            .iter()
+            .map(|v| TrustedProxy::parse(v).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parses_cidr_ranges_and_single_addresses() {
+        let range = TrustedProxy::parse("10.0.0.0/8").unwrap();
+        assert!(range.contains(ip("10.20.30.40")));
+        assert!(!range.contains(ip("11.0.0.1")));
+        assert!(!range.contains(ip("::1")));
+
+        let single = TrustedProxy::parse("127.0.0.1").unwrap();
+        assert!(single.contains(ip("127.0.0.1")));
+        assert!(!single.contains(ip("127.0.0.2")));
+
+        assert!(
+            TrustedProxy::parse("fd00::/8")
+                .unwrap()
+                .contains(ip("fd12::1"))
+        );
+        for invalid in ["10.0.0.0/33", "proxy", "10.0.0.0/x"] {
+            assert_eq!(TrustedProxy::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn forwarded_for_from_untrusted_peer_is_ignored() {
+        let peer = ip("203.0.113.7");
+        let spoofed = Some("1.2.3.4");
+
+        assert_eq!(resolve_client_ip(peer, spoofed, &[]), peer);
+        assert_eq!(
+            resolve_client_ip(peer, spoofed, &proxies(&["10.0.0.0/8"])),
+            peer
+        );
+    }
+
+    #[test]
+    fn forwarded_for_from_trusted_proxy_is_read_from_the_right() {
+        let trusted = proxies(&["10.0.0.0/8"]);
+        let proxy = ip("10.0.0.1");
+
+        // Klient si na zaciatok retazca podvrhol adresu, skutocna je posledna nedoveryhodna
+        let chain = Some("1.2.3.4, 198.51.100.9, 10.0.0.2");
+        assert_eq!(
+            resolve_client_ip(proxy, chain, &trusted),
+            ip("198.51.100.9")
+        );
+
+        // Cely retazec su proxy - pouzije sa prva adresa
+        assert_eq!(
+            resolve_client_ip(proxy, Some("10.1.1.1, 10.0.0.2"), &trusted),
+            ip("10.1.1.1")
+        );
+        // Bez hlavicky ostava adresa spojenia
+        assert_eq!(resolve_client_ip(proxy, None, &trusted), proxy);
+    }
+}
@@ -1,38 +1,69 @@
 // crud.rs
-use crate::db::create_pool;
+use crate::account_rules::AccountTypeRule;
+use crate::archive::get_transaction_history_with_archive;
+use crate::auth::ClientInfo;
+use crate::balance_alerts::record_negative_balance;
+use crate::config::Config;
+use crate::error::BankError;
+use crate::idempotency::{IdempotencyScope, archived_deposit_exists, release_expired_key};
+use crate::ids::{format_reference, new_transaction_id};
+use crate::isolation::retry_on_serialization_failure;
 use crate::models::PublicUser;
-use crate::{PubAccount, Transaction};
-use argon2::PasswordHash;
-use argon2::PasswordVerifier;
-use argon2::password_hash::SaltString;
-use argon2::password_hash::rand_core::OsRng;
-use argon2::{self, Argon2, password_hash::PasswordHasher};
+use crate::notifications::{NewNotification, crosses_below, notify, transfer_detail};
+use crate::password::{hash_password, verify_password};
+use crate::rates::RateProvider;
+use crate::rounding::MONEY_SCALE;
+use crate::sanitize::{normalize_tag, sanitize_optional_note};
+use crate::{
+    AccountBalance, AccountBatch, AccountFilter, AccountList, AccountMerge, AccountPin,
+    AccountStats, AccountStatus, AccountType, ApiKeyInfo, BalanceDiscrepancy, BatchDeposit,
+    BatchReversal, BatchTransferItem, CancelledTransfer, CancelledTransferKind, Counterparty,
+    CreatedApiKey, CurrencyBalanceTotal, CurrencyVolume, DepositBatch, DepositHold,
+    DuplicatePolicy, EmailStatus, EmailVerificationSent, FinalStatement, ImportReport,
+    ImportRowResult, ImportRowStatus, LoginAttempt, LoginHistoryPage, LoginHistoryQuery,
+    LowBalanceThreshold, MultiSourceTransfer, MultiSourceTransferRequest, NotificationEvent,
+    PubAccount, SortOrder, SystemStats, Transaction, TransactionParties, TransactionRequest,
+    TransactionSplit, TransactionTags, TransferBatch, TransferInitiation, TransferPreview,
+    TransferPreviewError,
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sha2::{Digest, Sha256};
 use sqlx::query;
+use sqlx::{PgConnection, PgPool, Postgres};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Vytvori noveho pouzivatela a zahashuje heslo
 ///
 /// # Parametre
+/// - pool: connection pool databazy
 /// - name: pouzivatelske meno (musi byt unikatne)
-/// - password: heslo v plain texte (bude zahashovane pomocou Argon2)
+/// - password: heslo v plain texte (bude zahashovane podla PASSWORD_HASHER, predvolene Argon2)
 ///
 /// # Navratova hodnota
-/// Vracia PublicUser (bez hesla) alebo chybu ak pouzivatel uz existuje
+/// Vracia PublicUser (bez hesla)
+///
+/// # Chyby
+/// - BankError::InvalidBody: heslo nesplna PasswordPolicy (PASSWORD_MIN_LENGTH, PASSWORD_REQUIRE_*)
+/// - BankError::AlreadyExists: meno je obsadene
+/// - BankError::PasswordHashing: heslo sa nepodarilo zahashovat
 ///
 /// # Bezpecnost
-/// Heslo je zahashovane pomocou Argon2 s nahodnou solu pred ulozenim do databazy
-pub async fn create_user(name: &str, password: &str) -> Result<PublicUser, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
-
-    // Hashovanie hesla pomocou Argon2
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(password.as_bytes(), &salt)
-        .unwrap()
-        .to_string();
+/// Heslo je zahashovane s nahodnou solou pred ulozenim do databazy.
+/// Algoritmus (a pri Argon2 varianta a verzia) sa beru z PasswordConfig (predvolene Argon2id).
+pub async fn create_user(
+    pool: &PgPool,
+    config: &Config,
+    name: &str,
+    password: &str,
+) -> Result<PublicUser, BankError> {
+    config.password_policy.check(password)?;
+
+    // Hashovanie hesla podla konfiguracie (PASSWORD_HASHER)
+    let password_hash = hash_password(&config.password, password)?;
 
     // Generovanie UUID pre noveho pouzivatela
     let user_id = Uuid::new_v4();
@@ -43,7 +74,7 @@ pub async fn create_user(name: &str, password: &str) -> Result<PublicUser, sqlx:
         name,
         password_hash
     )
-    .fetch_one(&pool)
+    .fetch_one(pool)
     .await?;
 
     Ok(PublicUser {
@@ -52,18 +83,122 @@ pub async fn create_user(name: &str, password: &str) -> Result<PublicUser, sqlx:
     })
 }
 
+/// Hromadne vytvori pouzivatelov z CSV (`username,password` na riadok)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - csv: obsah CSV; prazdne riadky a hlavicka `username,password` sa preskocia
+/// - policy: spravanie pri duplicitnom mene alebo neplatnom riadku
+///
+/// # Navratova hodnota
+/// Vracia ImportReport s vysledkom kazdeho riadku
+///
+/// # Poznamka
+/// Vsetky riadky sa vkladaju v jednej databazovej transakcii. Duplicitne meno
+/// (`ON CONFLICT DO NOTHING`) transakciu neprerusi, len sa nahlasi.
+/// Pri DuplicatePolicy::Abort sa pri akejkolvek chybe cely import stornuje
+/// a committed = false. Heslo je cast riadku za prvou ciarkou, moze teda
+/// obsahovat ciarky; uvodzovky CSV sa nespracuvaju. Riadok s heslom, ktore
+/// nesplna PasswordPolicy, je neplatny rovnako ako pri registracii.
+///
+/// # Chyby
+/// - BankError::PasswordHashing: heslo sa nepodarilo zahashovat (import sa prerusi)
+///
+/// # Bezpecnost
+/// Hesla sa hashuju rovnako ako pri registracii (PasswordConfig).
+pub async fn import_users(
+    pool: &PgPool,
+    config: &Config,
+    csv: &str,
+    policy: DuplicatePolicy,
+) -> Result<ImportReport, BankError> {
+    let password_policy = &config.password_policy;
+    let mut tx = pool.begin().await?;
+
+    let mut rows = Vec::new();
+
+    for (index, line) in csv.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || (line_no == 1 && line.eq_ignore_ascii_case("username,password")) {
+            continue;
+        }
+
+        let (username, password) = match line.split_once(',') {
+            Some((u, p)) if !u.trim().is_empty() && password_policy.check(p).is_ok() => {
+                (u.trim(), p)
+            }
+            _ => {
+                rows.push(ImportRowResult {
+                    line: line_no,
+                    username: line
+                        .split(',')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                    status: ImportRowStatus::Invalid,
+                    id: None,
+                });
+                continue;
+            }
+        };
+
+        // Hashovanie rovnako ako v create_user
+        let password_hash = hash_password(&config.password, password)?;
+
+        let inserted = query!(
+            "INSERT INTO users (id, username, password_hash) VALUES ($1, $2, $3)
+             ON CONFLICT (username) DO NOTHING
+             RETURNING id",
+            Uuid::new_v4(),
+            username,
+            password_hash
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        rows.push(ImportRowResult {
+            line: line_no,
+            username: username.to_string(),
+            status: match inserted {
+                Some(_) => ImportRowStatus::Created,
+                None => ImportRowStatus::Duplicate,
+            },
+            id: inserted.map(|row| row.id),
+        });
+    }
+
+    let created = rows.iter().filter(|r| r.id.is_some()).count();
+    let failed = rows.len() - created;
+
+    let committed = !(policy == DuplicatePolicy::Abort && failed > 0);
+    if committed {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+    }
+
+    Ok(ImportReport {
+        committed,
+        created,
+        failed,
+        rows,
+    })
+}
+
 /// Ziska pouzivatela podla jeho ID
 ///
 /// # Parametre
+/// - pool: connection pool databazy
 /// - user_id: UUID pouzivatela
 ///
 /// # Navratova hodnota
 /// Vracia PublicUser alebo chybu ak pouzivatel neexistuje
-pub async fn get_user(user_id: Uuid) -> Result<PublicUser, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
-
+pub async fn get_user(pool: &PgPool, user_id: Uuid) -> Result<PublicUser, sqlx::Error> {
     let row = query!("SELECT id, username FROM users WHERE id=$1", user_id)
-        .fetch_one(&pool)
+        .fetch_one(pool)
         .await?;
 
     Ok(PublicUser {
@@ -72,277 +207,4175 @@ pub async fn get_user(user_id: Uuid) -> Result<PublicUser, sqlx::Error> {
     })
 }
 
+/// Ziska pouzivatela podla mena bez ohladu na velkost pismen
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - username: meno pouzivatela (napr. pred prevodom na meno)
+///
+/// # Navratova hodnota
+/// Vracia PublicUser (len id a meno, ziadne citlive udaje)
+///
+/// # Poznamka
+/// Presna zhoda ma prednost. Ak sa meno lisi len velkostou pismen od viacerych
+/// pouzivatelov (napr. "Alice" a "ALICE" pri hladani "alice"), nevrati sa ziadny,
+/// aby sa peniaze neposlali nespravnemu pouzivatelovi.
+///
+/// # Chyby
+/// - BankError::UserNotFound: pouzivatel neexistuje alebo zhoda nie je jednoznacna
+pub async fn get_user_by_username(pool: &PgPool, username: &str) -> Result<PublicUser, BankError> {
+    let rows = query!(
+        r#"SELECT id, username, username = $1 AS "exact!" FROM users
+           WHERE LOWER(username) = LOWER($1)
+           ORDER BY username = $1 DESC
+           LIMIT 2"#,
+        username.trim()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    match rows.as_slice() {
+        [first, ..] if first.exact => Ok(PublicUser {
+            id: first.id,
+            username: first.username.clone(),
+        }),
+        [only] => Ok(PublicUser {
+            id: only.id,
+            username: only.username.clone(),
+        }),
+        _ => Err(BankError::UserNotFound),
+    }
+}
+
 /// Zmaze pouzivatela a vsetky jeho ucty
 ///
 /// # Parametre
+/// - pool: connection pool databazy
 /// - user_id: UUID pouzivatela na zmazanie
 ///
 /// # Navratova hodnota
 /// Vracia pocet zmazanych riadkov (0 ak pouzivatel neexistoval)
 ///
 /// # Poznamka
-/// Najprv su zmazane vsetky ucty pouzivatela, potom samotny pouzivatel
-pub async fn delete_user(user_id: Uuid) -> Result<u64, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
+/// Najprv su zmazane vsetky ucty pouzivatela, potom samotny pouzivatel.
+/// Historia transakcii protistran ostava zachovana, odkaz na zmazany ucet je NULL.
+///
+/// # Konzistencia
+/// Ucty pouzivatela su zamknute (FOR UPDATE), takze prebiehajuci prevod
+/// na/z tychto uctov sa bud dokonci pred zmazanim (a zmazanie potom odmietne
+/// nenulovy zostatok), alebo po zmazani skonci chybou AccountNotFound.
+///
+/// # Chyby
+/// - BankError::AccountHasFunds: niektory ucet pouzivatela ma nenulovy zostatok
+/// - BankError::AccountHasHolds: na niektorom ucte su cakajuce prevody alebo blokovane vklady
+/// - BankError::AccountHasScheduledTransfers: niektory ucet ma aktivne trvale prikazy
+///   (pri cancel_scheduled sa nepotvrdene prevody a trvale prikazy zrusia)
+pub async fn delete_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    cancel_scheduled: bool,
+) -> Result<u64, BankError> {
+    let mut tx = pool.begin().await?;
+
+    // Zamknutie uctov pouzivatela - serializuje zmazanie so subeznymi prevodmi
+    let accounts = query!(
+        "SELECT id, balance FROM accounts WHERE user_id = $1 ORDER BY id FOR UPDATE",
+        user_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Peniaze na uctoch by zmazanim zanikli
+    if accounts.iter().any(|a| !a.balance.is_zero()) {
+        return Err(BankError::AccountHasFunds);
+    }
+
+    for account in &accounts {
+        ensure_nothing_scheduled(&mut tx, account.id, cancel_scheduled).await?;
+    }
 
     // Najprv zmazeme vsetky ucty pouzivatela
     query!("DELETE FROM accounts WHERE user_id = $1", user_id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await?;
 
     // Potom zmazeme samotneho pouzivatela
     let result = query!("DELETE FROM users WHERE id = $1", user_id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await?;
 
+    tx.commit().await?;
+
     Ok(result.rows_affected())
 }
 
 /// Vytvori novy bankovy ucet pre pouzivatela
 ///
 /// # Parametre
+/// - pool: connection pool databazy
 /// - user_id: UUID pouzivatela, pre ktoreho sa ma ucet vytvorit
+/// - currency: mena uctu (ISO 4217), None = Config::default_currency (predvolene EUR)
+/// - account_type: typ uctu
+/// - initial_deposit: pociatocny zostatok (Decimal::ZERO = prazdny ucet)
 ///
 /// # Navratova hodnota
-/// Vracia PubAccount s nulovou pociatocnou bilanciou
-pub async fn create_account(user_id: Uuid) -> Result<PubAccount, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
+/// Vracia PubAccount s pociatocnym zostatkom a novym cislom uctu
+/// (format podla Config::account_numbers)
+///
+/// # Otvorenie uctu
+/// V tej istej transakcii sa zapise zaznam o otvoreni uctu (account_opening, bez odosielatela)
+/// so sumou pociatocneho zostatku - aj nulovou - takze historia kazdeho uctu zacina
+/// jasnym bodom. Pociatocny vklad podlieha blokacii velkych vkladov (deposit_hold)
+/// a rozsahu sum meny (AMOUNT_BOUNDS).
+///
+/// # Schvalovanie
+/// Ucty typov uvedenych v ACCOUNT_TYPES_REQUIRING_APPROVAL vznikaju v stave pending
+/// a kym ich administrator neschvali (approve_account), nemozno s nimi hybat penazmi.
+///
+/// # Limit
+/// Pouzivatel moze mat najviac MAX_ACCOUNTS_PER_USER (predvolene 10) nezatvorenych uctov.
+/// Riadok pouzivatela je pocas kontroly zamknuty, takze subezne vytvaranie limit neobide.
+///
+/// # Chyby
+/// - BankError::InvalidCurrency: kod meny nema tvar ISO 4217 (3 pismena)
+/// - BankError::ReferenceNotFound("user"): pouzivatel neexistuje
+/// - BankError::LimitExceeded: pouzivatel dosiahol maximalny pocet uctov
+/// - BankError::InvalidAmount: zaporny pociatocny zostatok alebo viac ako 2 desatinne miesta
+/// - BankError::AmountOutOfBounds: pociatocny vklad mimo rozsahu AMOUNT_BOUNDS
+/// - BankError::AccountPending: nenulovy pociatocny vklad na ucet, ktory caka na schvalenie
+pub async fn create_account(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    currency: Option<&str>,
+    account_type: AccountType,
+    initial_deposit: Decimal,
+) -> Result<PubAccount, BankError> {
+    if initial_deposit < Decimal::ZERO || initial_deposit.scale() > MONEY_SCALE {
+        return Err(BankError::InvalidAmount);
+    }
+    let currency = match currency {
+        Some(currency) => normalize_currency(currency)?,
+        None => config.default_currency.clone(),
+    };
+    let status = if requires_approval(config, account_type) {
+        AccountStatus::Pending
+    } else {
+        AccountStatus::Active
+    };
+    if initial_deposit > Decimal::ZERO {
+        // Na neschvaleny ucet sa nevklada - ani pri otvoreni
+        ensure_account_active(status)?;
+        check_amount_bounds(config, &currency, initial_deposit)?;
+    }
+
+    // Doba blokacie pociatocneho vkladu (None = vklad je k dispozicii hned)
+    let hold_secs = deposit_hold(config)
+        .filter(|(threshold, _)| initial_deposit > Decimal::ZERO && initial_deposit >= *threshold)
+        .map(|(_, hold)| hold.as_secs_f64());
+
+    let mut tx = pool.begin().await?;
     let account_id = Uuid::new_v4();
 
+    // Zamknutie pouzivatela - serializuje subezne vytvaranie uctov
+    query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(BankError::ReferenceNotFound("user"))?;
+
+    let open_accounts = query!(
+        r#"SELECT COUNT(*) AS "count!" FROM accounts WHERE user_id = $1 AND status <> 'closed'"#,
+        user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .count;
+
+    if open_accounts >= config.max_accounts_per_user {
+        return Err(BankError::LimitExceeded);
+    }
+
+    // Nahodne cislo uctu - pri (velmi nepravdepodobnej) kolizii sa vygeneruje nove
+    let numbers = &config.account_numbers;
+    let row = loop {
+        let inserted = query!(
+            r#"INSERT INTO accounts (id, account_number, user_id, balance, currency, account_type, status)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT (account_number) DO NOTHING
+               RETURNING id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus""#,
+            account_id,
+            numbers.generate(),
+            user_id,
+            initial_deposit,
+            currency,
+            account_type as AccountType,
+            status as AccountStatus
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(row) = inserted {
+            break row;
+        }
+    };
+
+    // Zaznam o otvoreni uctu - povod pociatocneho zostatku v historii
+    query!(
+        "INSERT INTO transactions (id, from_account, to_account, amount, currency, available_at, account_opening)
+         VALUES ($1, NULL, $2, $3, $4, NOW() + make_interval(secs => $5), TRUE)",
+        new_transaction_id(config),
+        account_id,
+        initial_deposit,
+        currency,
+        hold_secs
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(PubAccount {
+        id: row.id,
+        account_number: row.account_number,
+        user_id: row.user_id,
+        balance: row.balance,
+        currency: row.currency,
+        account_type: row.account_type,
+        status: row.status,
+        display_balance: None,
+    })
+}
+
+/// Overi, ze s uctom mozno hybat penazmi (vklad, prevod)
+///
+/// # Chyby
+/// - BankError::AccountClosed: ucet je zatvoreny
+/// - BankError::AccountFrozen: ucet je zablokovany
+/// - BankError::AccountPending: ucet caka na schvalenie
+pub fn ensure_account_active(status: AccountStatus) -> Result<(), BankError> {
+    match status {
+        AccountStatus::Active => Ok(()),
+        AccountStatus::Frozen => Err(BankError::AccountFrozen),
+        AccountStatus::Closed => Err(BankError::AccountClosed),
+        AccountStatus::Pending => Err(BankError::AccountPending),
+    }
+}
+
+/// Zisti, ci novy ucet daneho typu musi schvalit administrator
+///
+/// # Konfiguracia
+/// - Config::account_types_requiring_approval: typy z ACCOUNT_TYPES_REQUIRING_APPROVAL,
+///   napr. "business,savings" (predvolene ziadny typ schvalenie nevyzaduje)
+pub fn requires_approval(config: &Config, account_type: AccountType) -> bool {
+    config
+        .account_types_requiring_approval
+        .contains(&account_type)
+}
+
+/// Rozhodne o ucte cakajucom na schvalenie (administrator)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - approve: true = ucet sa aktivuje, false = ucet sa zamietne (zatvori)
+///
+/// # Navratova hodnota
+/// Vracia PubAccount s novym stavom (active alebo closed)
+///
+/// # Poznamka
+/// Zamietnuty ucet sa nemaze, len zatvori - cislo uctu tak nemoze byt pridelene znovu.
+/// Ucet v stave pending nemohol prijat ziadne peniaze, takze zatvorenie nic nepresuva.
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::AccountNotPending: ucet necaka na schvalenie
+pub async fn decide_account_approval(
+    pool: &PgPool,
+    account_id: Uuid,
+    approve: bool,
+) -> Result<PubAccount, BankError> {
+    let new_status = if approve {
+        AccountStatus::Active
+    } else {
+        AccountStatus::Closed
+    };
+
     let row = query!(
-        "INSERT INTO accounts (id, user_id, balance) VALUES ($1,$2,$3) RETURNING id, user_id, balance",
+        r#"UPDATE accounts SET status = $2
+           WHERE id = $1 AND status = 'pending'
+           RETURNING id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus""#,
         account_id,
-        user_id,
-        Decimal::ZERO
-    ).fetch_one(&pool).await?;
+        new_status as AccountStatus
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        let exists = query!("SELECT id FROM accounts WHERE id = $1", account_id)
+            .fetch_optional(pool)
+            .await?;
+        return Err(match exists {
+            Some(_) => BankError::AccountNotPending,
+            None => BankError::AccountNotFound,
+        });
+    };
 
     Ok(PubAccount {
         id: row.id,
+        account_number: row.account_number,
         user_id: row.user_id,
         balance: row.balance,
+        currency: row.currency,
+        account_type: row.account_type,
+        status: row.status,
+        display_balance: None,
     })
 }
 
-/// Ziska vsetky ucty pouzivatela
+/// Predvolena mena novych uctov, ak nie je nastavena premenna DEFAULT_CURRENCY (Config)
+pub const DEFAULT_CURRENCY: &str = "EUR";
+
+/// Overi a normalizuje kod meny na tvar ISO 4217 (3 velke pismena)
+pub fn normalize_currency(currency: &str) -> Result<String, BankError> {
+    let currency = currency.trim().to_uppercase();
+
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(BankError::InvalidCurrency);
+    }
+
+    Ok(currency)
+}
+
+/// Ziska ucty pouzivatela
 ///
 /// # Parametre
+/// - pool: connection pool databazy
 /// - user_id: UUID pouzivatela
+/// - filter: volitelne filtre podla stavu, typu, meny a nuloveho zostatku
+///   (nezadany filter = bez obmedzenia)
 ///
 /// # Navratova hodnota
-/// Vracia zoznam vsetkych uctov pouzivatela (moze byt prazdny)
-pub async fn get_account(user_id: Uuid) -> Result<Vec<PubAccount>, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
+/// Vracia AccountList s uctami zoradenymi podla vytvorenia (moze byt prazdny)
+///
+/// # Konfiguracia
+/// - MAX_ACCOUNTS_LISTED: najvyssi pocet vratenych uctov (predvolene 500);
+///   ak ma pouzivatel viac uctov, zoznam sa oreze a `truncated` je true
+pub async fn get_account(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    filter: &AccountFilter,
+) -> Result<AccountList, sqlx::Error> {
+    let max_listed = config.max_accounts_listed;
 
+    let currency = filter.currency.as_deref().map(|c| c.trim().to_uppercase());
+
+    // O jeden riadok viac, aby sa dalo zistit, ci bol zoznam orezany
     let rows = query!(
-        "SELECT id, user_id, balance FROM accounts WHERE user_id=$1",
-        user_id
+        r#"SELECT id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts
+           WHERE user_id = $1
+             AND ($3::text IS NULL OR status = $3)
+             AND ($4::text IS NULL OR account_type = $4)
+             AND ($5::text IS NULL OR currency = $5)
+             AND ($6 OR balance <> 0)
+           ORDER BY created_at, id
+           LIMIT $2"#,
+        user_id,
+        max_listed + 1,
+        filter.status as Option<AccountStatus>,
+        filter.account_type as Option<AccountType>,
+        currency,
+        filter.include_empty.unwrap_or(true)
     )
-    .fetch_all(&pool)
+    .fetch_all(pool)
     .await?;
 
+    let truncated = rows.len() as i64 > max_listed;
+
     // Konvertovanie riadkov z databazy na PubAccount struktury
     let accounts = rows
         .into_iter()
+        .take(max_listed as usize)
         .map(|row| PubAccount {
             id: row.id,
+            account_number: row.account_number,
             user_id: row.user_id,
             balance: row.balance,
+            currency: row.currency,
+            account_type: row.account_type,
+            status: row.status,
+            display_balance: None,
         })
         .collect();
 
-    Ok(accounts)
-}
-
-/// Prida peniaze na ucet
-///
-/// # Parametre
-/// - account_id: UUID uctu
-/// - money: suma na pridanie (musi byt kladna)
-///
-/// # Navratova hodnota
-/// Vracia aktualizovany PubAccount s novou bilanciou
-pub async fn add_money(account_id: Uuid, money: Decimal) -> Result<PubAccount, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
-
-    let row = query!(
-        "UPDATE accounts SET balance=balance+$1 WHERE id=$2 RETURNING id, user_id, balance",
-        money,
-        account_id
-    )
-    .fetch_one(&pool)
-    .await?;
-
-    Ok(PubAccount {
-        id: row.id,
-        user_id: row.user_id,
-        balance: row.balance,
+    Ok(AccountList {
+        accounts,
+        truncated,
     })
 }
 
-/// Vytvori transakciu - prevod penazi medzi dvoma uctami
+/// Ziska viacero uctov jednym dotazom
 ///
 /// # Parametre
-/// - from_account: UUID uctu odosielatela
-/// - to_account: UUID uctu prijemcu
-/// - amount: suma prevodu
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela
+/// - ids: UUID pozadovanych uctov (duplicity sa ignoruju)
 ///
 /// # Navratova hodnota
-/// Vracia Transaction objekt alebo chybu
-///
-/// # Bezpecnost a validacia
-/// - Pouziva databazovu transakciu (BEGIN/COMMIT) pre ACID vlastnosti
-/// - Overuje ci ma odosielatel dostatocny zostatok
-/// - Pouziva FOR UPDATE zamok pre zabranenie race conditions
-/// - Ak akakolvek operacia zlyhava, vsetky zmeny su automaticky stornovane (ROLLBACK)
+/// Vracia AccountBatch - ucty volajuceho v poradi poziadavky, zvysne ID
+/// rozdelene na neexistujuce (not_found) a cudzie (forbidden)
 ///
 /// # Chyby
-/// - sqlx::Error::RowNotFound: nedostatocny zostatok na ucte odosielatela
-/// - Ine sqlx::Error: problemy s databazou alebo neexistujuce ucty
-pub async fn make_transaction(
-    from_account: Uuid,
-    to_account: Uuid,
-    amount: Decimal,
-) -> Result<Transaction, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
+/// - BankError::LimitExceeded: viac ID nez MAX_ACCOUNTS_LISTED (predvolene 500),
+///   pocitaju sa aj duplicity
+pub async fn get_accounts_batch(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    ids: &[Uuid],
+) -> Result<AccountBatch, BankError> {
+    // Limit sa overi este pred odstranenim duplicit - velky zoznam sa ani neprechadza
+    if ids.len() > config.max_accounts_listed as usize {
+        return Err(BankError::LimitExceeded);
+    }
 
-    // Zacatie databazovej transakcie - zabezpecuje atomicitu operacie
-    let mut tx = pool.begin().await?;
+    let mut seen: HashSet<Uuid> = HashSet::with_capacity(ids.len());
+    let unique: Vec<Uuid> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
 
-    // Kontrola zostatku odosielatela a zablokovanie riadku (FOR UPDATE)
-    let sender = query!(
-        "SELECT balance FROM accounts WHERE id = $1 FOR UPDATE",
-        from_account
+    let rows = query!(
+        r#"SELECT id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts WHERE id = ANY($1)"#,
+        &unique
     )
-    .fetch_one(&mut *tx)
+    .fetch_all(pool)
     .await?;
 
-    // Validacia - overenie dostatocneho zostatku
-    if sender.balance < amount {
-        return Err(sqlx::Error::RowNotFound);
+    let mut found: HashMap<Uuid, PubAccount> = rows
+        .into_iter()
+        .map(|row| {
+            let account = PubAccount {
+                id: row.id,
+                account_number: row.account_number,
+                user_id: row.user_id,
+                balance: row.balance,
+                currency: row.currency,
+                account_type: row.account_type,
+                status: row.status,
+                display_balance: None,
+            };
+            (row.id, account)
+        })
+        .collect();
+
+    let mut batch = AccountBatch::default();
+    for id in unique {
+        match found.remove(&id) {
+            Some(account) if account.user_id == user_id => batch.accounts.push(account),
+            Some(_) => batch.forbidden.push(id),
+            None => batch.not_found.push(id),
+        }
     }
 
-    // Odcitanie penazi z uctu odosielatela
-    query!(
-        "UPDATE accounts SET balance = balance - $1 WHERE id = $2",
-        amount,
-        from_account
-    )
-    .execute(&mut *tx)
-    .await?;
+    Ok(batch)
+}
 
-    // Pripocitanie penazi na ucet prijemcu
-    query!(
-        "UPDATE accounts SET balance = balance + $1 WHERE id = $2",
-        amount,
-        to_account
-    )
-    .execute(&mut *tx)
-    .await?;
+/// Nastavi hranicu nizkeho zostatku uctu
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - user_id: UUID prihlaseneho pouzivatela (musi byt vlastnikom uctu)
+/// - threshold: hranica, pod ktoru ked zostatok klesne, posle sa notifikacia LowBalance
+///   (None = upozornenie vypnute)
+///
+/// # Chyby
+/// - BankError::InvalidAmount: hranica je zaporna
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::Forbidden: ucet patri inemu pouzivatelovi
+pub async fn set_low_balance_threshold(
+    pool: &PgPool,
+    account_id: Uuid,
+    user_id: Uuid,
+    threshold: Option<Decimal>,
+) -> Result<LowBalanceThreshold, BankError> {
+    if threshold.is_some_and(|t| t < Decimal::ZERO) {
+        return Err(BankError::InvalidAmount);
+    }
 
-    // Vytvorenie zaznamu transakcie v tabulke
-    let trans_id = Uuid::new_v4();
-    let transaction = query!(
-        "INSERT INTO transactions (id, from_account, to_account, amount) 
-         VALUES ($1, $2, $3, $4) 
-         RETURNING id, from_account, to_account, amount, created_at",
-        trans_id,
-        from_account,
-        to_account,
-        amount
+    let row = query!(
+        "UPDATE accounts SET low_balance_threshold = $3
+         WHERE id = $1 AND user_id = $2
+         RETURNING low_balance_threshold",
+        account_id,
+        user_id,
+        threshold
     )
-    .fetch_one(&mut *tx)
+    .fetch_optional(pool)
     .await?;
 
-    // Potvrdenie transakcie - vsetky zmeny su trvale ulozene
-    // Ak nedojde k commit(), zmeny sa automaticky stornuju
-    tx.commit().await?;
+    let Some(row) = row else {
+        let exists = query!("SELECT id FROM accounts WHERE id = $1", account_id)
+            .fetch_optional(pool)
+            .await?;
+        return Err(match exists {
+            Some(_) => BankError::Forbidden,
+            None => BankError::AccountNotFound,
+        });
+    };
 
-    Ok(Transaction {
-        id: transaction.id,
-        from_account: transaction.from_account,
-        to_account: transaction.to_account,
-        amount: transaction.amount,
-        created_at: transaction.created_at,
+    Ok(LowBalanceThreshold {
+        account_id,
+        threshold: row.low_balance_threshold,
     })
 }
 
-/// Prihlasenie pouzivatela pomocou mena a hesla
+/// Povolena dlzka PIN-u uctu (pocet cislic)
+const PIN_LENGTH: std::ops::RangeInclusive<usize> = 4..=8;
+
+/// Nastavi alebo odstrani PIN uctu pre velke prevody
 ///
 /// # Parametre
-/// - username: pouzivatelske meno
-/// - password: heslo v plain texte
-///
-/// # Navratova hodnota
-/// Vracia PublicUser alebo String s chybovou spravou
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - user_id: UUID prihlaseneho pouzivatela (musi byt vlastnikom uctu)
+/// - pin: novy PIN (4 az 8 cislic), None = PIN sa odstrani
 ///
-/// # Bezpecnost
-/// - Heslo je overovane pomocou Argon2 verify funkcie
-/// - Nehashuje sa znovu, len sa porovna s ulozenim hashom
+/// # Poznamka
+/// Ulozi sa len hash PIN-u (rovnako ako pri hesle, PASSWORD_HASHER).
 ///
 /// # Chyby
-/// - "User not found": pouzivatel s danym menom neexistuje
-/// - "Invalid password hash": chyba pri parsovani hashu z databazy
-/// - "Invalid password": heslo sa nezhoduje
-pub async fn login_user(username: &str, password: &str) -> Result<PublicUser, String> {
-    let pool: PgPool = create_pool().await;
+/// - BankError::InvalidBody: PIN nema 4 az 8 cislic
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::Forbidden: ucet patri inemu pouzivatelovi
+/// - BankError::PasswordHashing: PIN sa nepodarilo zahashovat
+pub async fn set_account_pin(
+    pool: &PgPool,
+    config: &Config,
+    account_id: Uuid,
+    user_id: Uuid,
+    pin: Option<&str>,
+) -> Result<AccountPin, BankError> {
+    if let Some(pin) = pin
+        && (!PIN_LENGTH.contains(&pin.len()) || !pin.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(BankError::InvalidBody(
+            "pin must be 4 to 8 digits".to_string(),
+        ));
+    }
 
-    // Ziskanie pouzivatela z databazy
-    let user = query!(
-        "SELECT id, username, password_hash FROM users WHERE username = $1",
-        username
-    )
-    .fetch_one(&pool)
-    .await
-    .map_err(|_| "User not found".to_string())?;
+    let pin_hash = pin
+        .map(|pin| hash_password(&config.password, pin))
+        .transpose()?;
 
-    // Parsovanie hashu hesla z databazy
-    let parsed_hash =
-        PasswordHash::new(&user.password_hash).map_err(|_| "Invalid password hash".to_string())?;
+    let updated = query!(
+        "UPDATE accounts SET pin_hash = $3 WHERE id = $1 AND user_id = $2",
+        account_id,
+        user_id,
+        pin_hash
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
 
-    // Overenie hesla pomocou Argon2
-    Argon2::default()
-        .verify_password(password.as_bytes(), &parsed_hash)
-        .map_err(|_| "Invalid password".to_string())?;
+    if updated == 0 {
+        let exists = query!("SELECT id FROM accounts WHERE id = $1", account_id)
+            .fetch_optional(pool)
+            .await?;
+        return Err(match exists {
+            Some(_) => BankError::Forbidden,
+            None => BankError::AccountNotFound,
+        });
+    }
 
-    Ok(PublicUser {
-        id: user.id,
-        username: user.username,
+    Ok(AccountPin {
+        account_id,
+        pin_set: pin_hash.is_some(),
     })
 }
 
-/// Ziska historiu vsetkych transakci pre dany ucet
+/// Overi PIN uctu odosielatela pri prevode od Config::transfer_pin_threshold
 ///
 /// # Parametre
-/// - account_id: UUID uctu
-///
-/// # Navratova hodnota
-/// Vracia zoznam vsetkych transakci (odoslanych aj prijatych) zoradeny podla casu
+/// - pool: connection pool databazy
+/// - from_account: UUID uctu odosielatela
+/// - amount: suma prevodu
+/// - pin: PIN zadany v poziadavke
 ///
 /// # Poznamka
-/// Transakcie su zoradene zostupne podla created_at (najnovsie prvy)
-pub async fn get_transaction_history(account_id: Uuid) -> Result<Vec<Transaction>, sqlx::Error> {
-    let pool: PgPool = create_pool().await;
+/// Prevody pod hranicou a z uctov bez PIN-u sa neoveruju (zadany PIN sa ignoruje).
+/// Neexistujuci ucet sa preskoci - chybu hlasi prevod.
+///
+/// # Chyby
+/// - BankError::PinRequired: ucet ma PIN, ale poziadavka ho neobsahuje
+/// - BankError::InvalidPin: PIN nesedi
+pub async fn verify_transfer_pin(
+    pool: &PgPool,
+    config: &Config,
+    from_account: Uuid,
+    amount: Decimal,
+    pin: Option<&str>,
+) -> Result<(), BankError> {
+    if config
+        .transfer_pin_threshold
+        .is_none_or(|threshold| amount < threshold)
+    {
+        return Ok(());
+    }
 
+    let pin_hash = query!("SELECT pin_hash FROM accounts WHERE id = $1", from_account)
+        .fetch_optional(pool)
+        .await?
+        .and_then(|row| row.pin_hash);
+
+    let Some(pin_hash) = pin_hash else {
+        return Ok(());
+    };
+    let pin = pin.ok_or(BankError::PinRequired)?;
+
+    // Poskodeny hash sa sprava ako nespravny PIN
+    if !verify_password(pin, &pin_hash).unwrap_or(false) {
+        return Err(BankError::InvalidPin);
+    }
+
+    Ok(())
+}
+
+/// Zatvori ucet a vrati zaverecny vypis
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - user_id: UUID prihlaseneho pouzivatela (musi byt vlastnikom uctu)
+/// - sweep_to: ucet, na ktory sa prevedie zostatok (None = ucet musi mat nulovy zostatok)
+/// - cancel_scheduled: zrusit nepotvrdene prevody a trvale prikazy uctu namiesto odmietnutia
+///
+/// # Navratova hodnota
+/// Vracia FinalStatement s historiou transakcii (vratane archivu) a prevodom zostatku
+///
+/// # Poznamka
+/// Prevod zostatku aj zmena stavu prebiehaju v jednej databazovej transakcii
+/// so zamknutym uctom. Za aktivne blokacie sa povazuju nepotvrdene prevody
+/// (pending_transfers), ktorych platnost este nevyprsala, a blokovane vklady.
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::Forbidden: ucet patri inemu pouzivatelovi
+/// - BankError::AccountClosed: ucet uz je zatvoreny
+/// - BankError::AccountHasHolds: na ucte su cakajuce prevody alebo blokovane vklady
+/// - BankError::AccountHasScheduledTransfers: ucet ma aktivne trvale prikazy
+/// - BankError::AccountHasFunds: ucet ma zostatok a nebol zadany ucet na prevod
+/// - BankError::AccountOverdrawn: ucet ma zaporny zostatok (dlh treba najprv vyrovnat)
+/// - BankError::CurrencyMismatch: ucet na prevod zostatku ma inu menu
+pub async fn close_account(
+    pool: &PgPool,
+    config: &Config,
+    account_id: Uuid,
+    user_id: Uuid,
+    sweep_to: Option<Uuid>,
+    cancel_scheduled: bool,
+) -> Result<FinalStatement, BankError> {
+    let mut tx = pool.begin().await?;
+
+    // Ucet aj ucet na prevod zostatku sa zamykaju v poradi podla ID
+    let locked: Vec<Uuid> = [account_id].into_iter().chain(sweep_to).collect();
+    lock_accounts(&mut tx, &locked).await?;
+
+    let account = query!(
+        r#"SELECT user_id, balance, currency, status AS "status: AccountStatus"
+           FROM accounts WHERE id = $1 FOR UPDATE"#,
+        account_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    if account.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+    if account.status == AccountStatus::Closed {
+        return Err(BankError::AccountClosed);
+    }
+
+    ensure_nothing_scheduled(&mut tx, account_id, cancel_scheduled).await?;
+
+    // Dlh precerpaneho uctu nesmie zatvorenim zaniknut
+    if account.balance < Decimal::ZERO {
+        return Err(BankError::AccountOverdrawn);
+    }
+
+    // Prevod zostatku pred zatvorenim - peniaze nesmu zaniknut
+    let sweep = if account.balance > Decimal::ZERO {
+        match sweep_to {
+            Some(target) if target != account_id => Some(
+                execute_internal_transfer(&mut tx, config, account_id, target, account.balance)
+                    .await?,
+            ),
+            _ => return Err(BankError::AccountHasFunds),
+        }
+    } else {
+        None
+    };
+
+    let closed_at = query!(
+        "UPDATE accounts SET status = 'closed' WHERE id = $1 RETURNING NOW() AS \"closed_at!\"",
+        account_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .closed_at;
+
+    tx.commit().await?;
+
+    let transactions =
+        get_transaction_history_with_archive(pool, account_id, SortOrder::Desc).await?;
+    let total_credits = transactions
+        .iter()
+        .filter(|t| t.to_account == Some(account_id))
+        .map(|t| t.credited_amount.unwrap_or(t.amount))
+        .sum();
+    let total_debits = transactions
+        .iter()
+        .filter(|t| t.from_account == Some(account_id))
+        .map(|t| t.amount)
+        .sum();
+
+    Ok(FinalStatement {
+        account_id,
+        currency: account.currency,
+        closed_at,
+        total_credits,
+        total_debits,
+        closing_balance: Decimal::ZERO,
+        sweep,
+        transactions,
+    })
+}
+
+/// Ci su na ucte aktivne blokacie - nepotvrdene prevody (pending_transfers),
+/// ktorych platnost este nevyprsala, alebo blokovane vklady
+async fn account_has_holds(conn: &mut PgConnection, account_id: Uuid) -> Result<bool, sqlx::Error> {
+    let has_holds = query!(
+        r#"SELECT EXISTS(
+               SELECT 1 FROM pending_transfers
+               WHERE (from_account = $1 OR to_account = $1)
+                 AND status = 'pending' AND expires_at > NOW()
+           ) OR EXISTS(
+               SELECT 1 FROM transactions
+               WHERE to_account = $1 AND from_account IS NULL AND available_at > NOW()
+           ) AS "exists!""#,
+        account_id
+    )
+    .fetch_one(conn)
+    .await?
+    .exists;
+
+    Ok(has_holds)
+}
+
+/// Ci ma ucet aktivne trvale prikazy - z uctu alebo na ucet (aj od inych pouzivatelov)
+async fn account_has_scheduled_transfers(
+    conn: &mut PgConnection,
+    account_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let scheduled = query!(
+        r#"SELECT EXISTS(
+               SELECT 1 FROM recurring_transfers
+               WHERE (from_account = $1 OR to_account = $1) AND active
+           ) AS "exists!""#,
+        account_id
+    )
+    .fetch_one(conn)
+    .await?
+    .exists;
+
+    Ok(scheduled)
+}
+
+/// Overi, ze na ucte nic necaka, pred jeho zatvorenim alebo zmazanim
+///
+/// # Parametre
+/// - account_id: zatvarany ucet (uz zamknuty)
+/// - cancel: najprv zrusit nepotvrdene prevody (stav cancelled) a deaktivovat trvale prikazy
+///
+/// # Poznamka
+/// Blokovane vklady sa zrusit nedaju (peniaze uz su na ucte), tie brania zatvoreniu vzdy.
+///
+/// # Chyby
+/// - BankError::AccountHasHolds: cakajuce prevody alebo blokovane vklady
+/// - BankError::AccountHasScheduledTransfers: aktivne trvale prikazy
+async fn ensure_nothing_scheduled(
+    conn: &mut PgConnection,
+    account_id: Uuid,
+    cancel: bool,
+) -> Result<(), BankError> {
+    if cancel {
+        query!(
+            "UPDATE pending_transfers SET status = 'cancelled'
+             WHERE (from_account = $1 OR to_account = $1) AND status = 'pending'",
+            account_id
+        )
+        .execute(&mut *conn)
+        .await?;
+        query!(
+            "UPDATE recurring_transfers SET active = FALSE
+             WHERE (from_account = $1 OR to_account = $1) AND active",
+            account_id
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if account_has_holds(conn, account_id).await? {
+        return Err(BankError::AccountHasHolds);
+    }
+    if account_has_scheduled_transfers(conn, account_id).await? {
+        return Err(BankError::AccountHasScheduledTransfers);
+    }
+    Ok(())
+}
+
+/// Zluci ucet do ineho uctu toho isteho vlastnika
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela (musi vlastnit oba ucty)
+/// - source: zlucovany ucet - po zluceni je zatvoreny
+/// - target: ucet, na ktory prejde zostatok
+/// - cancel_scheduled: zrusit nepotvrdene prevody a trvale prikazy zdrojoveho uctu
+///
+/// # Navratova hodnota
+/// Vracia AccountMerge s cielovym uctom po zluceni a prevodom zostatku
+///
+/// # Poznamka
+/// Prevod zostatku, zatvorenie zdrojoveho uctu a odkaz `merged_into` na cielovy ucet
+/// prebiehaju v jednej databazovej transakcii s oboma uctami zamknutymi. Historia
+/// zdrojoveho uctu zostava pri nom (transakcie sa neprepisuju), cez `merged_into`
+/// je dohladatelne, kam ucet presiel.
+///
+/// # Chyby
+/// - BankError::InvalidBody: zdrojovy a cielovy ucet su rovnake alebo maju rozne meny
+/// - BankError::AccountNotFound: niektory ucet neexistuje
+/// - BankError::Forbidden: niektory ucet patri inemu pouzivatelovi
+/// - BankError::AccountClosed / AccountFrozen / AccountPending: niektory ucet nie je aktivny
+/// - BankError::AccountHasHolds: na zdrojovom ucte su cakajuce prevody alebo blokovane vklady
+/// - BankError::AccountHasScheduledTransfers: zdrojovy ucet ma aktivne trvale prikazy
+/// - BankError::AccountOverdrawn: zdrojovy ucet ma zaporny zostatok
+pub async fn merge_accounts(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    source: Uuid,
+    target: Uuid,
+    cancel_scheduled: bool,
+) -> Result<AccountMerge, BankError> {
+    if source == target {
+        return Err(BankError::InvalidBody(
+            "target_account must not be the merged account".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    lock_accounts(&mut tx, &[source, target]).await?;
+
+    let accounts = query!(
+        r#"SELECT id, user_id, balance, currency, status AS "status: AccountStatus"
+           FROM accounts WHERE id = ANY($1)"#,
+        &[source, target][..]
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let find = |id: Uuid| {
+        accounts
+            .iter()
+            .find(|account| account.id == id)
+            .ok_or(BankError::AccountNotFound)
+    };
+    let (from, to) = (find(source)?, find(target)?);
+
+    if from.user_id != user_id || to.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+    ensure_account_active(from.status)?;
+    ensure_account_active(to.status)?;
+    if from.currency != to.currency {
+        return Err(BankError::InvalidBody(format!(
+            "target_account currency must be {}",
+            from.currency
+        )));
+    }
+    ensure_nothing_scheduled(&mut tx, source, cancel_scheduled).await?;
+    if from.balance < Decimal::ZERO {
+        return Err(BankError::AccountOverdrawn);
+    }
+
+    let transfer = if from.balance > Decimal::ZERO {
+        Some(execute_internal_transfer(&mut tx, config, source, target, from.balance).await?)
+    } else {
+        None
+    };
+
+    let merged_at = query!(
+        r#"UPDATE accounts SET status = 'closed', merged_into = $2 WHERE id = $1
+           RETURNING NOW() AS "merged_at!""#,
+        source,
+        target
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .merged_at;
+
+    let row = query!(
+        r#"SELECT id, account_number, user_id, balance, currency,
+                  account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts WHERE id = $1"#,
+        target
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(AccountMerge {
+        source_account: source,
+        target: PubAccount {
+            id: row.id,
+            account_number: row.account_number,
+            user_id: row.user_id,
+            balance: row.balance,
+            currency: row.currency,
+            account_type: row.account_type,
+            status: row.status,
+            display_balance: None,
+        },
+        merged_at,
+        transfer,
+    })
+}
+
+/// Najde ucet podla cisla uctu
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - number: cislo uctu (napr. BK12345678903), overuje sa podla Config::account_numbers
+///
+/// # Navratova hodnota
+/// Vracia PubAccount
+///
+/// # Chyby
+/// - BankError::InvalidAccountNumber: cislo nema platny tvar alebo kontrolne cislice
+/// - BankError::AccountNotFound: ucet s danym cislom neexistuje
+pub async fn get_account_by_number(
+    pool: &PgPool,
+    config: &Config,
+    number: &str,
+) -> Result<PubAccount, BankError> {
+    config.account_numbers.validate(number)?;
+
+    let row = query!(
+        r#"SELECT id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts WHERE account_number = $1"#,
+        number
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    Ok(PubAccount {
+        id: row.id,
+        account_number: row.account_number,
+        user_id: row.user_id,
+        balance: row.balance,
+        currency: row.currency,
+        account_type: row.account_type,
+        status: row.status,
+        display_balance: None,
+    })
+}
+
+/// Ziska jeden ucet podla ID
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+pub async fn get_account_by_id(pool: &PgPool, account_id: Uuid) -> Result<PubAccount, BankError> {
+    let row = query!(
+        r#"SELECT id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts WHERE id = $1"#,
+        account_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    Ok(PubAccount {
+        id: row.id,
+        account_number: row.account_number,
+        user_id: row.user_id,
+        balance: row.balance,
+        currency: row.currency,
+        account_type: row.account_type,
+        status: row.status,
+        display_balance: None,
+    })
+}
+
+/// Blokacia velkych vkladov
+///
+/// # Navratova hodnota
+/// Vracia (hranica, doba blokacie) alebo None, ak blokacia nie je nastavena
+///
+/// # Konfiguracia
+/// - Config::deposit_hold_threshold: vklady od tejto sumy (vratane) su blokovane,
+///   bez nastavenia sa neblokuje nic (DEPOSIT_HOLD_THRESHOLD)
+/// - Config::deposit_hold: doba blokacie (DEPOSIT_HOLD_SECS)
+pub fn deposit_hold(config: &Config) -> Option<(Decimal, Duration)> {
+    config
+        .deposit_hold_threshold
+        .map(|threshold| (threshold, config.deposit_hold))
+}
+
+/// Overi, ze vlastnik uctu ma overeny e-mail (ak to Config::require_verified_email vyzaduje)
+///
+/// # Poznamka
+/// Neexistujuci ucet sa preskoci - chybu hlasi volajuci.
+///
+/// # Chyby
+/// - BankError::EmailNotVerified: vlastnik uctu nema overeny e-mail
+pub(crate) async fn ensure_email_verified(
+    conn: &mut PgConnection,
+    config: &Config,
+    account_id: Uuid,
+) -> Result<(), BankError> {
+    if !config.require_verified_email {
+        return Ok(());
+    }
+
+    let verified = query!(
+        "SELECT u.email_verified
+         FROM accounts a JOIN users u ON u.id = a.user_id
+         WHERE a.id = $1",
+        account_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .is_none_or(|row| row.email_verified);
+
+    if !verified {
+        return Err(BankError::EmailNotVerified);
+    }
+    Ok(())
+}
+
+/// Overi, ze ucet existuje aspon Config::min_account_age a moze posielat peniaze
+///
+/// # Poznamka
+/// Nulovy vek (predvolene) kontrolu vypne. Neexistujuci ucet a ucty bez casu
+/// zalozenia (pred jeho zavedenim) sa preskocia.
+///
+/// # Chyby
+/// - BankError::AccountTooNew: ucet bol zalozeny prilis nedavno
+pub(crate) async fn ensure_account_age(
+    conn: &mut PgConnection,
+    config: &Config,
+    account_id: Uuid,
+) -> Result<(), BankError> {
+    let min_age = config.min_account_age;
+    if min_age.is_zero() {
+        return Ok(());
+    }
+
+    let too_new = query!(
+        r#"SELECT COALESCE(created_at > NOW() - make_interval(secs => $2), FALSE) AS "too_new!"
+           FROM accounts WHERE id = $1"#,
+        account_id,
+        min_age.as_secs_f64()
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .is_some_and(|row| row.too_new);
+
+    if too_new {
+        return Err(BankError::AccountTooNew);
+    }
+    Ok(())
+}
+
+/// Overi, ze suma lezi v rozsahu povolenom pre danu menu
+///
+/// # Konfiguracia
+/// - Config::amount_bounds (AMOUNT_BOUNDS): rozsahy podla meny, napr.
+///   `EUR=0.01..10000,CZK=1..250000` (mena bez zaznamu nema obmedzenie, predvolene ziadne)
+///
+/// # Chyby
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny
+pub fn check_amount_bounds(
+    config: &Config,
+    currency: &str,
+    amount: Decimal,
+) -> Result<(), BankError> {
+    match config.amount_bounds.for_currency(currency) {
+        Some(bounds) if !bounds.contains(amount) => Err(BankError::AmountOutOfBounds {
+            currency: currency.trim().to_uppercase(),
+            bounds,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Overi, ze priamy prevod nevyzaduje potvrdenie
+///
+/// # Konfiguracia
+/// - Config::transfer_confirmation_threshold (TRANSFER_CONFIRMATION_THRESHOLD): od tejto
+///   sumy (vratane) sa prevod musi zacat cez initiate_transfer a potvrdit tokenom
+///
+/// # Chyby
+/// - BankError::ConfirmationRequired: suma je na hranici alebo nad nou
+pub fn check_confirmation_threshold(config: &Config, amount: Decimal) -> Result<(), BankError> {
+    match config.transfer_confirmation_threshold {
+        Some(threshold) if amount >= threshold => Err(BankError::ConfirmationRequired),
+        _ => Ok(()),
+    }
+}
+
+/// Overi sumu voci rozsahu meny uctu (pozri check_amount_bounds)
+///
+/// # Poznamka
+/// Bez nastavenych rozsahov sa databaza nevola. Neexistujuci ucet sa preskoci -
+/// chybu hlasi volajuci.
+///
+/// # Chyby
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny uctu
+pub(crate) async fn ensure_amount_in_bounds(
+    conn: &mut PgConnection,
+    config: &Config,
+    account_id: Uuid,
+    amount: Decimal,
+) -> Result<(), BankError> {
+    if config.amount_bounds.is_empty() {
+        return Ok(());
+    }
+
+    let currency = query!("SELECT currency FROM accounts WHERE id = $1", account_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    match currency {
+        Some(row) => check_amount_bounds(config, &row.currency, amount),
+        None => Ok(()),
+    }
+}
+
+/// Zamkne ucty (FOR UPDATE) v stalom poradi podla ID
+///
+/// Kazda operacia, ktora meni zostatky viacerych uctov, musi ucty zamknut
+/// touto funkciou skor, nez zamkne ktorykolvek z nich inak. Dva opacne prevody
+/// (A->B a B->A) potom cakaju na ten isty prvy zamok a nezablokuju sa navzajom
+/// (deadlock). Neexistujuce ucty sa preskocia - chybu hlasi volajuci.
+pub(crate) async fn lock_accounts(
+    conn: &mut PgConnection,
+    account_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    query!(
+        "SELECT id FROM accounts WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+        account_ids
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(())
+}
+
+/// Sucet vkladov na ucet, ktore este nie su k dispozicii
+async fn held_deposits(conn: &mut PgConnection, account_id: Uuid) -> Result<Decimal, sqlx::Error> {
+    let held = query!(
+        r#"SELECT COALESCE(SUM(amount), 0) AS "held!"
+           FROM transactions
+           WHERE to_account = $1 AND from_account IS NULL AND available_at > NOW()"#,
+        account_id
+    )
+    .fetch_one(conn)
+    .await?
+    .held;
+
+    Ok(held)
+}
+
+/// Ziska zostatok uctu a sumu, ktoru mozno hned pouzit
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+///
+/// # Navratova hodnota
+/// Vracia AccountBalance - available_balance je zostatok bez blokovanych vkladov
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+pub async fn get_account_balance(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<AccountBalance, BankError> {
+    let balance = query!("SELECT balance FROM accounts WHERE id = $1", account_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(BankError::AccountNotFound)?
+        .balance;
+
+    let holds: Vec<DepositHold> = query!(
+        r#"SELECT id, amount, available_at AS "available_at!"
+           FROM transactions
+           WHERE to_account = $1 AND from_account IS NULL AND available_at > NOW()
+           ORDER BY available_at, id"#,
+        account_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| DepositHold {
+        transaction_id: row.id,
+        amount: row.amount,
+        available_at: row.available_at,
+    })
+    .collect();
+
+    let held: Decimal = holds.iter().map(|hold| hold.amount).sum();
+
+    Ok(AccountBalance {
+        account_id,
+        balance,
+        available_balance: balance - held,
+        holds,
+    })
+}
+
+/// Prida peniaze na ucet
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - money: suma na pridanie (musi byt kladna)
+/// - idempotency_key: volitelny kluc, ktory zabrani dvojitemu pripisaniu pri opakovani poziadavky
+///   (plati IDEMPOTENCY_KEY_TTL_SECS, ak je nastavene)
+///
+/// # Navratova hodnota
+/// Vracia aktualizovany PubAccount s novou bilanciou
+///
+/// # Chyby
+/// - BankError::InvalidAmount: suma nie je kladna
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::AccountClosed / BankError::AccountFrozen: ucet nie je aktivny
+/// - BankError::AmountTooLarge: suma alebo novy zostatok sa nezmesti do stlpca balance
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny uctu (AMOUNT_BOUNDS)
+/// - BankError::Database: ina chyba databazy
+///
+/// # Idempotencia
+/// Vklad je zaznamenany v tabulke transakcii (bez odosielatela). Ak uz vklad
+/// s rovnakym klucom na dany ucet existuje, zmena zostatku sa stornuje
+/// a vrati sa ucet bez dalsieho pripisania.
+///
+/// # Blokacia velkych vkladov
+/// Vklad od DEPOSIT_HOLD_THRESHOLD sa pripise na zostatok hned, ale do dostupneho
+/// zostatku (available_balance) sa zapocita az po DEPOSIT_HOLD_SECS (pozri deposit_hold).
+pub async fn add_money(
+    pool: &PgPool,
+    config: &Config,
+    account_id: Uuid,
+    money: Decimal,
+    idempotency_key: Option<&str>,
+) -> Result<PubAccount, BankError> {
+    if money <= Decimal::ZERO {
+        return Err(BankError::InvalidAmount);
+    }
+
+    // Doba blokacie vkladu (None = vklad je k dispozicii hned)
+    let hold_secs = deposit_hold(config)
+        .filter(|(threshold, _)| money >= *threshold)
+        .map(|(_, hold)| hold.as_secs_f64());
+
+    // Zmena zostatku a zaznam vkladu musia prebehnut v jednej transakcii
+    let mut tx = pool.begin().await?;
+
+    // UPDATE ... RETURNING bez riadku znamena, ze ucet neexistuje
+    let row = query!(
+        r#"UPDATE accounts SET balance=balance+$1 WHERE id=$2
+           RETURNING id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus""#,
+        money,
+        account_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    // Na zatvoreny alebo zablokovany ucet sa nevklada (zmena sa stornuje)
+    ensure_account_active(row.status)?;
+    check_amount_bounds(config, &row.currency, money)?;
+
+    // Kluc starsi ako IDEMPOTENCY_KEY_TTL_SECS uz pred opakovanim nechrani
+    let mut archived = false;
+    if let Some(key) = idempotency_key {
+        let scope = IdempotencyScope::Deposit {
+            to_account: account_id,
+        };
+        release_expired_key(&mut tx, config, scope, key).await?;
+        archived = archived_deposit_exists(&mut tx, account_id, key).await?;
+    }
+
+    // Zaznam vkladu - unikatny index na (to_account, idempotency_key) vkladov zachyti opakovanie
+    // v aktivnych transakciach, archivovany vklad s klucom sa kontroluje vyssie
+    let deposit = if archived {
+        None
+    } else {
+        query!(
+        "INSERT INTO transactions (id, from_account, to_account, amount, currency, idempotency_key, available_at)
+         VALUES ($1, NULL, $2, $3, $4, $5, NOW() + make_interval(secs => $6))
+         ON CONFLICT (to_account, idempotency_key)
+             WHERE idempotency_key IS NOT NULL AND reference IS NULL DO NOTHING
+         RETURNING id",
+        new_transaction_id(config),
+        account_id,
+        money,
+        row.currency,
+        idempotency_key,
+        hold_secs
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    };
+
+    if deposit.is_none() {
+        // Opakovana poziadavka - vklad uz bol pripisany, zmenu zostatku stornujeme
+        tx.rollback().await?;
+
+        let current = query!(
+            r#"SELECT id, account_number, user_id, balance, currency,
+                     account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+               FROM accounts WHERE id=$1"#,
+            account_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        return Ok(PubAccount {
+            id: current.id,
+            account_number: current.account_number,
+            user_id: current.user_id,
+            balance: current.balance,
+            currency: current.currency,
+            account_type: current.account_type,
+            status: current.status,
+            display_balance: None,
+        });
+    }
+
+    tx.commit().await?;
+
+    Ok(PubAccount {
+        id: row.id,
+        account_number: row.account_number,
+        user_id: row.user_id,
+        balance: row.balance,
+        currency: row.currency,
+        account_type: row.account_type,
+        status: row.status,
+        display_balance: None,
+    })
+}
+
+/// Prida peniaze na viacero uctov naraz (napr. hromadna vyplata miezd)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - credits: dvojice (ucet, suma) v poradi, v akom sa maju zaznamenat; ucet sa moze opakovat
+///
+/// # Navratova hodnota
+/// Vracia DepositBatch so zaznamom kazdeho vkladu a stavom uctov po pripisani
+///
+/// # Chyby
+/// - BankError::InvalidBody: prazdny zoznam
+/// - BankError::InvalidAmount: niektora suma nie je kladna
+/// - BankError::AccountNotFound: niektory ucet neexistuje
+/// - BankError::AccountClosed / BankError::AccountFrozen / BankError::AccountPending: ucet nie je aktivny
+/// - BankError::AmountTooLarge: suma alebo novy zostatok sa nezmesti do stlpca balance
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny uctu (AMOUNT_BOUNDS)
+///
+/// # Poznamka
+/// Zmena zostatkov aj zaznamy vkladov prebehnu v jednej databazovej transakcii jednym
+/// dotazom (UNNEST) - pri akejkolvek chybe sa nepripise nic. Kazda suma sa zaznamena ako
+/// samostatny vklad (bez odosielatela) s blokaciou podla DEPOSIT_HOLD_THRESHOLD ako
+/// pri add_money, na zostatok uctu sa pripise ich sucet. Ucty sa zamykaju v poradi
+/// podla ID, takze subezne davky sa navzajom nezablokuju. Kluce idempotencie sa
+/// v davke nepouzivaju.
+pub async fn add_money_batch(
+    pool: &PgPool,
+    config: &Config,
+    credits: Vec<(Uuid, Decimal)>,
+) -> Result<DepositBatch, BankError> {
+    if credits.is_empty() {
+        return Err(BankError::InvalidBody(
+            "at least one deposit is required".to_string(),
+        ));
+    }
+    if credits.iter().any(|(_, amount)| *amount <= Decimal::ZERO) {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let hold = deposit_hold(config);
+    let transaction_ids: Vec<Uuid> = credits.iter().map(|_| new_transaction_id(config)).collect();
+    let account_ids: Vec<Uuid> = credits.iter().map(|(account_id, _)| *account_id).collect();
+    let amounts: Vec<Decimal> = credits.iter().map(|(_, amount)| *amount).collect();
+    // Doba blokacie kazdeho vkladu (None = vklad je k dispozicii hned)
+    let hold_secs: Vec<Option<f64>> = amounts
+        .iter()
+        .map(|amount| {
+            hold.filter(|(threshold, _)| amount >= threshold)
+                .map(|(_, hold)| hold.as_secs_f64())
+        })
+        .collect();
+
+    let mut tx = pool.begin().await?;
+
+    // Zamknutie uctov, pripisanie suctu na kazdy ucet a zaznam vkladov v jednom dotaze;
+    // sumy sa zaokruhlia ako v stlpci amount, aby sucet sedel so zaznamenanymi vkladmi
+    let rows = query!(
+        r#"WITH credits AS (
+               SELECT transaction_id, account_id, amount::NUMERIC(15, 2) AS amount, hold_secs
+               FROM UNNEST($1::uuid[], $2::uuid[], $3::numeric[], $4::float8[])
+                   AS c(transaction_id, account_id, amount, hold_secs)
+           ),
+           locked AS (
+               SELECT id FROM accounts
+               WHERE id IN (SELECT account_id FROM credits)
+               ORDER BY id
+               FOR UPDATE
+           ),
+           updated AS (
+               UPDATE accounts a SET balance = a.balance + t.total
+               FROM (SELECT account_id, SUM(amount) AS total FROM credits GROUP BY account_id) t
+               JOIN locked l ON l.id = t.account_id
+               WHERE a.id = t.account_id
+               RETURNING a.id, a.account_number, a.user_id, a.balance, a.currency,
+                         a.account_type, a.status
+           ),
+           deposits AS (
+               INSERT INTO transactions (id, from_account, to_account, amount, currency, available_at)
+               SELECT c.transaction_id, NULL, c.account_id, c.amount, u.currency,
+                      NOW() + make_interval(secs => c.hold_secs)
+               FROM credits c JOIN updated u ON u.id = c.account_id
+           )
+           SELECT id AS "id!", account_number, user_id AS "user_id!", balance AS "balance!",
+                  currency AS "currency!", account_type AS "account_type!: AccountType",
+                  status AS "status!: AccountStatus"
+           FROM updated"#,
+        &transaction_ids,
+        &account_ids,
+        &amounts,
+        &hold_secs as &[Option<f64>]
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut accounts: HashMap<Uuid, PubAccount> = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.id,
+                PubAccount {
+                    id: row.id,
+                    account_number: row.account_number,
+                    user_id: row.user_id,
+                    balance: row.balance,
+                    currency: row.currency,
+                    account_type: row.account_type,
+                    status: row.status,
+                    display_balance: None,
+                },
+            )
+        })
+        .collect();
+
+    // Chyba ktorejkolvek polozky zrusi celu davku (tx sa pri navrate zahodi)
+    for (account_id, amount) in &credits {
+        let account = accounts.get(account_id).ok_or(BankError::AccountNotFound)?;
+        ensure_account_active(account.status)?;
+        check_amount_bounds(config, &account.currency, *amount)?;
+    }
+
+    tx.commit().await?;
+
+    let deposits = transaction_ids
+        .into_iter()
+        .zip(credits)
+        .map(|(transaction_id, (account_id, amount))| BatchDeposit {
+            transaction_id,
+            account_id,
+            amount,
+        })
+        .collect();
+
+    let accounts = account_ids
+        .iter()
+        .filter_map(|account_id| accounts.remove(account_id))
+        .collect();
+
+    Ok(DepositBatch { deposits, accounts })
+}
+
+/// Vytvori transakciu - prevod penazi medzi dvoma uctami
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - rates: zdroj kurzov pre prevod na ucet v inej mene (pozri execute_transfer)
+/// - request: ucty odosielatela a prijemcu, suma, volitelne poznamky (odosielatela
+///   a prijemcu), kluc idempotencie a rozdelenie do kategorii; PIN overuje volajuci
+///   (verify_transfer_pin)
+///
+/// # Navratova hodnota
+/// Vracia Transaction objekt (s poznamkou odosielatela) alebo chybu
+///
+/// # Bezpecnost a validacia
+/// - Pouziva databazovu transakciu (BEGIN/COMMIT) pre ACID vlastnosti
+/// - Overuje ci ma odosielatel dostatocny zostatok
+/// - Pouziva FOR UPDATE zamok pre zabranenie race conditions
+/// - Ak akakolvek operacia zlyhava, vsetky zmeny su automaticky stornovane (ROLLBACK)
+///
+/// # Chyby
+/// - BankError::InvalidAmount: suma nie je kladna
+/// - BankError::InvalidNote: poznamka obsahuje nulovy bajt alebo je prilis dlha
+/// - BankError::AccountNotFound: ucet odosielatela alebo prijemcu neexistuje
+/// - BankError::AccountClosed / BankError::AccountFrozen: ucet odosielatela alebo prijemcu nie je aktivny
+/// - BankError::InsufficientFunds: nedostatocny zostatok na ucte odosielatela
+/// - BankError::WithdrawalLimitReached: ucet vycerpal mesacny limit vyberov svojho typu (ACCOUNT_TYPE_RULES)
+/// - BankError::DuplicateTransfer: rovnaky prevod bez kluca idempotencie v casovom okne
+/// - BankError::IdempotencyKeyReused: kluc idempotencie uz bol pouzity s inym prijemcom alebo sumou
+/// - BankError::InvalidBody: neplatne rozdelenie (pozri normalize_splits)
+/// - BankError::Busy: ucet je zamknuty inou operaciou dlhsie ako TRANSFER_LOCK_TIMEOUT_MS
+/// - BankError::SerializationFailure: konflikt so subeznym prevodom trval aj po
+///   TRANSFER_SERIALIZATION_RETRIES opakovaniach (len pri TRANSFER_ISOLATION_LEVEL=serializable)
+/// - BankError::EmailNotVerified: vlastnik uctu odosielatela nema overeny e-mail (REQUIRE_VERIFIED_EMAIL)
+/// - BankError::AccountTooNew: ucet odosielatela je mladsi ako MIN_ACCOUNT_AGE_SECS
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny uctu odosielatela (AMOUNT_BOUNDS)
+/// - BankError::RateUnavailable: ucet prijemcu ma inu menu a kurz nie je k dispozicii
+/// - BankError::Database: ine problemy s databazou
+///
+/// # Poznamka
+/// Poznamky sa pred ulozenim cistia (sanitize_note) - riadiace znaky
+/// sa nahradia medzerou a medzery sa normalizuju.
+///
+/// # Rozdelenie do kategorii
+/// Ak je zadane `splits`, ulozi sa v tej istej databazovej transakcii ako prevod
+/// (tabulka transaction_splits) a vrati sa v Transaction::splits.
+///
+/// # Idempotencia
+/// Ak prevod s rovnakym `idempotency_key` z uctu odosielatela uz existuje,
+/// vrati sa povodna transakcia a peniaze sa znova nepresuvaju. Kluc musi prist
+/// s rovnakym prijemcom a sumou, inak sa prevod odmietne (IdempotencyKeyReused).
+/// Kluce prevodov su oddelene od klucov vkladov (add_money). Kluc plati
+/// IDEMPOTENCY_KEY_TTL_SECS (ak je nastavene), potom sa prevod vykona ako novy.
+/// Pri subeznych prevodoch s rovnakym klucom sa vykona len prvy potvrdeny, ostatne
+/// sa stornuju a vratia jeho transakciu.
+///
+/// # Konfiguracia
+/// - DUPLICATE_TRANSFER_WINDOW_SECS: ak je nastavene (> 0), prevod bez kluca
+///   idempotencie s rovnakym odosielatelom, prijemcom a sumou ako prevod
+///   spred menej nez tolkych sekund sa odmietne (ochrana pred dvojklikom)
+/// - TRANSFER_LOCK_TIMEOUT_MS: najdlhsie cakanie na zamok uctu (Config::transfer_lock_timeout, 0 = bez limitu)
+/// - TRANSFER_ISOLATION_LEVEL: uroven izolacie transakcie prevodu (read-committed alebo
+///   serializable); pri serializable sa prevod po konflikte (SQLSTATE 40001) zopakuje
+///   najviac TRANSFER_SERIALIZATION_RETRIES krat
+/// - TRANSFER_CONFIRMATION_THRESHOLD: od tejto sumy sa prevod odmietne
+///   (BankError::ConfirmationRequired) a treba ho zacat cez initiate_transfer
+pub async fn make_transaction(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    request: &TransactionRequest,
+) -> Result<Transaction, BankError> {
+    check_confirmation_threshold(config, request.amount)?;
+
+    retry_on_serialization_failure(config.transfer_serialization_retries, || {
+        make_transaction_attempt(pool, config, rates, request)
+    })
+    .await
+}
+
+/// Jeden pokus o prevod v samostatnej databazovej transakcii (pozri make_transaction)
+async fn make_transaction_attempt(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    request: &TransactionRequest,
+) -> Result<Transaction, BankError> {
+    let (from_account, to_account, amount) =
+        (request.from_account, request.to_account, request.amount);
+    let notes = TransferNotes {
+        sender: request.sender_note.as_deref(),
+        receiver: request.receiver_note.as_deref(),
+    };
+    let idempotency_key = request.idempotency_key.as_deref();
+    let splits = normalize_splits(amount, &request.splits)?;
+
+    // Zacatie databazovej transakcie - zabezpecuje atomicitu operacie
+    let mut tx = pool.begin().await?;
+
+    // Uroven izolacie sa musi nastavit pred prvym dotazom transakcie
+    config.transfer_isolation.apply(&mut tx).await?;
+
+    // Obmedzenie cakania na zamok (SET LOCAL plati len do konca transakcie)
+    let timeout = config.transfer_lock_timeout;
+    if !timeout.is_zero() {
+        query!(
+            "SELECT set_config('lock_timeout', $1, true)",
+            format!("{}ms", timeout.as_millis())
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+    }
+
+    ensure_email_verified(&mut tx, config, from_account).await?;
+    ensure_account_age(&mut tx, config, from_account).await?;
+    ensure_amount_in_bounds(&mut tx, config, from_account, amount).await?;
+
+    if let Some(key) = idempotency_key {
+        // Kluc starsi ako IDEMPOTENCY_KEY_TTL_SECS uz pred opakovanim nechrani
+        let scope = IdempotencyScope::Transfer { from_account };
+        release_expired_key(&mut tx, config, scope, key).await?;
+
+        // Opakovana poziadavka - vratime povodnu transakciu (aj uz archivovanu)
+        if let Some(existing) =
+            replayed_transfer(&mut tx, from_account, to_account, amount, key).await?
+        {
+            return Ok(existing);
+        }
+    } else if !config.duplicate_transfer_window.is_zero() {
+        // Zamok uctov - subezne rovnake prevody sa vyhodnotia postupne
+        lock_accounts(&mut tx, &[from_account, to_account]).await?;
+
+        let duplicate = query!(
+            r#"SELECT EXISTS (
+                   SELECT 1 FROM transactions
+                   WHERE from_account = $1 AND to_account = $2 AND amount = $3
+                     AND created_at > NOW() - make_interval(secs => $4)
+               ) AS "duplicate!""#,
+            from_account,
+            to_account,
+            amount,
+            config.duplicate_transfer_window.as_secs_f64()
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .duplicate;
+
+        if duplicate {
+            return Err(BankError::DuplicateTransfer);
+        }
+    }
+
+    let mut transaction = execute_transfer(
+        &mut tx,
+        config,
+        rates,
+        from_account,
+        to_account,
+        amount,
+        notes,
+    )
+    .await?;
+
+    for split in &splits {
+        query!(
+            "INSERT INTO transaction_splits (transaction_id, category, amount) VALUES ($1, $2, $3)",
+            transaction.id,
+            split.category,
+            split.amount
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    transaction.splits = splits;
+
+    if let Some(key) = idempotency_key {
+        let stored = query!(
+            "UPDATE transactions SET idempotency_key = $1 WHERE id = $2",
+            key,
+            transaction.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(BankError::from);
+
+        // Subezny prevod s rovnakym klucom sa potvrdil skor (idx_transactions_transfer_idempotency) -
+        // tento prevod sa stornuje a vrati sa vysledok prveho
+        if let Err(BankError::AlreadyExists(field @ "idempotency key")) = stored {
+            tx.rollback().await?;
+            let mut conn = pool.acquire().await?;
+            return replayed_transfer(&mut conn, from_account, to_account, amount, key)
+                .await?
+                .ok_or(BankError::AlreadyExists(field));
+        }
+        stored?;
+    }
+
+    // Potvrdenie transakcie - vsetky zmeny su trvale ulozene
+    // Ak nedojde k commit(), zmeny sa automaticky stornuju
+    tx.commit().await?;
+
+    Ok(transaction)
+}
+
+/// Uz vykonany prevod s danym klucom idempotencie (aj archivovany)
+///
+/// # Navratova hodnota
+/// Some s povodnou transakciou, None ak prevod s klucom neexistuje
+///
+/// # Chyby
+/// - BankError::IdempotencyKeyReused: kluc patri prevodu s inym prijemcom alebo sumou
+async fn replayed_transfer(
+    conn: &mut PgConnection,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+    key: &str,
+) -> Result<Option<Transaction>, BankError> {
+    let existing = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account, amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at, sender_note
+           FROM transactions
+           WHERE from_account = $1 AND idempotency_key = $2 AND reference IS NOT NULL
+           UNION ALL
+           SELECT id, reference, from_account, to_account, amount,
+                  credited_amount, credited_currency, rate, currency,
+                  created_at, sender_note
+           FROM archived_transactions
+           WHERE from_account = $1 AND idempotency_key = $2 AND reference IS NOT NULL"#,
+        from_account,
+        key
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(existing) = existing else {
+        return Ok(None);
+    };
+    // Rovnaky kluc s inym prijemcom alebo sumou nie je opakovanie, ale chyba klienta
+    if existing.to_account != Some(to_account) || existing.amount != amount {
+        return Err(BankError::IdempotencyKeyReused);
+    }
+    Ok(Some(Transaction {
+        id: existing.id,
+        reference: existing.reference,
+        from_account: existing.from_account,
+        to_account: existing.to_account,
+        amount: existing.amount,
+        currency: existing.currency,
+        credited_amount: existing.credited_amount,
+        credited_currency: existing.credited_currency,
+        rate: existing.rate,
+        created_at: existing.created_at,
+        note: existing.sender_note,
+        parties: None,
+        splits: list_transaction_splits(conn, existing.id).await?,
+    }))
+}
+
+/// Vrati nahlad prevodu bez jeho vykonania
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - rates: zdroj kurzov pre prevod na ucet v inej mene
+/// - user_id: UUID prihlaseneho pouzivatela (musi vlastnit ucet odosielatela)
+/// - from_account: UUID uctu odosielatela
+/// - to_account: UUID uctu prijemcu
+/// - amount: suma prevodu
+///
+/// # Navratova hodnota
+/// Vracia TransferPreview s poplatkom, pripisanou sumou (pri roznych menach),
+/// vyslednym zostatkom odosielatela a informaciou, ci by prevod prebehol.
+/// Chyby samotneho prevodu (napr. INSUFFICIENT_FUNDS) su v poli `error`.
+///
+/// # Poznamka
+/// Prevod sa skutocne vykona (execute_transfer) v databazovej transakcii, ktora
+/// sa na konci stornuje (ROLLBACK) - nahlad preto pouziva presne tie iste pravidla
+/// ako skutocny prevod. Poplatok a pripisana suma sa zistia zo zmeny zostatkov;
+/// prevody zatial poplatky neuctuju, takze poplatok je 0. Pri roznych menach je
+/// pripisana suma prepocitana aktualnym kurzom `rates` rovnako ako pri prevode.
+/// Stornuje sa vsetko okrem sekvencie referencnych cisel - v cislovani prevodov
+/// moze vzniknut medzera.
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet odosielatela neexistuje
+/// - BankError::Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - BankError::Database: problemy s databazou
+pub async fn preview_transfer(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    user_id: Uuid,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+) -> Result<TransferPreview, BankError> {
+    let mut tx = pool.begin().await?;
+
+    let sender = query!(
+        "SELECT user_id, balance, currency FROM accounts WHERE id = $1",
+        from_account
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    if sender.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+
+    let recipient = query!(
+        "SELECT balance, currency FROM accounts WHERE id = $1",
+        to_account
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let mut preview = TransferPreview {
+        from_account,
+        to_account,
+        amount,
+        from_currency: sender.currency.clone(),
+        to_currency: recipient.as_ref().map(|r| r.currency.clone()),
+        fee: None,
+        converted_amount: None,
+        resulting_balance: None,
+        would_succeed: false,
+        error: None,
+    };
+
+    let outcome = match check_amount_bounds(config, &sender.currency, amount) {
+        Ok(()) => {
+            execute_transfer(
+                &mut tx,
+                config,
+                rates,
+                from_account,
+                to_account,
+                amount,
+                TransferNotes::default(),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match outcome {
+        Ok(_) => {
+            let after = query!(
+                "SELECT id, balance FROM accounts WHERE id = ANY($1)",
+                &[from_account, to_account][..]
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+            let balance_of =
+                |id: Uuid| after.iter().find(|row| row.id == id).map(|row| row.balance);
+
+            let sender_after = balance_of(from_account).unwrap_or(sender.balance);
+            preview.fee = Some(sender.balance - sender_after - amount);
+            preview.resulting_balance = Some(sender_after);
+            if let (Some(recipient), Some(recipient_after)) = (&recipient, balance_of(to_account))
+                && recipient.currency != sender.currency
+            {
+                preview.converted_amount = Some(recipient_after - recipient.balance);
+            }
+            preview.would_succeed = true;
+        }
+        Err(BankError::Database(e)) => return Err(BankError::Database(e)),
+        Err(e) => {
+            preview.error = Some(TransferPreviewError {
+                code: e.code().to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    // Nahlad nesmie nic zmenit - vsetky zmeny prevodu sa stornuju
+    tx.rollback().await?;
+
+    Ok(preview)
+}
+
+/// Overi a normalizuje rozdelenie sumy prevodu do kategorii
+///
+/// # Navratova hodnota
+/// Vracia casti s normalizovanymi kategoriami (ako normalize_tag) v povodnom poradi
+///
+/// # Chyby
+/// - BankError::InvalidBody: neplatny nazov kategorie (pravidla normalize_tag), cast
+///   nie je kladna, ma viac ako 2 desatinne miesta, kategoria sa opakuje alebo sucet
+///   casti sa nerovna sume prevodu
+fn normalize_splits(
+    amount: Decimal,
+    splits: &[TransactionSplit],
+) -> Result<Vec<TransactionSplit>, BankError> {
+    let mut normalized: Vec<TransactionSplit> = Vec::with_capacity(splits.len());
+
+    for split in splits {
+        // Chybna kategoria je chyba tela prevodu, nie stitku transakcie (InvalidTag)
+        let category = normalize_tag(&split.category).map_err(|_| {
+            BankError::InvalidBody(format!("invalid split category '{}'", split.category))
+        })?;
+        if split.amount <= Decimal::ZERO || split.amount.scale() > MONEY_SCALE {
+            return Err(BankError::InvalidBody(format!(
+                "split amount for '{category}' must be positive with at most {MONEY_SCALE} decimal places"
+            )));
+        }
+        if normalized.iter().any(|s| s.category == category) {
+            return Err(BankError::InvalidBody(format!(
+                "split category '{category}' must not repeat"
+            )));
+        }
+        normalized.push(TransactionSplit {
+            category,
+            amount: split.amount,
+        });
+    }
+
+    let total: Decimal = normalized.iter().map(|s| s.amount).sum();
+    if !normalized.is_empty() && total != amount {
+        return Err(BankError::InvalidBody(format!(
+            "split amounts sum to {total}, expected {amount}"
+        )));
+    }
+
+    Ok(normalized)
+}
+
+/// Ziska rozdelenie transakcie do kategorii
+async fn list_transaction_splits(
+    conn: &mut PgConnection,
+    transaction_id: Uuid,
+) -> Result<Vec<TransactionSplit>, sqlx::Error> {
+    let splits = query!(
+        "SELECT category, amount FROM transaction_splits WHERE transaction_id = $1 ORDER BY category",
+        transaction_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| TransactionSplit {
+        category: row.category,
+        amount: row.amount,
+    })
+    .collect();
+    Ok(splits)
+}
+
+/// Poznamky prevodu od odosielatela a pre prijemcu (pred ulozenim sa cistia)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferNotes<'a> {
+    pub sender: Option<&'a str>,
+    pub receiver: Option<&'a str>,
+}
+
+/// Kto prevod vyvolal - urcuje, ci platia pravidla typu uctu (ACCOUNT_TYPE_RULES)
+/// a ako sa urci suma pripisana prijemcovi
+#[derive(Clone, Copy)]
+enum TransferOrigin<'a> {
+    /// Prevod na pokyn klienta (aj trvaly prikaz) s jeho poznamkami - plati precerpanie
+    /// a limit vyberov, suma sa do meny prijemcu prepocita kurzom z `rates`
+    Customer {
+        notes: TransferNotes<'a>,
+        rates: &'a dyn RateProvider,
+    },
+    /// Presun zostatku pri zatvoreni alebo zluceni uctu - bez precerpania, limit vyberov
+    /// neplati (zatvorenie uctu nesmie zablokovat), meny uctov sa musia zhodovat
+    Internal,
+    /// Storno prevodu davky - ako Internal, prijemca (povodny odosielatel) dostane spat
+    /// presne povodne odpisanu sumu `credit`
+    Reversal { credit: Decimal },
+}
+
+/// Vykona prevod v ramci existujucej databazovej transakcie
+///
+/// Zamkne ucet odosielatela (FOR UPDATE), overi zostatok a pravidla typu uctu,
+/// presunie peniaze, zapise zaznam transakcie a notifikacie ucastnikom.
+/// Commit je na volajucom.
+///
+/// # Poznamka
+/// Ak ma ucet prijemcu inu menu, suma sa prepocita kurzom `rates` (mena odosielatela
+/// -> mena prijemcu) a zaokruhli podla Config::rounding_mode. Prijemcovi sa pripise
+/// prepocitana suma, ktora sa ulozi do stlpca credited_amount.
+///
+/// # Konfiguracia
+/// - Config::account_type_rules (ACCOUNT_TYPE_RULES): precerpanie a mesacny limit
+///   vyberov podla typu uctu odosielatela (pozri AccountTypeRules)
+/// - Config::rounding_mode (ROUNDING_MODE): zaokruhlenie prepocitanej sumy
+///
+/// # Chyby
+/// - BankError::RateUnavailable: kurz medzi menami uctov nie je k dispozicii
+/// - BankError::InvalidAmount: suma nie je kladna alebo je po prepocte nulova
+pub(crate) async fn execute_transfer(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    config: &Config,
+    rates: &dyn RateProvider,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+    notes: TransferNotes<'_>,
+) -> Result<Transaction, BankError> {
+    transfer_funds(
+        tx,
+        config,
+        from_account,
+        to_account,
+        amount,
+        TransferOrigin::Customer { notes, rates },
+    )
+    .await
+}
+
+/// Vykona presun zostatku bez pravidiel typu uctu (zatvorenie, zlucenie)
+///
+/// # Chyby
+/// - BankError::CurrencyMismatch: ucty maju rozne meny (zostatok sa neprepocitava)
+pub(crate) async fn execute_internal_transfer(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    config: &Config,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+) -> Result<Transaction, BankError> {
+    transfer_funds(
+        tx,
+        config,
+        from_account,
+        to_account,
+        amount,
+        TransferOrigin::Internal,
+    )
+    .await
+}
+
+/// Pocet prevodov z uctu od zaciatku aktualneho kalendarneho mesiaca (UTC), aj archivovanych
+async fn monthly_withdrawal_count(
+    conn: &mut PgConnection,
+    account_id: Uuid,
+) -> Result<i64, sqlx::Error> {
+    let count = query!(
+        r#"SELECT COUNT(*) AS "count!" FROM (
+               SELECT created_at FROM transactions
+               WHERE from_account = $1 AND reference IS NOT NULL
+               UNION ALL
+               SELECT created_at FROM archived_transactions
+               WHERE from_account = $1 AND reference IS NOT NULL
+           ) t
+           WHERE created_at >= date_trunc('month', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'"#,
+        account_id
+    )
+    .fetch_one(conn)
+    .await?
+    .count;
+    Ok(count)
+}
+
+/// Spolocna cast execute_transfer a execute_internal_transfer
+async fn transfer_funds(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    config: &Config,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+    origin: TransferOrigin<'_>,
+) -> Result<Transaction, BankError> {
+    if amount <= Decimal::ZERO {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let notes = match origin {
+        TransferOrigin::Customer { notes, .. } => notes,
+        TransferOrigin::Internal | TransferOrigin::Reversal { .. } => TransferNotes::default(),
+    };
+
+    // Poznamky bez riadiacich znakov (ochrana logov a vypisov)
+    let sender_note = sanitize_optional_note(notes.sender, config.transaction_note_max_length)?;
+    let receiver_note = sanitize_optional_note(notes.receiver, config.transaction_note_max_length)?;
+
+    // Zamknutie oboch uctov v poradi podla ID (ochrana pred deadlockom)
+    lock_accounts(tx, &[from_account, to_account]).await?;
+
+    // Kontrola zostatku odosielatela (riadok je uz zamknuty)
+    let sender = query!(
+        r#"SELECT balance, user_id, currency, status AS "status: AccountStatus", low_balance_threshold,
+                  account_type AS "account_type: AccountType"
+           FROM accounts WHERE id = $1 FOR UPDATE"#,
+        from_account
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    ensure_account_active(sender.status)?;
+
+    let rule = match origin {
+        TransferOrigin::Customer { .. } => config.account_type_rules.for_type(sender.account_type),
+        TransferOrigin::Internal | TransferOrigin::Reversal { .. } => AccountTypeRule::UNRESTRICTED,
+    };
+
+    // Validacia - overenie dostatocneho dostupneho zostatku (bez blokovanych vkladov,
+    // vratane povoleneho precerpania typu uctu)
+    let available = sender.balance - held_deposits(tx, from_account).await? + rule.overdraft_limit;
+    if available < amount {
+        return Err(BankError::InsufficientFunds);
+    }
+
+    // Mesacny limit vyberov - ucet je zamknuty, subezne prevody sa spocitaju postupne
+    if let Some(limit) = rule.monthly_withdrawals
+        && monthly_withdrawal_count(tx, from_account).await? >= i64::from(limit)
+    {
+        return Err(BankError::WithdrawalLimitReached {
+            account_type: sender.account_type,
+            limit,
+        });
+    }
+
+    // Suma pripisana prijemcovi v mene jeho uctu - urci sa pred odpisom
+    let recipient_currency = query!("SELECT currency FROM accounts WHERE id = $1", to_account)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or(BankError::AccountNotFound)?
+        .currency;
+    let (credited, rate) = match origin {
+        TransferOrigin::Reversal { credit } => (credit, None),
+        _ if recipient_currency == sender.currency => (amount, None),
+        TransferOrigin::Customer { rates, .. } => {
+            let rate = rates.rate(&sender.currency, &recipient_currency).await?;
+            let credited = config.rounding_mode.round(amount * rate, MONEY_SCALE);
+            if credited <= Decimal::ZERO {
+                return Err(BankError::InvalidAmount);
+            }
+            (credited, Some(rate))
+        }
+        TransferOrigin::Internal => {
+            return Err(BankError::CurrencyMismatch {
+                from: sender.currency,
+                to: recipient_currency,
+            });
+        }
+    };
+
+    let converted = recipient_currency != sender.currency;
+
+    // Odcitanie penazi z uctu odosielatela
+    let debited = query!(
+        "UPDATE accounts SET balance = balance - $1 WHERE id = $2 RETURNING balance",
+        amount,
+        from_account
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    // Pripocitanie penazi na ucet prijemcu
+    let recipient = query!(
+        r#"UPDATE accounts SET balance = balance + $1 WHERE id = $2
+           RETURNING user_id, status AS "status: AccountStatus""#,
+        credited,
+        to_account
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    ensure_account_active(recipient.status)?;
+
+    // Referencne cislo z databazovej sekvencie - unikatne aj pri subeznych prevodoch
+    let seq = query!(
+        r#"SELECT nextval('transfer_reference_seq') AS "seq!",
+                  EXTRACT(YEAR FROM NOW())::INT AS "year!""#
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    let reference = format_reference(seq.year, seq.seq);
+
+    // Vytvorenie zaznamu transakcie v tabulke
+    let trans_id = new_transaction_id(config);
+    let transaction = query!(
+        "INSERT INTO transactions
+             (id, reference, from_account, to_account, amount, credited_amount, credited_currency,
+              rate, currency, sender_note, receiver_note)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         RETURNING id, reference, from_account, to_account, amount, credited_amount,
+                   credited_currency, rate, currency, created_at, sender_note",
+        trans_id,
+        reference,
+        from_account,
+        to_account,
+        amount,
+        converted.then_some(credited),
+        converted.then_some(&recipient_currency),
+        rate,
+        sender.currency,
+        sender_note,
+        receiver_note
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    // Notifikacie sa odoslu len ak sa prevod potvrdi (su v tej istej transakcii)
+    let detail = transfer_detail(
+        tx,
+        config,
+        NotificationEvent::IncomingTransfer,
+        to_account,
+        from_account,
+        credited,
+        &reference,
+    )
+    .await?;
+    notify(
+        tx,
+        recipient.user_id,
+        NewNotification {
+            event: NotificationEvent::IncomingTransfer,
+            transaction_id: Some(transaction.id),
+            amount: Some(credited),
+            detail: detail.as_deref(),
+        },
+    )
+    .await?;
+
+    // Upozornenie pre administratora, ak odpis dostal zostatok pod nulu
+    record_negative_balance(
+        tx,
+        config,
+        from_account,
+        sender.balance,
+        debited.balance,
+        Some(transaction.id),
+    )
+    .await?;
+
+    if config
+        .large_withdrawal_threshold
+        .is_some_and(|threshold| amount >= threshold)
+    {
+        let detail = transfer_detail(
+            tx,
+            config,
+            NotificationEvent::LargeWithdrawal,
+            from_account,
+            to_account,
+            amount,
+            &reference,
+        )
+        .await?;
+        notify(
+            tx,
+            sender.user_id,
+            NewNotification {
+                event: NotificationEvent::LargeWithdrawal,
+                transaction_id: Some(transaction.id),
+                amount: Some(amount),
+                detail: detail.as_deref(),
+            },
+        )
+        .await?;
+    }
+
+    if let Some(threshold) = sender.low_balance_threshold {
+        let new_balance = sender.balance - amount;
+        if crosses_below(sender.balance, new_balance, threshold) {
+            let detail = format!("balance {new_balance} below {threshold}");
+            notify(
+                tx,
+                sender.user_id,
+                NewNotification {
+                    event: NotificationEvent::LowBalance,
+                    transaction_id: Some(transaction.id),
+                    amount: Some(amount),
+                    detail: Some(&detail),
+                },
+            )
+            .await?;
+        }
+    }
+
+    Ok(Transaction {
+        id: transaction.id,
+        reference: transaction.reference,
+        from_account: transaction.from_account,
+        to_account: transaction.to_account,
+        amount: transaction.amount,
+        currency: transaction.currency,
+        credited_amount: transaction.credited_amount,
+        credited_currency: transaction.credited_currency,
+        rate: transaction.rate,
+        created_at: transaction.created_at,
+        note: transaction.sender_note,
+        parties: None,
+        splits: Vec::new(),
+    })
+}
+
+/// Vykona prevod, ktory cerpa z viacerych uctov odosielatela
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela (musi vlastnit vsetky zdrojove ucty)
+/// - request: zdrojove ucty v poradi cerpania, ucet prijemcu, celkova suma a poznamky
+///   ako pri make_transaction (platia pre kazdu ciastkovu transakciu); PIN sa tu
+///   neoveruje (verify_transfer_pin)
+///
+/// # Navratova hodnota
+/// Vracia MultiSourceTransfer s ciastkovymi transakciami. Z kazdeho uctu sa odcita
+/// cely dostupny zostatok alebo zvysok sumy, dalsie ucty sa po pokryti sumy nepouziju.
+///
+/// # Poznamka
+/// Vsetky ciastkove prevody prebiehaju v jednej databazovej transakcii - bud sa vykonaju
+/// vsetky, alebo ziadny. Zdrojove ucty sa zamykaju v poradi podla ID, takze dva subezne
+/// prevody s tymi istymi uctami v inom poradi sa nezablokuju navzajom.
+///
+/// # Chyby
+/// - BankError::InvalidAmount: suma nie je kladna
+/// - BankError::InvalidBody: prazdny zoznam zdrojov, duplicitny zdroj alebo prijemca medzi zdrojmi
+/// - BankError::AccountNotFound: niektory ucet neexistuje
+/// - BankError::Forbidden: zdrojovy ucet patri inemu pouzivatelovi
+/// - BankError::InsufficientFunds: suma zostatkov zdrojov nestaci
+/// - BankError::EmailNotVerified: pouzivatel nema overeny e-mail (REQUIRE_VERIFIED_EMAIL)
+/// - BankError::AccountTooNew: niektory zdroj je mladsi ako MIN_ACCOUNT_AGE_SECS
+/// - BankError::AmountOutOfBounds: celkova suma je mimo rozsahu meny niektoreho zdroja
+/// - BankError::ConfirmationRequired: celkova suma je od TRANSFER_CONFIRMATION_THRESHOLD
+/// - chyby ensure_account_active pre zdroje aj prijemcu
+pub async fn make_transaction_from_multiple(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    user_id: Uuid,
+    request: &MultiSourceTransferRequest,
+) -> Result<MultiSourceTransfer, BankError> {
+    let sources = request.sources.as_slice();
+    let (to_account, amount) = (request.to_account, request.amount);
+    let notes = TransferNotes {
+        sender: request.sender_note.as_deref(),
+        receiver: request.receiver_note.as_deref(),
+    };
+    if amount <= Decimal::ZERO {
+        return Err(BankError::InvalidAmount);
+    }
+    check_confirmation_threshold(config, amount)?;
+    if sources.is_empty() {
+        return Err(BankError::InvalidBody(
+            "sources must not be empty".to_string(),
+        ));
+    }
+    if sources.contains(&to_account) {
+        return Err(BankError::InvalidBody(
+            "to_account must not be one of the sources".to_string(),
+        ));
+    }
+    let unique: HashSet<Uuid> = sources.iter().copied().collect();
+    if unique.len() != sources.len() {
+        return Err(BankError::InvalidBody(
+            "sources must not repeat".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Zamknutie vsetkych zdrojov aj prijemcu naraz v stalom poradi
+    let locked: Vec<Uuid> = sources.iter().copied().chain([to_account]).collect();
+    lock_accounts(&mut tx, &locked).await?;
+
+    let rows = query!(
+        r#"SELECT id, user_id, balance, currency, status AS "status: AccountStatus"
+           FROM accounts WHERE id = ANY($1)
+           ORDER BY id
+           FOR UPDATE"#,
+        sources
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    if rows.len() != sources.len() {
+        return Err(BankError::AccountNotFound);
+    }
+    if rows.iter().any(|row| row.user_id != user_id) {
+        return Err(BankError::Forbidden);
+    }
+    for row in &rows {
+        ensure_account_active(row.status)?;
+        check_amount_bounds(config, &row.currency, amount)?;
+    }
+    ensure_email_verified(&mut tx, config, sources[0]).await?;
+    for source in sources {
+        ensure_account_age(&mut tx, config, *source).await?;
+    }
+
+    // Dostupne zostatky zdrojov (bez blokovanych vkladov)
+    let mut balances: HashMap<Uuid, Decimal> = HashMap::new();
+    for row in &rows {
+        let held = held_deposits(&mut tx, row.id).await?;
+        balances.insert(row.id, row.balance - held);
+    }
+
+    let available: Decimal = balances.values().sum();
+    if available < amount {
+        return Err(BankError::InsufficientFunds);
+    }
+
+    let transfer_group_id = Uuid::new_v4();
+    let mut remaining = amount;
+    let mut transactions = Vec::new();
+
+    for source in sources {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let part = balances[source].min(remaining);
+        if part <= Decimal::ZERO {
+            continue;
+        }
+
+        let transaction =
+            execute_transfer(&mut tx, config, rates, *source, to_account, part, notes).await?;
+        remaining -= part;
+        transactions.push(transaction);
+    }
+
+    let ids: Vec<Uuid> = transactions.iter().map(|t| t.id).collect();
+    query!(
+        "UPDATE transactions SET transfer_group_id = $1 WHERE id = ANY($2)",
+        transfer_group_id,
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(MultiSourceTransfer {
+        transfer_group_id,
+        to_account,
+        amount,
+        transactions,
+    })
+}
+
+/// Najvyssi pocet prevodov v jednej hromadnej davke
+pub const MAX_BATCH_TRANSFERS: usize = 100;
+
+/// Vykona hromadny prevod z jedneho uctu viacerym prijemcom
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela (musi vlastnit ucet odosielatela)
+/// - from_account: ucet odosielatela
+/// - transfers: prevody davky (prijemca, suma, poznamky) v poradi vykonania
+///
+/// # Navratova hodnota
+/// Vracia TransferBatch - vsetky transakcie davky maju spolocne batch_id,
+/// podla ktoreho sa da davka stornovat (reverse_batch)
+///
+/// # Poznamka
+/// Vsetky prevody prebiehaju v jednej databazovej transakcii - bud sa vykonaju
+/// vsetky, alebo ziadny. Vsetky ucty davky sa zamknu naraz v poradi podla ID.
+///
+/// # Chyby
+/// - BankError::InvalidBody: prazdna davka, viac ako MAX_BATCH_TRANSFERS prevodov
+///   alebo prijemca je ucet odosielatela
+/// - BankError::InvalidAmount: niektora suma nie je kladna
+/// - BankError::AccountNotFound: niektory ucet neexistuje
+/// - BankError::Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - BankError::InsufficientFunds: zostatok nestaci na celu davku
+/// - BankError::EmailNotVerified: pouzivatel nema overeny e-mail (REQUIRE_VERIFIED_EMAIL)
+/// - BankError::AccountTooNew: ucet odosielatela je mladsi ako MIN_ACCOUNT_AGE_SECS
+/// - BankError::AmountOutOfBounds: suma niektoreho prevodu je mimo rozsahu meny odosielatela
+/// - BankError::ConfirmationRequired: suma niektoreho prevodu je od TRANSFER_CONFIRMATION_THRESHOLD
+/// - chyby ensure_account_active pre odosielatela aj prijemcov
+pub async fn make_batch_transfer(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    user_id: Uuid,
+    from_account: Uuid,
+    transfers: &[BatchTransferItem],
+) -> Result<TransferBatch, BankError> {
+    if transfers.is_empty() {
+        return Err(BankError::InvalidBody(
+            "transfers must not be empty".to_string(),
+        ));
+    }
+    if transfers.len() > MAX_BATCH_TRANSFERS {
+        return Err(BankError::InvalidBody(format!(
+            "a batch may contain at most {MAX_BATCH_TRANSFERS} transfers"
+        )));
+    }
+    if transfers.iter().any(|t| t.to_account == from_account) {
+        return Err(BankError::InvalidBody(
+            "to_account must not be from_account".to_string(),
+        ));
+    }
+    if transfers.iter().any(|t| t.amount <= Decimal::ZERO) {
+        return Err(BankError::InvalidAmount);
+    }
+    for transfer in transfers {
+        check_confirmation_threshold(config, transfer.amount)?;
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Zamknutie odosielatela aj vsetkych prijemcov naraz v stalom poradi
+    let locked: Vec<Uuid> = std::iter::once(from_account)
+        .chain(transfers.iter().map(|t| t.to_account))
+        .collect();
+    lock_accounts(&mut tx, &locked).await?;
+
+    let sender = query!(
+        r#"SELECT user_id, currency, status AS "status: AccountStatus" FROM accounts WHERE id = $1"#,
+        from_account
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    if sender.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+    ensure_account_active(sender.status)?;
+    for transfer in transfers {
+        check_amount_bounds(config, &sender.currency, transfer.amount)?;
+    }
+    ensure_email_verified(&mut tx, config, from_account).await?;
+    ensure_account_age(&mut tx, config, from_account).await?;
+
+    let batch_id = Uuid::new_v4();
+    query!(
+        "INSERT INTO transfer_batches (id, from_account) VALUES ($1, $2)",
+        batch_id,
+        from_account
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut transactions = Vec::with_capacity(transfers.len());
+    for transfer in transfers {
+        let transaction = execute_transfer(
+            &mut tx,
+            config,
+            rates,
+            from_account,
+            transfer.to_account,
+            transfer.amount,
+            TransferNotes {
+                sender: transfer.sender_note.as_deref(),
+                receiver: transfer.receiver_note.as_deref(),
+            },
+        )
+        .await?;
+        transactions.push(transaction);
+    }
+
+    let ids: Vec<Uuid> = transactions.iter().map(|t| t.id).collect();
+    query!(
+        "UPDATE transactions SET batch_id = $1 WHERE id = ANY($2)",
+        batch_id,
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(TransferBatch {
+        batch_id,
+        from_account,
+        total: transactions.iter().map(|t| t.amount).sum(),
+        transactions,
+    })
+}
+
+/// Stornuje celu hromadnu davku prevodov
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - batch_id: identifikator davky (TransferBatch::batch_id)
+///
+/// # Navratova hodnota
+/// Vracia BatchReversal so storno transakciami - kazda vracia sumu povodnej
+/// transakcie od prijemcu spat odosielatelovi a odkazuje na nu cez reversal_of
+///
+/// # Poznamka
+/// Pri prevode medzi menami sa prijemcovi odpise presne pripisana suma (credited_amount)
+/// a odosielatel dostane spat povodnu sumu - storno sa neprepocitava aktualnym kurzom.
+/// Vsetky storna prebiehaju v jednej databazovej transakcii. Ak niektory prijemca
+/// uz nema dost penazi (alebo jeho ucet nie je aktivny), nestornuje sa nic a
+/// zostatky zostanu bezo zmeny. Zahrnu sa aj uz archivovane transakcie davky.
+///
+/// # Chyby
+/// - BankError::BatchNotFound: davka neexistuje
+/// - BankError::BatchAlreadyReversed: davka uz bola stornovana
+/// - BankError::InsufficientFunds: niektory prijemca nema dost penazi na vratenie
+/// - chyby ensure_account_active pre prijemcov aj odosielatela
+pub async fn reverse_batch(
+    pool: &PgPool,
+    config: &Config,
+    batch_id: Uuid,
+) -> Result<BatchReversal, BankError> {
+    let mut tx = pool.begin().await?;
+
+    // Zamok davky - subezne storno tej istej davky pocka a skonci BatchAlreadyReversed
+    let batch = query!(
+        "SELECT reversed_at FROM transfer_batches WHERE id = $1 FOR UPDATE",
+        batch_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::BatchNotFound)?;
+
+    if batch.reversed_at.is_some() {
+        return Err(BankError::BatchAlreadyReversed);
+    }
+
+    let originals = query!(
+        r#"SELECT id AS "id!", from_account AS "from_account!", to_account AS "to_account!",
+                  amount AS "amount!", COALESCE(credited_amount, amount) AS "credited_amount!"
+           FROM (
+               SELECT id, from_account, to_account, amount, credited_amount, created_at, batch_id
+               FROM transactions
+               UNION ALL
+               SELECT id, from_account, to_account, amount, credited_amount, created_at, batch_id
+               FROM archived_transactions
+           ) t
+           WHERE batch_id = $1
+           ORDER BY created_at, id"#,
+        batch_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Zamknutie vsetkych uctov davky naraz v stalom poradi
+    let locked: Vec<Uuid> = originals
+        .iter()
+        .flat_map(|t| [t.from_account, t.to_account])
+        .collect();
+    lock_accounts(&mut tx, &locked).await?;
+
+    let mut transactions = Vec::with_capacity(originals.len());
+    for original in &originals {
+        let transaction = transfer_funds(
+            &mut tx,
+            config,
+            original.to_account,
+            original.from_account,
+            original.credited_amount,
+            TransferOrigin::Reversal {
+                credit: original.amount,
+            },
+        )
+        .await?;
+
+        query!(
+            "UPDATE transactions SET reversal_of = $1 WHERE id = $2",
+            original.id,
+            transaction.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        transactions.push(transaction);
+    }
+
+    let reversed_at = query!(
+        r#"UPDATE transfer_batches SET reversed_at = NOW() WHERE id = $1
+           RETURNING reversed_at AS "reversed_at!""#,
+        batch_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .reversed_at;
+
+    tx.commit().await?;
+
+    Ok(BatchReversal {
+        batch_id,
+        reversed_at,
+        total: transactions.iter().map(|t| t.amount).sum(),
+        transactions,
+    })
+}
+
+/// Zacne prevod, ktory moze vyzadovat potvrdenie
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - from_account, to_account, amount: ako pri make_transaction
+/// - notes: poznamky odosielatela a pre prijemcu
+///
+/// # Navratova hodnota
+/// - TransferInitiation::Completed: suma je pod hranicou, prevod sa vykonal hned
+/// - TransferInitiation::Pending: vytvoreny cakajuci prevod s tokenom na potvrdenie
+///
+/// # Poznamka
+/// Cakajuci prevod nepresuva peniaze. Zostatok sa overi az pri potvrdeni,
+/// rozsah sumy (AMOUNT_BOUNDS) uz pri zacati.
+///
+/// # Konfiguracia
+/// - Config::transfer_confirmation_threshold (TRANSFER_CONFIRMATION_THRESHOLD): od tejto
+///   sumy je potrebne potvrdenie (bez nej nikdy)
+/// - TRANSFER_CONFIRMATION_TTL_SECS: platnost tokenu (predvolene 300 s)
+pub async fn initiate_transfer(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    from_account: Uuid,
+    to_account: Uuid,
+    amount: Decimal,
+    notes: TransferNotes<'_>,
+) -> Result<TransferInitiation, BankError> {
+    // Male prevody nevyzaduju potvrdenie
+    if check_confirmation_threshold(config, amount).is_ok() {
+        let request = TransactionRequest {
+            from_account,
+            to_account,
+            amount,
+            sender_note: notes.sender.map(str::to_string),
+            receiver_note: notes.receiver.map(str::to_string),
+            idempotency_key: None,
+            splits: Vec::new(),
+            pin: None,
+        };
+        let transaction = make_transaction(pool, config, rates, &request).await?;
+        return Ok(TransferInitiation::Completed {
+            transaction: Box::new(transaction),
+        });
+    }
+
+    if amount <= Decimal::ZERO {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let sender_note = sanitize_optional_note(notes.sender, config.transaction_note_max_length)?;
+    let receiver_note = sanitize_optional_note(notes.receiver, config.transaction_note_max_length)?;
+
+    ensure_account_exists(pool, from_account).await?;
+    ensure_account_exists(pool, to_account).await?;
+    ensure_amount_in_bounds(&mut *pool.acquire().await?, config, from_account, amount).await?;
+
+    let row = query!(
+        "INSERT INTO pending_transfers
+             (id, token, from_account, to_account, amount, sender_note, receiver_note, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, NOW() + make_interval(secs => $8))
+         RETURNING id, token, expires_at",
+        Uuid::new_v4(),
+        Uuid::new_v4().simple().to_string(),
+        from_account,
+        to_account,
+        amount,
+        sender_note,
+        receiver_note,
+        config.transfer_confirmation_ttl.as_secs_f64()
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(TransferInitiation::Pending {
+        id: row.id,
+        token: row.token,
+        expires_at: row.expires_at,
+    })
+}
+
+/// Potvrdi cakajuci prevod a vykona ho
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - token: token z initiate_transfer
+///
+/// # Navratova hodnota
+/// Vracia vykonanu Transaction
+///
+/// # Bezpecnost
+/// Cakajuci prevod je zamknuty (FOR UPDATE) a presun penazi prebieha v tej istej
+/// databazovej transakcii, takze token nie je mozne pouzit dvakrat.
+///
+/// # Chyby
+/// - BankError::TransferNotFound: token neexistuje alebo uz bol pouzity
+/// - BankError::TransferExpired: platnost tokenu vyprsala
+/// - BankError::InsufficientFunds: odosielatel uz nema dostatocny zostatok
+/// - BankError::EmailNotVerified: odosielatel nema overeny e-mail (REQUIRE_VERIFIED_EMAIL)
+/// - BankError::AccountTooNew: ucet odosielatela je mladsi ako MIN_ACCOUNT_AGE_SECS
+pub async fn confirm_transfer(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+    token: &str,
+) -> Result<Transaction, BankError> {
+    let mut tx = pool.begin().await?;
+
+    let pending = query!(
+        r#"SELECT id, from_account, to_account, amount, sender_note, receiver_note,
+                  expires_at < NOW() AS "expired!"
+           FROM pending_transfers
+           WHERE token = $1 AND status = 'pending'
+           FOR UPDATE"#,
+        token
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::TransferNotFound)?;
+
+    if pending.expired {
+        query!(
+            "UPDATE pending_transfers SET status = 'expired' WHERE id = $1",
+            pending.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        return Err(BankError::TransferExpired);
+    }
+
+    ensure_email_verified(&mut tx, config, pending.from_account).await?;
+    ensure_account_age(&mut tx, config, pending.from_account).await?;
+
+    // Prevod s opatovnou kontrolou zostatku v tej istej transakcii
+    let transaction = execute_transfer(
+        &mut tx,
+        config,
+        rates,
+        pending.from_account,
+        pending.to_account,
+        pending.amount,
+        TransferNotes {
+            sender: pending.sender_note.as_deref(),
+            receiver: pending.receiver_note.as_deref(),
+        },
+    )
+    .await?;
+
+    query!(
+        "UPDATE pending_transfers SET status = 'confirmed', transaction_id = $1 WHERE id = $2",
+        transaction.id,
+        pending.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(transaction)
+}
+
+/// Zrusi prevod, ktory este nebol vykonany
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela (vlastnik uctu odosielatela)
+/// - transfer_id: ID cakajuceho prevodu (z /transactions/initiate) alebo trvaleho prikazu
+///
+/// # Navratova hodnota
+/// Vracia CancelledTransfer s druhom a udajmi zruseneho prevodu
+///
+/// # Poznamka
+/// Cakajuci prevod dostane stav `cancelled` - jeho token uz nejde potvrdit a prestane
+/// blokovat zatvorenie uctov (account_has_holds). Trvaly prikaz sa deaktivuje.
+/// Peniaze sa nepresuvaju, cakajuci prevod ich neblokoval.
+///
+/// # Chyby
+/// - BankError::TransferNotFound: prevod s danym ID neexistuje
+/// - BankError::Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - BankError::TransferExpired: platnost cakajuceho prevodu uz vyprsala
+/// - BankError::TransferNotPending: prevod uz bol vykonany alebo zruseny
+pub async fn cancel_transfer(
+    pool: &PgPool,
+    user_id: Uuid,
+    transfer_id: Uuid,
+) -> Result<CancelledTransfer, BankError> {
+    let mut tx = pool.begin().await?;
+
+    // Zamok riadku - subezne potvrdenie a zrusenie sa vyhodnotia postupne
+    let pending = query!(
+        r#"SELECT p.from_account, p.to_account, p.amount, p.status,
+                  p.expires_at < NOW() AS "expired!", a.user_id
+           FROM pending_transfers p
+           JOIN accounts a ON a.id = p.from_account
+           WHERE p.id = $1
+           FOR UPDATE OF p"#,
+        transfer_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(pending) = pending {
+        if pending.user_id != user_id {
+            return Err(BankError::Forbidden);
+        }
+        match pending.status.as_str() {
+            "pending" if pending.expired => {
+                query!(
+                    "UPDATE pending_transfers SET status = 'expired' WHERE id = $1",
+                    transfer_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+                return Err(BankError::TransferExpired);
+            }
+            "pending" => {}
+            "expired" => return Err(BankError::TransferExpired),
+            _ => return Err(BankError::TransferNotPending),
+        }
+
+        query!(
+            "UPDATE pending_transfers SET status = 'cancelled' WHERE id = $1",
+            transfer_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        return Ok(CancelledTransfer {
+            id: transfer_id,
+            kind: CancelledTransferKind::Pending,
+            from_account: pending.from_account,
+            to_account: pending.to_account,
+            amount: pending.amount,
+        });
+    }
+
+    let recurring = query!(
+        "SELECT user_id, from_account, to_account, amount, active
+         FROM recurring_transfers WHERE id = $1 FOR UPDATE",
+        transfer_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::TransferNotFound)?;
+
+    if recurring.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+    if !recurring.active {
+        return Err(BankError::TransferNotPending);
+    }
+
+    query!(
+        "UPDATE recurring_transfers SET active = FALSE WHERE id = $1",
+        transfer_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(CancelledTransfer {
+        id: transfer_id,
+        kind: CancelledTransferKind::Recurring,
+        from_account: recurring.from_account,
+        to_account: recurring.to_account,
+        amount: recurring.amount,
+    })
+}
+
+/// Najdlhsia povolena dlzka e-mailovej adresy (RFC 5321)
+const MAX_EMAIL_LEN: usize = 254;
+
+/// Normalizuje a overi tvar e-mailovej adresy
+///
+/// # Navratova hodnota
+/// Vracia adresu bez okrajovych medzier a malymi pismenami
+///
+/// # Chyby
+/// - BankError::InvalidBody: adresa nema tvar `meno@domena.tld` alebo je prilis dlha
+fn normalize_email(email: &str) -> Result<String, BankError> {
+    let email = email.trim().to_lowercase();
+    let valid = email.len() <= MAX_EMAIL_LEN
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+        && email.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+        });
+
+    if !valid {
+        return Err(BankError::InvalidBody("invalid email address".to_string()));
+    }
+    Ok(email)
+}
+
+/// Vyda overovaci token pre e-mailovu adresu pouzivatela
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+/// - email: adresa, ktora sa ma overit
+///
+/// # Navratova hodnota
+/// Vracia EmailVerificationSent s adresou a casom platnosti (bez tokenu)
+///
+/// # Poznamka
+/// Token sa neposiela v odpovedi, ale mimo nej ako notifikacia EmailVerification
+/// v tej istej transakcii - kto ma len kluc, adresu neoveri bez pristupu k schranke.
+/// Adresa sa k pouzivatelovi zapise az po overeni - predchadzajuca overena adresa
+/// dovtedy plati.
+///
+/// # Konfiguracia
+/// - EMAIL_VERIFICATION_TTL_SECS: platnost tokenu (predvolene 86400 s)
+///
+/// # Chyby
+/// - BankError::InvalidBody: neplatna e-mailova adresa
+pub async fn request_email_verification(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    email: &str,
+) -> Result<EmailVerificationSent, BankError> {
+    let email = normalize_email(email)?;
+
+    let mut tx = pool.begin().await?;
+
+    let row = query!(
+        "INSERT INTO email_verifications (token, user_id, email, expires_at)
+         VALUES ($1, $2, $3, NOW() + make_interval(secs => $4))
+         RETURNING token, email, expires_at",
+        Uuid::new_v4().simple().to_string(),
+        user_id,
+        email,
+        config.email_verification_ttl.as_secs_f64()
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    notify(
+        &mut tx,
+        user_id,
+        NewNotification {
+            event: NotificationEvent::EmailVerification,
+            transaction_id: None,
+            amount: None,
+            detail: Some(&row.token),
+        },
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(EmailVerificationSent {
+        email: row.email,
+        expires_at: row.expires_at,
+    })
+}
+
+/// Overi e-mailovu adresu tokenom z request_email_verification
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - token: overovaci token
+///
+/// # Navratova hodnota
+/// Vracia EmailStatus s novou overenou adresou
+///
+/// # Chyby
+/// - BankError::InvalidVerificationToken: token neexistuje, uz bol pouzity alebo vyprsal
+pub async fn verify_email(pool: &PgPool, token: &str) -> Result<EmailStatus, BankError> {
+    let mut tx = pool.begin().await?;
+
+    let pending = query!(
+        r#"SELECT user_id, email
+           FROM email_verifications
+           WHERE token = $1 AND verified_at IS NULL AND expires_at > NOW()
+           FOR UPDATE"#,
+        token
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(BankError::InvalidVerificationToken)?;
+
+    query!(
+        "UPDATE email_verifications SET verified_at = NOW() WHERE token = $1",
+        token
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let user = query!(
+        "UPDATE users SET email = $1, email_verified = TRUE WHERE id = $2
+         RETURNING id, email, email_verified",
+        pending.email,
+        pending.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(EmailStatus {
+        user_id: user.id,
+        email: user.email,
+        email_verified: user.email_verified,
+    })
+}
+
+/// Prihlasenie pouzivatela pomocou mena a hesla
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - username: pouzivatelske meno
+/// - password: heslo v plain texte
+///
+/// # Navratova hodnota
+/// Vracia PublicUser alebo String s chybovou spravou
+///
+/// # Bezpecnost
+/// - Heslo je overovane funkciou verify_password (Argon2, bcrypt alebo scrypt podla prefixu hashu)
+/// - Nehashuje sa znovu, len sa porovna s ulozenim hashom
+/// - Algoritmus, varianta a verzia sa beru z ulozeneho hashu, takze funguje aj pre hashe
+///   vytvorene pred zmenou konfiguracie (PASSWORD_HASHER, ARGON2_*)
+///
+/// # Chyby
+/// - "User not found": pouzivatel s danym menom neexistuje
+/// - "Invalid password hash": chyba pri parsovani hashu z databazy
+/// - "Invalid password": heslo sa nezhoduje
+pub async fn login_user(
+    pool: &PgPool,
+    username: &str,
+    password: &str,
+) -> Result<PublicUser, String> {
+    // Ziskanie pouzivatela z databazy
+    let user = query!(
+        "SELECT id, username, password_hash FROM users WHERE username = $1",
+        username
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|_| "User not found".to_string())?;
+
+    // Overenie hesla algoritmom podla prefixu ulozeneho hashu (parametre su v hashi)
+    let valid = verify_password(password, &user.password_hash)
+        .map_err(|_| "Invalid password hash".to_string())?;
+    if !valid {
+        return Err("Invalid password".to_string());
+    }
+
+    Ok(PublicUser {
+        id: user.id,
+        username: user.username,
+    })
+}
+
+/// Zaznamena pokus o prihlasenie
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - username: zadane pouzivatelske meno (aj neexistujuce)
+/// - success: ci bolo prihlasenie uspesne
+/// - client: IP adresa a user agent klienta
+///
+/// # Poznamka
+/// Pokus o prihlasenie neexistujuceho pouzivatela sa ulozi len s menom
+/// (user_id je NULL), pouzivatel sa nevytvara. Uspesne prihlasenie
+/// s user agentom, s ktorym sa pouzivatel este neprihlasil, vyvola
+/// notifikaciu NewDeviceLogin (okrem uplne prveho prihlasenia).
+pub async fn record_login_attempt(
+    pool: &PgPool,
+    username: &str,
+    success: bool,
+    client: &ClientInfo,
+) -> Result<(), BankError> {
+    let mut tx = pool.begin().await?;
+
+    // Predchadzajuce uspesne prihlasenia - zistuju sa pred zapisom noveho pokusu
+    let previous = query!(
+        r#"SELECT COUNT(*) AS "logins!",
+                  COUNT(*) FILTER (WHERE la.user_agent IS NOT DISTINCT FROM $2) AS "same_device!"
+           FROM login_attempts la
+           JOIN users u ON u.id = la.user_id
+           WHERE u.username = $1 AND la.success"#,
+        username,
+        client.user_agent
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let attempt = query!(
+        "INSERT INTO login_attempts (id, user_id, username, success, ip, user_agent)
+         VALUES ($1, (SELECT id FROM users WHERE username = $2), $2, $3, $4, $5)
+         RETURNING user_id",
+        Uuid::new_v4(),
+        username,
+        success,
+        client.ip,
+        client.user_agent
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Nove zariadenie = uspesne prihlasenie s user agentom, ktory sa este neprihlasil
+    let new_device = success && previous.logins > 0 && previous.same_device == 0;
+
+    if let Some(user_id) = attempt.user_id.filter(|_| new_device) {
+        let detail = match (&client.user_agent, &client.ip) {
+            (Some(agent), Some(ip)) => format!("{agent} ({ip})"),
+            (Some(agent), None) => agent.clone(),
+            (None, Some(ip)) => ip.clone(),
+            (None, None) => "unknown device".to_string(),
+        };
+
+        notify(
+            &mut tx,
+            user_id,
+            NewNotification {
+                event: NotificationEvent::NewDeviceLogin,
+                transaction_id: None,
+                amount: None,
+                detail: Some(&detail),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Predvoleny pocet pokusov o prihlasenie na stranke historie
+pub const DEFAULT_LOGIN_HISTORY_LIMIT: i64 = 100;
+
+/// Najvyssi pocet pokusov o prihlasenie na stranke historie
+pub const MAX_LOGIN_HISTORY_LIMIT: i64 = 500;
+
+/// Ziska stranku pokusov o prihlasenie pouzivatela od najnovsieho
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+/// - query: filter uspechu a strankovanie
+///
+/// # Navratova hodnota
+/// Vracia LoginHistoryPage s celkovym poctom vyhovujucich pokusov a jednou strankou
+///
+/// # Chyby
+/// - BankError::InvalidBody: limit mimo 1..=500 alebo zaporny offset
+pub async fn list_login_attempts(
+    pool: &PgPool,
+    user_id: Uuid,
+    query: &LoginHistoryQuery,
+) -> Result<LoginHistoryPage, BankError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LOGIN_HISTORY_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    if !(1..=MAX_LOGIN_HISTORY_LIMIT).contains(&limit) {
+        return Err(BankError::InvalidBody(format!(
+            "limit must be between 1 and {MAX_LOGIN_HISTORY_LIMIT}"
+        )));
+    }
+    if offset < 0 {
+        return Err(BankError::InvalidBody(
+            "offset must not be negative".to_string(),
+        ));
+    }
+
+    let total = query!(
+        r#"SELECT COUNT(*) AS "total!"
+           FROM login_attempts
+           WHERE user_id = $1
+             AND ($2::boolean IS NULL OR success = $2)"#,
+        user_id,
+        query.success
+    )
+    .fetch_one(pool)
+    .await?
+    .total;
+
+    let rows = query!(
+        "SELECT id, success, ip, user_agent, created_at
+         FROM login_attempts
+         WHERE user_id = $1
+           AND ($2::boolean IS NULL OR success = $2)
+         ORDER BY created_at DESC, id
+         LIMIT $3 OFFSET $4",
+        user_id,
+        query.success,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let attempts = rows
+        .into_iter()
+        .map(|row| LoginAttempt {
+            id: row.id,
+            success: row.success,
+            ip: row.ip,
+            user_agent: row.user_agent,
+            created_at: row.created_at,
+        })
+        .collect();
+
+    Ok(LoginHistoryPage {
+        total,
+        limit,
+        offset,
+        attempts,
+    })
+}
+
+/// Ziska historiu vsetkych transakci pre dany ucet
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - order: smer zoradenia podla created_at (SortOrder::Desc = najnovsie prve)
+///
+/// # Navratova hodnota
+/// Vracia zoznam vsetkych transakci (odoslanych aj prijatych) zoradeny podla casu
+///
+/// # Poznamka
+/// Transakcie s rovnakym created_at su zoradene podla id, takze poradie je stale.
+/// Kazda transakcia obsahuje len poznamku strany, ktorou je dany ucet
+/// (poznamku odosielatela pre odoslane, poznamku prijemcu pre prijate).
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje (existujuci ucet bez transakcii vrati prazdny zoznam)
+pub async fn get_transaction_history(
+    pool: &PgPool,
+    account_id: Uuid,
+    order: SortOrder,
+) -> Result<Vec<Transaction>, BankError> {
+    ensure_account_exists(pool, account_id).await?;
+
+    // Pri rovnakom case rozhoduje id, takze poradie je stale a asc je presne opacne k desc
+    let rows = query!(
+        "SELECT id, reference, from_account, to_account, amount,
+                credited_amount, credited_currency, rate, currency, created_at,
+                CASE WHEN from_account = $1 THEN sender_note ELSE receiver_note END AS note
+         FROM transactions 
+         WHERE from_account = $1 OR to_account = $1
+         ORDER BY CASE WHEN $2 THEN created_at END ASC, CASE WHEN $2 THEN id END ASC,
+                  created_at DESC, id DESC",
+        account_id,
+        order == SortOrder::Asc
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Konvertovanie riadkov z databazy na Transaction struktury
+    let transactions = rows
+        .into_iter()
+        .map(|row| Transaction {
+            id: row.id,
+            reference: row.reference,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            currency: row.currency,
+            credited_amount: row.credited_amount,
+            credited_currency: row.credited_currency,
+            rate: row.rate,
+            created_at: row.created_at,
+            note: row.note,
+            parties: None,
+            splits: Vec::new(),
+        })
+        .collect();
+    Ok(transactions)
+}
+
+/// Doplni k transakciam mena vlastnikov uctov odosielatela a prijemcu
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - transactions: transakcie, ktorym sa nastavi pole parties
+///
+/// # Poznamka
+/// Mena sa nacitaju jednym dotazom pre vsetky ucty naraz. Vklad nema ucet
+/// odosielatela, preto ma from_username None (rovnako ako zmazany ucet).
+pub async fn expand_transaction_parties(
+    pool: &PgPool,
+    transactions: &mut [Transaction],
+) -> Result<(), BankError> {
+    let account_ids: Vec<Uuid> = transactions
+        .iter()
+        .flat_map(|t| [t.from_account, t.to_account])
+        .flatten()
+        .collect();
+
+    let owners: HashMap<Uuid, String> = query!(
+        "SELECT a.id, u.username
+         FROM accounts a
+         JOIN users u ON u.id = a.user_id
+         WHERE a.id = ANY($1)",
+        &account_ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.username))
+    .collect();
+
+    let username = |account: Option<Uuid>| account.and_then(|id| owners.get(&id).cloned());
+
+    for transaction in transactions.iter_mut() {
+        transaction.parties = Some(TransactionParties {
+            from_username: username(transaction.from_account),
+            to_username: username(transaction.to_account),
+        });
+    }
+
+    Ok(())
+}
+
+/// Najde transakciu podla referencneho cisla (napr. TRX-2024-000001)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - reference: referencne cislo prevodu
+///
+/// # Navratova hodnota
+/// Vracia transakciu bez poznamok (nie je znama strana, ktora sa pyta)
+///
+/// # Poznamka
+/// Hlada aj v archivovanych transakciach, referencne cislo plati trvalo.
+///
+/// # Chyby
+/// - BankError::TransactionNotFound: transakcia s danym cislom neexistuje
+pub async fn get_transaction_by_reference(
+    pool: &PgPool,
+    reference: &str,
+) -> Result<Transaction, BankError> {
+    let row = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account,
+                  amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at
+               FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at
+               FROM archived_transactions
+           ) t
+           WHERE reference = $1"#,
+        reference
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(BankError::TransactionNotFound)?;
+
+    Ok(Transaction {
+        id: row.id,
+        reference: row.reference,
+        from_account: row.from_account,
+        to_account: row.to_account,
+        amount: row.amount,
+        currency: row.currency,
+        credited_amount: row.credited_amount,
+        credited_currency: row.credited_currency,
+        rate: row.rate,
+        created_at: row.created_at,
+        note: None,
+        parties: None,
+        splits: Vec::new(),
+    })
+}
+
+/// Ziska detail jednej transakcie pre jej ucastnika
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - transaction_id: UUID transakcie
+/// - user_id: UUID prihlaseneho pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia transakciu s poznamkou strany, ktorou je pouzivatel
+/// (poznamka odosielatela ak vlastni ucet odosielatela, inak poznamka prijemcu)
+///
+/// # Poznamka
+/// Hlada aj v archivovanych transakciach.
+///
+/// # Chyby
+/// - BankError::TransactionNotFound: transakcia neexistuje
+/// - BankError::Forbidden: pouzivatel nevlastni ucet odosielatela ani prijemcu
+pub async fn get_transaction(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    user_id: Uuid,
+) -> Result<Transaction, BankError> {
+    let row = query!(
+        r#"SELECT t.id AS "id!", t.reference, t.from_account, t.to_account,
+                  t.amount AS "amount!", t.credited_amount, t.credited_currency, t.rate,
+                  t.currency, t.created_at, t.sender_note, t.receiver_note,
+                  fa.user_id AS "from_user?", ta.user_id AS "to_user?"
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM archived_transactions
+           ) t
+           LEFT JOIN accounts fa ON fa.id = t.from_account
+           LEFT JOIN accounts ta ON ta.id = t.to_account
+           WHERE t.id = $1"#,
+        transaction_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(BankError::TransactionNotFound)?;
+
+    let note = if row.from_user == Some(user_id) {
+        row.sender_note
+    } else if row.to_user == Some(user_id) {
+        row.receiver_note
+    } else {
+        return Err(BankError::Forbidden);
+    };
+
+    Ok(Transaction {
+        id: row.id,
+        reference: row.reference,
+        from_account: row.from_account,
+        to_account: row.to_account,
+        amount: row.amount,
+        currency: row.currency,
+        credited_amount: row.credited_amount,
+        credited_currency: row.credited_currency,
+        rate: row.rate,
+        created_at: row.created_at,
+        note,
+        parties: None,
+        splits: list_transaction_splits(&mut *pool.acquire().await?, row.id).await?,
+    })
+}
+
+/// Ziska stitky transakcie, ktore si nastavil pouzivatel
+async fn list_transaction_tags(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    user_id: Uuid,
+) -> Result<TransactionTags, BankError> {
+    let tags = query!(
+        "SELECT tag FROM transaction_tags WHERE transaction_id = $1 AND user_id = $2 ORDER BY tag",
+        transaction_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| row.tag)
+    .collect();
+
+    Ok(TransactionTags {
+        transaction_id,
+        tags,
+    })
+}
+
+/// Prida stitok k transakcii
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - transaction_id: UUID transakcie
+/// - user_id: UUID prihlaseneho pouzivatela (ucastnik transakcie)
+/// - tag: stitok, normalizuje sa malymi pismenami (pozri normalize_tag)
+///
+/// # Navratova hodnota
+/// Vracia vsetky stitky pouzivatela pre danu transakciu
+///
+/// # Poznamka
+/// Stitky su sukromne - kazdy ucastnik transakcie ma vlastne.
+/// Pridanie existujuceho stitku nic nezmeni.
+///
+/// # Chyby
+/// - BankError::InvalidTag: neplatny stitok
+/// - BankError::TransactionNotFound: transakcia neexistuje
+/// - BankError::Forbidden: pouzivatel nie je ucastnikom transakcie
+pub async fn add_transaction_tag(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    user_id: Uuid,
+    tag: &str,
+) -> Result<TransactionTags, BankError> {
+    let tag = normalize_tag(tag)?;
+    get_transaction(pool, transaction_id, user_id).await?;
+
+    query!(
+        "INSERT INTO transaction_tags (transaction_id, user_id, tag)
+         VALUES ($1, $2, $3)
+         ON CONFLICT DO NOTHING",
+        transaction_id,
+        user_id,
+        tag
+    )
+    .execute(pool)
+    .await?;
+
+    list_transaction_tags(pool, transaction_id, user_id).await
+}
+
+/// Odstrani stitok z transakcie
+///
+/// # Navratova hodnota
+/// Vracia zostavajuce stitky pouzivatela pre danu transakciu
+///
+/// # Chyby
+/// - BankError::InvalidTag: neplatny stitok
+/// - BankError::TransactionNotFound: transakcia neexistuje
+/// - BankError::Forbidden: pouzivatel nie je ucastnikom transakcie
+pub async fn remove_transaction_tag(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    user_id: Uuid,
+    tag: &str,
+) -> Result<TransactionTags, BankError> {
+    let tag = normalize_tag(tag)?;
+    get_transaction(pool, transaction_id, user_id).await?;
+
+    query!(
+        "DELETE FROM transaction_tags WHERE transaction_id = $1 AND user_id = $2 AND tag = $3",
+        transaction_id,
+        user_id,
+        tag
+    )
+    .execute(pool)
+    .await?;
+
+    list_transaction_tags(pool, transaction_id, user_id).await
+}
+
+/// Ponecha v historii uctu len transakcie so stitkom vlastnika uctu
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu, ktoreho historia sa filtruje
+/// - tag: stitok (normalizuje sa ako pri pridani)
+/// - transactions: historia uctu
+///
+/// # Chyby
+/// - BankError::InvalidTag: neplatny stitok
+pub async fn filter_transactions_by_tag(
+    pool: &PgPool,
+    account_id: Uuid,
+    tag: &str,
+    transactions: &mut Vec<Transaction>,
+) -> Result<(), BankError> {
+    let tag = normalize_tag(tag)?;
+
+    let tagged: HashSet<Uuid> = query!(
+        "SELECT tt.transaction_id
+         FROM transaction_tags tt
+         JOIN accounts a ON a.user_id = tt.user_id
+         WHERE a.id = $1 AND tt.tag = $2",
+        account_id,
+        tag
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| row.transaction_id)
+    .collect();
+
+    transactions.retain(|t| tagged.contains(&t.id));
+    Ok(())
+}
+
+/// Overi, ze zostatok kazdeho uctu zodpoveda jeho transakciam
+///
+/// # Navratova hodnota
+/// Vracia zoznam uctov, ktorych ulozeny zostatok sa lisi od suctu
+/// prijmov (prevody, vklady, urok) minus vydavkov; prazdny zoznam = knihy sedia
+///
+/// # Poznamka
+/// Pocita sa z aktivnych aj archivovanych transakcii jednym dotazom,
+/// takze vysledok zodpoveda jednemu konzistentnemu snimku databazy.
+pub async fn check_balance_consistency(
+    pool: &PgPool,
+) -> Result<Vec<BalanceDiscrepancy>, BankError> {
     let rows = query!(
-        "SELECT id, from_account, to_account, amount, created_at 
-         FROM transactions 
-         WHERE from_account = $1 OR to_account = $1
-         ORDER BY created_at DESC",
+        r#"WITH movements AS (
+               SELECT to_account AS account_id, COALESCE(credited_amount, amount) AS amount
+               FROM transactions
+               UNION ALL
+               SELECT from_account, -amount FROM transactions
+               UNION ALL
+               SELECT to_account, COALESCE(credited_amount, amount) FROM archived_transactions
+               UNION ALL
+               SELECT from_account, -amount FROM archived_transactions
+           )
+           SELECT a.id AS "account_id!", a.balance AS "stored_balance!",
+                  COALESCE(SUM(m.amount), 0) AS "computed_balance!"
+           FROM accounts a
+           LEFT JOIN movements m ON m.account_id = a.id
+           GROUP BY a.id, a.balance
+           HAVING a.balance <> COALESCE(SUM(m.amount), 0)
+           ORDER BY a.id"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let discrepancies = rows
+        .into_iter()
+        .map(|row| BalanceDiscrepancy {
+            account_id: row.account_id,
+            stored_balance: row.stored_balance,
+            computed_balance: row.computed_balance,
+            difference: row.stored_balance - row.computed_balance,
+        })
+        .collect();
+    Ok(discrepancies)
+}
+
+/// Vypocita suhrnne statistiky uctu za casove okno
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - from: zaciatok okna (vratane), None = bez obmedzenia
+/// - to: koniec okna (bez neho), None = bez obmedzenia
+///
+/// # Navratova hodnota
+/// Vracia AccountStats s prijmami, vydavkami, cistou zmenou a poctom transakcii
+///
+/// # Poznamka
+/// Hodnoty su pocitane agregacnymi funkciami v databaze, riadky sa nenacitavaju.
+/// Zahrnute su aj archivovane transakcie (rovnako ako v historii uctu).
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+pub async fn account_stats(
+    pool: &PgPool,
+    account_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<AccountStats, BankError> {
+    ensure_account_exists(pool, account_id).await?;
+
+    let row = query!(
+        r#"SELECT COALESCE(SUM(COALESCE(credited_amount, amount)) FILTER (WHERE to_account = $1), 0)
+                      AS "total_credits!",
+                  COALESCE(SUM(amount) FILTER (WHERE from_account = $1), 0) AS "total_debits!",
+                  COUNT(*) AS "transaction_count!"
+           FROM (SELECT from_account, to_account, amount, credited_amount, created_at
+                 FROM transactions
+                 UNION ALL
+                 SELECT from_account, to_account, amount, credited_amount, created_at
+                 FROM archived_transactions) t
+           WHERE (from_account = $1 OR to_account = $1)
+             AND ($2::timestamptz IS NULL OR created_at >= $2)
+             AND ($3::timestamptz IS NULL OR created_at < $3)"#,
+        account_id,
+        from,
+        to
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(AccountStats {
+        account_id,
+        total_credits: row.total_credits,
+        total_debits: row.total_debits,
+        net_change: row.total_credits - row.total_debits,
+        transaction_count: row.transaction_count,
+    })
+}
+
+/// Vypocita suhrnne statistiky celeho systemu
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - from: zaciatok okna objemu transakcii (vratane), None = bez obmedzenia
+/// - to: koniec okna (bez neho), None = bez obmedzenia
+///
+/// # Navratova hodnota
+/// Vracia SystemStats s poctom pouzivatelov a uctov, zostatkami podla meny
+/// a objemom transakcii v okne podla meny
+///
+/// # Poznamka
+/// Hodnoty su pocitane agregacnymi funkciami v databaze v jednej transakcii
+/// (REPEATABLE READ), takze pocty a sucty zodpovedaju tomu istemu okamihu.
+/// Objem zahrna aj archivovane transakcie, zostatok zaokruhlovacieho uctu nie.
+pub async fn system_stats(
+    pool: &PgPool,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<SystemStats, BankError> {
+    let mut tx = pool.begin().await?;
+
+    query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    let counts = query!(
+        r#"SELECT (SELECT COUNT(*) FROM users) AS "user_count!",
+                  (SELECT COUNT(*) FROM accounts) AS "account_count!""#
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let balances = query!(
+        r#"SELECT currency, COUNT(*) AS "account_count!", SUM(balance) AS "total_balance!"
+           FROM accounts
+           GROUP BY currency
+           ORDER BY currency"#
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| CurrencyBalanceTotal {
+        currency: row.currency,
+        account_count: row.account_count,
+        total_balance: row.total_balance,
+    })
+    .collect();
+
+    let volume = query!(
+        r#"SELECT currency, COUNT(*) AS "transaction_count!", SUM(amount) AS "total_amount!"
+           FROM (SELECT currency, amount, created_at FROM transactions
+                 UNION ALL
+                 SELECT currency, amount, created_at FROM archived_transactions) t
+           WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+             AND ($2::timestamptz IS NULL OR created_at < $2)
+           GROUP BY currency
+           ORDER BY currency NULLS LAST"#,
+        from,
+        to
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| CurrencyVolume {
+        currency: row.currency,
+        transaction_count: row.transaction_count,
+        total_amount: row.total_amount,
+    })
+    .collect();
+
+    tx.commit().await?;
+
+    Ok(SystemStats {
+        user_count: counts.user_count,
+        account_count: counts.account_count,
+        balances,
+        from,
+        to,
+        volume,
+    })
+}
+
+/// Zoznam protistran uctu (ucty, s ktorymi ucet obchodoval)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - user_id: UUID prihlaseneho pouzivatela (musi byt vlastnikom uctu)
+///
+/// # Navratova hodnota
+/// Vracia protistrany s poctom transakcii a casom poslednej, najcastejsie prve
+///
+/// # Poznamka
+/// Zapocitavaju sa aktivne aj archivovane transakcie. Vklady, odpisy a transakcie
+/// so zmazanym uctom protistrany nemaju protistranu a vynechaju sa.
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+/// - BankError::Forbidden: ucet patri inemu pouzivatelovi
+pub async fn list_counterparties(
+    pool: &PgPool,
+    account_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<Counterparty>, BankError> {
+    let owner = query!("SELECT user_id FROM accounts WHERE id = $1", account_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(BankError::AccountNotFound)?
+        .user_id;
+    if owner != user_id {
+        return Err(BankError::Forbidden);
+    }
+
+    let rows = query!(
+        r#"WITH movements AS (
+               SELECT to_account AS counterparty, TRUE AS sent, created_at
+               FROM transactions WHERE from_account = $1
+               UNION ALL
+               SELECT from_account, FALSE, created_at
+               FROM transactions WHERE to_account = $1
+               UNION ALL
+               SELECT to_account, TRUE, created_at
+               FROM archived_transactions WHERE from_account = $1
+               UNION ALL
+               SELECT from_account, FALSE, created_at
+               FROM archived_transactions WHERE to_account = $1
+           )
+           SELECT a.id AS "account_id!", a.account_number, a.user_id AS "user_id!",
+                  COUNT(*) AS "transaction_count!",
+                  COUNT(*) FILTER (WHERE m.sent) AS "sent_count!",
+                  COUNT(*) FILTER (WHERE NOT m.sent) AS "received_count!",
+                  MAX(m.created_at) AS last_transacted_at
+           FROM movements m
+           JOIN accounts a ON a.id = m.counterparty
+           WHERE m.counterparty <> $1
+           GROUP BY a.id, a.account_number, a.user_id
+           ORDER BY COUNT(*) DESC, MAX(m.created_at) DESC NULLS LAST, a.id"#,
         account_id
     )
-    .fetch_all(&pool)
+    .fetch_all(pool)
     .await?;
 
-    // Konvertovanie riadkov z databazy na Transaction struktury
-    let transactions = rows
+    let counterparties = rows
         .into_iter()
-        .map(|row| Transaction {
+        .map(|row| Counterparty {
+            account_id: row.account_id,
+            account_number: row.account_number,
+            user_id: Some(row.user_id),
+            transaction_count: row.transaction_count,
+            sent_count: row.sent_count,
+            received_count: row.received_count,
+            last_transacted_at: row.last_transacted_at,
+        })
+        .collect();
+    Ok(counterparties)
+}
+
+/// Spocita zostatky pouzivatela podla meny
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia mapu mena -> sucet zostatkov cez vsetky nezatvorene ucty pouzivatela
+///
+/// # Chyby
+/// - BankError::UserNotFound: pouzivatel neexistuje
+pub async fn get_balances_by_currency(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<BTreeMap<String, Decimal>, BankError> {
+    query!("SELECT id FROM users WHERE id = $1", user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(BankError::UserNotFound)?;
+
+    let rows = query!(
+        r#"SELECT currency, SUM(balance) AS "total!"
+           FROM accounts
+           WHERE user_id = $1 AND status <> 'closed'
+           GROUP BY currency"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.currency, row.total))
+        .collect())
+}
+
+/// Spocita celkovy zostatok pouzivatela cez vsetky nezatvorene ucty
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia presny sucet zostatkov (numeric -> Decimal bez straty presnosti),
+/// pouzivatel bez uctov ma sucet 0
+///
+/// # Poznamka
+/// Sucet nerozlisuje meny - pri uctoch v roznych menach pouzite get_balances_by_currency.
+///
+/// # Chyby
+/// - BankError::UserNotFound: pouzivatel neexistuje
+pub async fn total_balance(pool: &PgPool, user_id: Uuid) -> Result<Decimal, BankError> {
+    query!("SELECT id FROM users WHERE id = $1", user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(BankError::UserNotFound)?;
+
+    // SUM nad ziadnymi riadkami vrati NULL
+    let total = query!(
+        r#"SELECT SUM(balance) AS "total: Decimal"
+           FROM accounts
+           WHERE user_id = $1 AND status <> 'closed'"#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?
+    .total;
+
+    Ok(total.unwrap_or(Decimal::ZERO))
+}
+
+/// Vytvori novy API kluc pre pouzivatela
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia CreatedApiKey s celym klucom - kluc sa zobrazi len raz
+///
+/// # Bezpecnost
+/// Kluc ma 256 bitov nahodnosti. V databaze je ulozeny len jeho SHA-256 hash,
+/// takze z databazy sa kluc neda ziskat.
+pub async fn create_api_key(pool: &PgPool, user_id: Uuid) -> Result<CreatedApiKey, BankError> {
+    // Generovanie nahodneho kluca
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let key = format!(
+        "bk_{}",
+        secret
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    );
+    let prefix = key[..11].to_string();
+
+    let row = query!(
+        "INSERT INTO api_keys (id, user_id, key_hash, prefix) VALUES ($1, $2, $3, $4)
+         RETURNING id, created_at",
+        Uuid::new_v4(),
+        user_id,
+        hash_api_key(&key),
+        prefix
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(CreatedApiKey {
+        id: row.id,
+        key,
+        prefix,
+        created_at: row.created_at,
+    })
+}
+
+/// Zrusi API kluc pouzivatela
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID vlastnika kluca
+/// - key_id: UUID kluca
+///
+/// # Chyby
+/// - BankError::ApiKeyNotFound: kluc neexistuje, patri inemu pouzivatelovi alebo uz je zruseny
+pub async fn revoke_api_key(pool: &PgPool, user_id: Uuid, key_id: Uuid) -> Result<(), BankError> {
+    let result = query!(
+        "UPDATE api_keys SET revoked_at = NOW()
+         WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        key_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(BankError::ApiKeyNotFound);
+    }
+
+    Ok(())
+}
+
+/// Ziska zoznam API klucov pouzivatela (aktivnych aj zrusenych)
+pub async fn list_api_keys(pool: &PgPool, user_id: Uuid) -> Result<Vec<ApiKeyInfo>, BankError> {
+    let rows = query!(
+        "SELECT id, prefix, created_at, revoked_at FROM api_keys
+         WHERE user_id = $1 ORDER BY created_at DESC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ApiKeyInfo {
             id: row.id,
-            from_account: row.from_account,
-            to_account: row.to_account,
-            amount: row.amount,
+            prefix: row.prefix,
             created_at: row.created_at,
+            revoked_at: row.revoked_at,
         })
-        .collect();
-    Ok(transactions)
+        .collect())
+}
+
+/// Overi API kluc a vrati ID jeho vlastnika
+///
+/// # Chyby
+/// - BankError::Unauthorized: kluc neexistuje alebo je zruseny
+pub async fn authenticate_api_key(pool: &PgPool, key: &str) -> Result<Uuid, BankError> {
+    query!(
+        "SELECT user_id FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+        hash_api_key(key)
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row| row.user_id)
+    .ok_or(BankError::Unauthorized)
+}
+
+/// SHA-256 hash API kluca v hex tvare
+fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Overi, ze ucet s danym ID existuje
+///
+/// # Chyby
+/// - BankError::AccountNotFound: ucet neexistuje
+pub(crate) async fn ensure_account_exists(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<(), BankError> {
+    query!("SELECT id FROM accounts WHERE id = $1", account_id)
+        .fetch_optional(pool)
+        .await?
+        .map(|_| ())
+        .ok_or(BankError::AccountNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(category: &str, amount: i64) -> TransactionSplit {
+        TransactionSplit {
+            category: category.to_string(),
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn normalize_splits_accepts_parts_summing_to_amount() {
+        let splits = [split("Rent", 60), split("utilities", 40)];
+
+        let normalized = normalize_splits(Decimal::from(100), &splits).unwrap();
+
+        let categories: Vec<&str> = normalized.iter().map(|s| s.category.as_str()).collect();
+        assert_eq!(categories, ["rent", "utilities"]);
+        assert_eq!(normalized[0].amount, Decimal::from(60));
+    }
+
+    #[test]
+    fn normalize_splits_rejects_mismatched_sum() {
+        let splits = [split("rent", 60), split("utilities", 30)];
+
+        let result = normalize_splits(Decimal::from(100), &splits);
+
+        assert!(matches!(result, Err(BankError::InvalidBody(_))));
+    }
+
+    #[test]
+    fn normalize_splits_reports_bad_category_as_invalid_body() {
+        let splits = [split("rent!", 100)];
+
+        let result = normalize_splits(Decimal::from(100), &splits);
+
+        assert!(matches!(result, Err(BankError::InvalidBody(_))));
+    }
 }
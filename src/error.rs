@@ -0,0 +1,488 @@
+// error.rs
+use crate::amount_bounds::AmountBounds;
+use crate::models::AccountType;
+use crate::password::PasswordError;
+use crate::request_id::{current_request_id, log_prefix};
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use std::fmt;
+
+/// Spolocny typ chyby pre bankove operacie
+///
+/// Kazda varianta ma svoj HTTP status a strojovo citatelny kod,
+/// ktory sa vracia klientovi v tele odpovede:
+/// ```json
+/// {"error": {"code": "RATE_UNAVAILABLE", "message": "..."}}
+/// ```
+#[derive(Debug)]
+pub enum BankError {
+    /// Kurz pre danu dvojicu mien nie je k dispozicii
+    RateUnavailable { from: String, to: String },
+    /// Ucet s danym ID neexistuje
+    AccountNotFound,
+    /// Cislo uctu nema platny tvar alebo kontrolne cislice
+    InvalidAccountNumber,
+    /// Kod meny nie je platny (ISO 4217)
+    InvalidCurrency,
+    /// Presun zostatku medzi uctami v roznych menach (zatvorenie uctu) sa neprepocitava
+    CurrencyMismatch { from: String, to: String },
+    /// Suma musi byt kladna
+    InvalidAmount,
+    /// Pozadovany format sum v odpovedi nie je podporovany
+    InvalidAmountFormat,
+    /// Poznamka k transakcii obsahuje nulovy bajt alebo je prilis dlha
+    InvalidNote,
+    /// Stitok transakcie je prazdny, prilis dlhy alebo obsahuje nepovolene znaky
+    InvalidTag,
+    /// V tele poziadavky chybaju povinne polia (zoznam nazvov)
+    MissingFields(Vec<&'static str>),
+    /// Pole v tele poziadavky ma neplatnu hodnotu
+    InvalidBody(String),
+    /// Suma alebo vysledny zostatok prekracuje presnost stlpca v databaze
+    AmountTooLarge,
+    /// Suma je mimo rozsahu povoleneho pre menu uctu (AMOUNT_BOUNDS)
+    AmountOutOfBounds {
+        currency: String,
+        bounds: AmountBounds,
+    },
+    /// Nedostatocny zostatok na ucte odosielatela
+    InsufficientFunds,
+    /// Ucet odosielatela vycerpal mesacny pocet vyberov pre svoj typ (ACCOUNT_TYPE_RULES)
+    WithdrawalLimitReached {
+        account_type: AccountType,
+        limit: u32,
+    },
+    /// Rovnaky prevod bol prave vykonany (pravdepodobne dvojklik)
+    DuplicateTransfer,
+    /// Kluc idempotencie prevodu uz bol pouzity s inym prijemcom alebo sumou
+    IdempotencyKeyReused,
+    /// Transakcia neexistuje
+    TransactionNotFound,
+    /// Cakajuci prevod neexistuje alebo uz bol spracovany
+    TransferNotFound,
+    /// Platnost potvrdenia prevodu vyprsala
+    TransferExpired,
+    /// Prevod uz nie je mozne zrusit (bol vykonany alebo zruseny)
+    TransferNotPending,
+    /// Hromadna davka prevodov neexistuje
+    BatchNotFound,
+    /// Hromadna davka uz bola stornovana
+    BatchAlreadyReversed,
+    /// Prevody vyzaduju overeny e-mail (REQUIRE_VERIFIED_EMAIL) a pouzivatel ho nema
+    EmailNotVerified,
+    /// Overovaci token e-mailu neexistuje, bol pouzity alebo vyprsal
+    InvalidVerificationToken,
+    /// Ucet je mladsi ako MIN_ACCOUNT_AGE_SECS a zatial nemoze posielat peniaze
+    AccountTooNew,
+    /// Prevod od TRANSFER_PIN_THRESHOLD z uctu s PIN-om bez zadaneho PIN-u
+    PinRequired,
+    /// Zadany PIN nesedi s PIN-om uctu
+    InvalidPin,
+    /// Prevod od TRANSFER_CONFIRMATION_THRESHOLD treba zacat cez /transactions/initiate
+    ConfirmationRequired,
+    /// API kluc neexistuje alebo uz bol zruseny
+    ApiKeyNotFound,
+    /// Pouzivatel s danym ID neexistuje
+    UserNotFound,
+    /// Ucet ma nenulovy zostatok a nemoze byt zmazany
+    AccountHasFunds,
+    /// Ucet ma zaporny zostatok (precerpanie) a nemoze byt zatvoreny ani zluceny
+    AccountOverdrawn,
+    /// Ucet je zatvoreny (natrvalo)
+    AccountClosed,
+    /// Ucet je docasne zablokovany (moze byt odblokovany)
+    AccountFrozen,
+    /// Ucet caka na schvalenie administratorom
+    AccountPending,
+    /// Ucet nie je v stave cakania na schvalenie (uz bol schvaleny alebo zamietnuty)
+    AccountNotPending,
+    /// Na ucte su aktivne blokacie (cakajuce prevody)
+    AccountHasHolds,
+    /// Na ucte su naplanovane prevody (aktivne trvale prikazy)
+    AccountHasScheduledTransfers,
+    /// Prekroceny limit (napr. maximalny pocet uctov pouzivatela)
+    LimitExceeded,
+    /// Odkaz na neexistujuci zaznam (porusenie cudzieho kluca), obsahuje nazov entity
+    ReferenceNotFound(&'static str),
+    /// Zaznam s rovnakou jedinecnou hodnotou uz existuje (porusenie UNIQUE), obsahuje nazov pola
+    AlreadyExists(&'static str),
+    /// Chybajuce alebo neplatne prihlasovacie udaje (napr. API kluc)
+    Unauthorized,
+    /// Chyba opravnenia (napr. neplatny administratorsky token)
+    Forbidden,
+    /// Server je v rezime udrzby a neprijima zapisy
+    ReadOnly,
+    /// Spracovanie poziadavky prekrocilo casovy limit
+    Timeout,
+    /// Ucet je prave zamknuty inou operaciou a zamok sa nepodarilo ziskat vcas
+    Busy,
+    /// Prevod sa nepodarilo serializovat so subeznymi prevodmi ani po opakovaniach
+    SerializationFailure,
+    /// Prekroceny limit poziadaviek na trasu, obsahuje pocet sekund do dalsieho pokusu
+    RateLimited { retry_after_secs: u64 },
+    /// Pouzivatel ma prave rozbehnutych MAX_CONCURRENT_TRANSFERS_PER_USER prevodov
+    TooManyTransfers,
+    /// Heslo alebo PIN sa nepodarilo zahashovat (chyba kniznice alebo parametrov)
+    PasswordHashing(PasswordError),
+    /// Chyba databazy (nepredvidana)
+    Database(sqlx::Error),
+}
+
+impl BankError {
+    /// HTTP status zodpovedajuci chybe
+    pub fn status(&self) -> StatusCode {
+        match self {
+            BankError::RateUnavailable { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::AccountNotFound => StatusCode::NOT_FOUND,
+            BankError::InvalidAccountNumber => StatusCode::BAD_REQUEST,
+            BankError::InvalidCurrency => StatusCode::BAD_REQUEST,
+            BankError::CurrencyMismatch { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::InvalidAmount => StatusCode::BAD_REQUEST,
+            BankError::InvalidAmountFormat => StatusCode::BAD_REQUEST,
+            BankError::InvalidNote => StatusCode::BAD_REQUEST,
+            BankError::InvalidTag => StatusCode::BAD_REQUEST,
+            BankError::MissingFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::InvalidBody(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::AmountTooLarge => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::AmountOutOfBounds { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::InsufficientFunds => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::WithdrawalLimitReached { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::DuplicateTransfer => StatusCode::CONFLICT,
+            BankError::IdempotencyKeyReused => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::TransactionNotFound => StatusCode::NOT_FOUND,
+            BankError::TransferNotFound => StatusCode::NOT_FOUND,
+            BankError::TransferExpired => StatusCode::GONE,
+            BankError::TransferNotPending => StatusCode::CONFLICT,
+            BankError::BatchNotFound => StatusCode::NOT_FOUND,
+            BankError::BatchAlreadyReversed => StatusCode::CONFLICT,
+            BankError::EmailNotVerified => StatusCode::FORBIDDEN,
+            BankError::InvalidVerificationToken => StatusCode::BAD_REQUEST,
+            BankError::AccountTooNew => StatusCode::FORBIDDEN,
+            BankError::PinRequired => StatusCode::FORBIDDEN,
+            BankError::InvalidPin => StatusCode::FORBIDDEN,
+            BankError::ConfirmationRequired => StatusCode::FORBIDDEN,
+            BankError::ApiKeyNotFound => StatusCode::NOT_FOUND,
+            BankError::UserNotFound => StatusCode::NOT_FOUND,
+            BankError::AccountHasFunds => StatusCode::CONFLICT,
+            BankError::AccountOverdrawn => StatusCode::CONFLICT,
+            BankError::AccountClosed => StatusCode::CONFLICT,
+            BankError::AccountFrozen => StatusCode::CONFLICT,
+            BankError::AccountPending => StatusCode::CONFLICT,
+            BankError::AccountNotPending => StatusCode::CONFLICT,
+            BankError::AccountHasHolds => StatusCode::CONFLICT,
+            BankError::AccountHasScheduledTransfers => StatusCode::CONFLICT,
+            BankError::LimitExceeded => StatusCode::UNPROCESSABLE_ENTITY,
+            BankError::ReferenceNotFound(_) => StatusCode::BAD_REQUEST,
+            BankError::AlreadyExists(_) => StatusCode::CONFLICT,
+            BankError::Unauthorized => StatusCode::UNAUTHORIZED,
+            BankError::Forbidden => StatusCode::FORBIDDEN,
+            BankError::ReadOnly => StatusCode::SERVICE_UNAVAILABLE,
+            BankError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            BankError::Busy => StatusCode::SERVICE_UNAVAILABLE,
+            BankError::SerializationFailure => StatusCode::SERVICE_UNAVAILABLE,
+            BankError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            BankError::TooManyTransfers => StatusCode::TOO_MANY_REQUESTS,
+            BankError::PasswordHashing(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            BankError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Strojovo citatelny kod chyby
+    pub fn code(&self) -> &'static str {
+        match self {
+            BankError::RateUnavailable { .. } => "RATE_UNAVAILABLE",
+            BankError::AccountNotFound => "ACCOUNT_NOT_FOUND",
+            BankError::InvalidAccountNumber => "INVALID_ACCOUNT_NUMBER",
+            BankError::InvalidCurrency => "INVALID_CURRENCY",
+            BankError::CurrencyMismatch { .. } => "CURRENCY_MISMATCH",
+            BankError::InvalidAmount => "INVALID_AMOUNT",
+            BankError::InvalidAmountFormat => "INVALID_AMOUNT_FORMAT",
+            BankError::InvalidNote => "INVALID_NOTE",
+            BankError::InvalidTag => "INVALID_TAG",
+            BankError::MissingFields(_) => "MISSING_FIELDS",
+            BankError::InvalidBody(_) => "INVALID_BODY",
+            BankError::AmountTooLarge => "AMOUNT_TOO_LARGE",
+            BankError::AmountOutOfBounds { .. } => "AMOUNT_OUT_OF_BOUNDS",
+            BankError::InsufficientFunds => "INSUFFICIENT_FUNDS",
+            BankError::WithdrawalLimitReached { .. } => "WITHDRAWAL_LIMIT_REACHED",
+            BankError::DuplicateTransfer => "DUPLICATE_TRANSFER",
+            BankError::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            BankError::TransactionNotFound => "TRANSACTION_NOT_FOUND",
+            BankError::TransferNotFound => "TRANSFER_NOT_FOUND",
+            BankError::TransferExpired => "TRANSFER_EXPIRED",
+            BankError::TransferNotPending => "TRANSFER_NOT_PENDING",
+            BankError::BatchNotFound => "BATCH_NOT_FOUND",
+            BankError::BatchAlreadyReversed => "BATCH_ALREADY_REVERSED",
+            BankError::EmailNotVerified => "EMAIL_NOT_VERIFIED",
+            BankError::InvalidVerificationToken => "INVALID_VERIFICATION_TOKEN",
+            BankError::AccountTooNew => "ACCOUNT_TOO_NEW",
+            BankError::PinRequired => "PIN_REQUIRED",
+            BankError::InvalidPin => "INVALID_PIN",
+            BankError::ConfirmationRequired => "CONFIRMATION_REQUIRED",
+            BankError::ApiKeyNotFound => "API_KEY_NOT_FOUND",
+            BankError::UserNotFound => "USER_NOT_FOUND",
+            BankError::AccountHasFunds => "ACCOUNT_HAS_FUNDS",
+            BankError::AccountOverdrawn => "ACCOUNT_OVERDRAWN",
+            BankError::AccountClosed => "ACCOUNT_CLOSED",
+            BankError::AccountFrozen => "ACCOUNT_FROZEN",
+            BankError::AccountPending => "ACCOUNT_PENDING",
+            BankError::AccountNotPending => "ACCOUNT_NOT_PENDING",
+            BankError::AccountHasHolds => "ACCOUNT_HAS_HOLDS",
+            BankError::AccountHasScheduledTransfers => "ACCOUNT_HAS_SCHEDULED_TRANSFERS",
+            BankError::LimitExceeded => "LIMIT_EXCEEDED",
+            BankError::ReferenceNotFound(_) => "REFERENCE_NOT_FOUND",
+            BankError::AlreadyExists(_) => "ALREADY_EXISTS",
+            BankError::Unauthorized => "UNAUTHORIZED",
+            BankError::Forbidden => "FORBIDDEN",
+            BankError::ReadOnly => "READ_ONLY",
+            BankError::Timeout => "TIMEOUT",
+            BankError::Busy => "BUSY",
+            BankError::SerializationFailure => "SERIALIZATION_FAILURE",
+            BankError::RateLimited { .. } => "RATE_LIMITED",
+            BankError::TooManyTransfers => "TOO_MANY_CONCURRENT_TRANSFERS",
+            BankError::PasswordHashing(_) => "PASSWORD_HASHING_ERROR",
+            BankError::Database(_) => "DATABASE_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankError::RateUnavailable { from, to } => {
+                write!(f, "Exchange rate {from} -> {to} is not available")
+            }
+            BankError::AccountNotFound => write!(f, "Account not found"),
+            BankError::InvalidAccountNumber => write!(f, "Invalid account number"),
+            BankError::InvalidCurrency => write!(f, "Invalid currency code"),
+            BankError::CurrencyMismatch { from, to } => {
+                write!(f, "Cannot move a {from} balance to a {to} account")
+            }
+            BankError::InvalidAmount => write!(f, "Amount must be positive"),
+            BankError::InvalidAmountFormat => write!(f, "Unsupported amount format"),
+            BankError::InvalidNote => write!(f, "Invalid transaction note"),
+            BankError::InvalidTag => write!(f, "Invalid transaction tag"),
+            BankError::MissingFields(fields) => {
+                write!(f, "Missing required fields: {}", fields.join(", "))
+            }
+            BankError::InvalidBody(reason) => write!(f, "Invalid request body: {reason}"),
+            BankError::AmountTooLarge => write!(f, "Amount is too large"),
+            BankError::AmountOutOfBounds { currency, bounds } => match (bounds.min, bounds.max) {
+                (Some(min), Some(max)) => {
+                    write!(f, "Amount must be between {min} and {max} {currency}")
+                }
+                (Some(min), None) => write!(f, "Amount must be at least {min} {currency}"),
+                (None, Some(max)) => write!(f, "Amount must be at most {max} {currency}"),
+                (None, None) => write!(f, "Amount is out of bounds for {currency}"),
+            },
+            BankError::InsufficientFunds => write!(f, "Insufficient funds"),
+            BankError::WithdrawalLimitReached {
+                account_type,
+                limit,
+            } => write!(
+                f,
+                "{} accounts allow at most {limit} withdrawals per month",
+                account_type.as_str()
+            ),
+            BankError::DuplicateTransfer => write!(
+                f,
+                "An identical transfer was just made; send an idempotency_key to repeat it intentionally"
+            ),
+            BankError::IdempotencyKeyReused => write!(
+                f,
+                "idempotency_key was already used for a transfer with a different recipient or amount"
+            ),
+            BankError::TransactionNotFound => write!(f, "Transaction not found"),
+            BankError::TransferNotFound => write!(f, "Pending transfer not found"),
+            BankError::TransferExpired => write!(f, "Transfer confirmation has expired"),
+            BankError::TransferNotPending => {
+                write!(f, "Transfer has already been settled or cancelled")
+            }
+            BankError::BatchNotFound => write!(f, "Transfer batch not found"),
+            BankError::BatchAlreadyReversed => write!(f, "Transfer batch was already reversed"),
+            BankError::EmailNotVerified => {
+                write!(f, "Email address must be verified before making transfers")
+            }
+            BankError::InvalidVerificationToken => {
+                write!(f, "Verification token is invalid or has expired")
+            }
+            BankError::AccountTooNew => write!(f, "Account is too new to send money yet"),
+            BankError::PinRequired => write!(f, "Account PIN is required for this transfer"),
+            BankError::InvalidPin => write!(f, "Account PIN is incorrect"),
+            BankError::ConfirmationRequired => write!(
+                f,
+                "Transfers of this amount require confirmation, use /transactions/initiate"
+            ),
+            BankError::ApiKeyNotFound => write!(f, "API key not found"),
+            BankError::UserNotFound => write!(f, "User not found"),
+            BankError::AccountHasFunds => write!(f, "Account still has funds"),
+            BankError::AccountOverdrawn => write!(f, "Account has a negative balance"),
+            BankError::AccountClosed => write!(f, "Account is closed and cannot be reopened"),
+            BankError::AccountFrozen => write!(f, "Account is frozen"),
+            BankError::AccountPending => write!(f, "Account is awaiting approval"),
+            BankError::AccountNotPending => write!(f, "Account is not awaiting approval"),
+            BankError::AccountHasHolds => write!(f, "Account has pending transfers"),
+            BankError::AccountHasScheduledTransfers => {
+                write!(f, "Account has scheduled recurring transfers")
+            }
+            BankError::LimitExceeded => write!(f, "Limit exceeded"),
+            BankError::ReferenceNotFound(entity) => {
+                write!(f, "Referenced {entity} does not exist")
+            }
+            BankError::AlreadyExists(field) => write!(f, "{field} already exists"),
+            BankError::Unauthorized => write!(f, "Unauthorized"),
+            BankError::Forbidden => write!(f, "Forbidden"),
+            BankError::ReadOnly => write!(f, "Service is in read-only mode"),
+            BankError::Timeout => write!(f, "Request timed out"),
+            BankError::Busy => write!(f, "Account is busy, try again later"),
+            BankError::SerializationFailure => {
+                write!(
+                    f,
+                    "Transfer conflicted with a concurrent transfer, try again"
+                )
+            }
+            BankError::RateLimited { retry_after_secs } => {
+                write!(f, "Too many requests, retry in {retry_after_secs} s")
+            }
+            BankError::TooManyTransfers => {
+                write!(f, "Too many transfers in progress, try again later")
+            }
+            BankError::PasswordHashing(e) => write!(f, "{e}"),
+            BankError::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BankError {}
+
+/// SQLSTATE pre pretecenie ciselnej hodnoty (numeric_value_out_of_range)
+const NUMERIC_OUT_OF_RANGE: &str = "22003";
+
+/// SQLSTATE pre porusenie cudzieho kluca (foreign_key_violation)
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// SQLSTATE pre vyprsanie lock_timeout (lock_not_available)
+const LOCK_NOT_AVAILABLE: &str = "55P03";
+
+/// SQLSTATE pre konflikt serializovatelnej transakcie (serialization_failure)
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// Za kolko sekund ma klient zopakovat poziadavku pri BankError::Busy, SerializationFailure
+/// a TooManyTransfers
+const BUSY_RETRY_AFTER_SECS: u64 = 1;
+
+/// SQLSTATE pre porusenie jedinecnosti (unique_violation)
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// Jedinecne obmedzenia a pole, ktoreho hodnota sa opakuje
+///
+/// Nove UNIQUE obmedzenie (alebo index) treba doplnit sem, inak klient dostane
+/// vseobecnu spravu "record already exists".
+const UNIQUE_CONSTRAINTS: &[(&str, &str)] = &[
+    ("users_username_key", "username"),
+    ("idx_accounts_account_number", "account number"),
+    ("idx_transactions_deposit_idempotency", "idempotency key"),
+    ("idx_transactions_transfer_idempotency", "idempotency key"),
+    ("idx_transactions_reference", "transfer reference"),
+    ("idx_transactions_account_opening", "account opening"),
+    ("idx_archived_transactions_reference", "transfer reference"),
+    ("api_keys_key_hash_key", "API key"),
+    ("pending_transfers_token_key", "confirmation token"),
+];
+
+/// Urci pole podla nazvu porusenho jedinecneho obmedzenia
+fn duplicate_field(constraint: Option<&str>) -> &'static str {
+    constraint
+        .and_then(|c| {
+            UNIQUE_CONSTRAINTS
+                .iter()
+                .find(|(name, _)| *name == c)
+                .map(|(_, field)| *field)
+        })
+        .unwrap_or("record")
+}
+
+/// Urci odkazovanu entitu podla nazvu porusenho obmedzenia
+fn referenced_entity(constraint: Option<&str>) -> &'static str {
+    match constraint {
+        Some(c) if c.ends_with("user_id_fkey") => "user",
+        Some(c) if c.contains("account") => "account",
+        Some(c) if c.ends_with("transaction_id_fkey") => "transaction",
+        _ => "record",
+    }
+}
+
+impl From<PasswordError> for BankError {
+    fn from(e: PasswordError) -> Self {
+        BankError::PasswordHashing(e)
+    }
+}
+
+impl From<sqlx::Error> for BankError {
+    fn from(e: sqlx::Error) -> Self {
+        let Some(db) = e.as_database_error() else {
+            return BankError::Database(e);
+        };
+
+        match db.code().as_deref() {
+            // Pretecenie NUMERIC stlpca (napr. balance + obrovska suma)
+            Some(NUMERIC_OUT_OF_RANGE) => BankError::AmountTooLarge,
+            // Odkaz na neexistujuceho pouzivatela, ucet, ...
+            Some(FOREIGN_KEY_VIOLATION) => {
+                BankError::ReferenceNotFound(referenced_entity(db.constraint()))
+            }
+            // Zamok uctu sa nepodarilo ziskat do lock_timeout
+            Some(LOCK_NOT_AVAILABLE) => BankError::Busy,
+            // Subezny prevod zmenil data citane touto transakciou (SERIALIZABLE)
+            Some(SERIALIZATION_FAILURE) => BankError::SerializationFailure,
+            // Opakovane meno, cislo uctu, kluc idempotencie, ...
+            Some(UNIQUE_VIOLATION) => BankError::AlreadyExists(duplicate_field(db.constraint())),
+            _ => BankError::Database(e),
+        }
+    }
+}
+
+impl IntoResponse for BankError {
+    fn into_response(self) -> Response {
+        // Detaily databazovej chyby a chyby hashovania neposielame klientovi
+        let message = match &self {
+            BankError::Database(_) | BankError::PasswordHashing(_) => {
+                "Internal server error".to_string()
+            }
+            other => other.to_string(),
+        };
+
+        let mut error = json!({"code": self.code(), "message": message});
+        // Pri chybajucich poliach klient dostane aj ich zoznam
+        if let BankError::MissingFields(fields) = &self {
+            error["fields"] = json!(fields);
+        }
+        // ID poziadavky, podla ktoreho sa chyba najde v logoch
+        if let Some(request_id) = current_request_id() {
+            error["request_id"] = json!(request_id);
+        }
+        if let BankError::Database(_) | BankError::PasswordHashing(_) = &self {
+            tracing::error!("{}{self}", log_prefix());
+        }
+
+        let mut response = (self.status(), Json(json!({"error": error}))).into_response();
+        // Pri zaneprazdnenom ucte a prekrocenom limite klient vie, kedy to skusit znova
+        let retry_after = match self {
+            BankError::Busy | BankError::SerializationFailure | BankError::TooManyTransfers => {
+                Some(BUSY_RETRY_AFTER_SECS)
+            }
+            BankError::RateLimited { retry_after_secs } => Some(retry_after_secs),
+            _ => None,
+        };
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
@@ -0,0 +1,195 @@
+// export.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::models::{AccountStatus, AccountType, PubAccount, PublicUser, Transaction};
+use crate::request_id::log_prefix;
+use axum::body::Bytes;
+use chrono::Utc;
+use futures_util::TryStreamExt;
+use futures_util::stream::{self, Stream};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{PgPool, query};
+use std::io;
+use std::pin::Pin;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Prud casti JSON dokumentu s exportom udajov pouzivatela
+pub type ExportStream = Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>>;
+
+/// Kolko pripravenych casti moze cakat na odoslanie klientovi
+const EXPORT_BUFFER_CHUNKS: usize = 16;
+
+/// Exportuje udaje pouzivatela (GDPR - prenositelnost udajov)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia prud JSON dokumentu:
+/// `{"exported_at": ..., "user": {...}, "accounts": [...], "transactions": [...]}`.
+/// Transakcie zahrnaju aj archiv, poznamka je vzdy ta, ktoru vidi pouzivatel.
+///
+/// # Poznamka
+/// Riadky sa citaju z databazy postupne a posielaju klientovi hned, takze export
+/// uctu s velkou historiou nedrzi celu historiu v pamati. Ak sa klient odpoji,
+/// citanie sa zastavi. Chyba databazy pocas exportu ukonci prud chybou
+/// (klient dostane neuplny dokument). ID pouzivatela v logu chyby sa maskuje
+/// podla Config::log_id_masking (LOG_ID_MASKING).
+///
+/// # Chyby
+/// - BankError::UserNotFound: pouzivatel neexistuje
+pub async fn export_user_data(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+) -> Result<ExportStream, BankError> {
+    let user = query!("SELECT id, username FROM users WHERE id = $1", user_id)
+        .fetch_optional(pool)
+        .await?
+        .map(|row| PublicUser {
+            id: row.id,
+            username: row.username,
+        })
+        .ok_or(BankError::UserNotFound)?;
+
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_CHUNKS);
+
+    // Uloha na pozadi nededi ID poziadavky, predpona logu sa preto zachyti vopred
+    let log_prefix = log_prefix();
+    let log_id_masking = config.log_id_masking;
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        let mut out = ExportWriter { sender };
+        if let Err(e) = write_export(&pool, &user, &mut out).await {
+            tracing::error!(
+                "{log_prefix}Export of user {} failed: {e}",
+                log_id_masking.format(user.id)
+            );
+            let _ = out.sender.send(Err(io::Error::other(e))).await;
+        }
+    });
+
+    Ok(Box::pin(stream::unfold(receiver, |mut receiver| async {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    })))
+}
+
+/// Odosielanie casti exportu klientovi
+struct ExportWriter {
+    sender: mpsc::Sender<Result<Bytes, io::Error>>,
+}
+
+impl ExportWriter {
+    /// Odosle cast dokumentu, vrati false ak sa klient odpojil
+    async fn write(&mut self, chunk: String) -> bool {
+        self.sender.send(Ok(Bytes::from(chunk))).await.is_ok()
+    }
+
+    /// Odosle dalsi prvok pola (pred kazdym okrem prveho ciarku)
+    async fn item<T: Serialize>(&mut self, first: &mut bool, value: &T) -> bool {
+        let separator = if *first { "" } else { "," };
+        *first = false;
+        self.write(format!("{separator}{}", json!(value))).await
+    }
+}
+
+/// Zapise cely dokument exportu; pri odpojeni klienta skonci bez chyby
+async fn write_export(
+    pool: &PgPool,
+    user: &PublicUser,
+    out: &mut ExportWriter,
+) -> Result<(), sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let header = format!(
+        r#"{{"exported_at":{},"user":{},"accounts":["#,
+        json!(Utc::now()),
+        json!(user)
+    );
+    if !out.write(header).await {
+        return Ok(());
+    }
+
+    let mut account_ids = Vec::new();
+    let mut first = true;
+    let mut accounts = query!(
+        r#"SELECT id, account_number, user_id, balance, currency,
+                  account_type AS "account_type: AccountType", status AS "status: AccountStatus"
+           FROM accounts WHERE user_id = $1
+           ORDER BY created_at, id"#,
+        user.id
+    )
+    .fetch(&mut *conn);
+
+    while let Some(row) = accounts.try_next().await? {
+        account_ids.push(row.id);
+        let account = PubAccount {
+            id: row.id,
+            account_number: row.account_number,
+            user_id: row.user_id,
+            balance: row.balance,
+            currency: row.currency,
+            account_type: row.account_type,
+            status: row.status,
+            display_balance: None,
+        };
+        if !out.item(&mut first, &account).await {
+            return Ok(());
+        }
+    }
+    drop(accounts);
+
+    if !out.write(r#"],"transactions":["#.to_string()).await {
+        return Ok(());
+    }
+
+    let mut first = true;
+    let mut transactions = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account,
+                  amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at,
+                  CASE WHEN from_account = ANY($1) THEN sender_note ELSE receiver_note END AS note
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM archived_transactions
+           ) t
+           WHERE from_account = ANY($1) OR to_account = ANY($1)
+           ORDER BY created_at, id"#,
+        &account_ids
+    )
+    .fetch(&mut *conn);
+
+    while let Some(row) = transactions.try_next().await? {
+        let transaction = Transaction {
+            id: row.id,
+            reference: row.reference,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            currency: row.currency,
+            credited_amount: row.credited_amount,
+            credited_currency: row.credited_currency,
+            rate: row.rate,
+            created_at: row.created_at,
+            note: row.note,
+            parties: None,
+            splits: Vec::new(),
+        };
+        if !out.item(&mut first, &transaction).await {
+            return Ok(());
+        }
+    }
+
+    out.write("]}".to_string()).await;
+    Ok(())
+}
@@ -0,0 +1,273 @@
+// Testy handlerov nad Repository v pamati (bez databazy a DATABASE_URL)
+mod mock_repository;
+
+use super::app;
+use axum::body::{Body, Bytes};
+use axum::extract::{ConnectInfo, FromRequest};
+use axum::http::{Method, Request, StatusCode, header};
+use axum::response::Response;
+use bank_backend::{API_KEY_HEADER, AccountType, AppState, Config, Repository};
+use mock_repository::MockRepository;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Stav servera nad danym repozitarom s predvolenou konfiguraciou
+fn state(repo: Arc<MockRepository>) -> AppState {
+    let config = Config::from_lookup(|name| {
+        (name == "DATABASE_URL").then(|| "postgres://localhost/unused".to_string())
+    })
+    .unwrap();
+    AppState::from_repository(config, repo)
+}
+
+/// Poziadavka od klienta 127.0.0.1 s volitelnym API klucom a JSON telom
+fn request(method: Method, uri: &str, api_key: Option<&str>, body: Option<Value>) -> Request<Body> {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(key) = api_key {
+        request = request.header(API_KEY_HEADER, key);
+    }
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+    let mut request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40000))));
+    request
+}
+
+/// Posle poziadavku celym routerom servera (vratane middleware)
+async fn send(
+    state: AppState,
+    method: Method,
+    uri: &str,
+    api_key: Option<&str>,
+    body: Option<Value>,
+) -> Response {
+    app(state)
+        .oneshot(request(method, uri, api_key, body))
+        .await
+        .unwrap()
+}
+
+/// Telo odpovede ako JSON
+async fn json_body(response: Response) -> Value {
+    let body = Bytes::from_request(Request::new(response.into_body()), &())
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn missing_account_balance_is_404_with_error_body() {
+    let state = state(Arc::new(MockRepository::default()));
+    let uri = format!("/accounts/{}/balance", Uuid::new_v4());
+
+    let response = send(state, Method::GET, &uri, None, None).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], "ACCOUNT_NOT_FOUND");
+    assert!(body["error"]["request_id"].is_string());
+}
+
+#[tokio::test]
+async fn existing_account_balance_is_served_from_repository() {
+    let repo = Arc::new(MockRepository::default());
+    let user = repo.create_user("alice", "secret").await.unwrap();
+    let account = repo
+        .create_account(
+            user.id,
+            Some("EUR"),
+            AccountType::Checking,
+            Decimal::new(2500, 2),
+        )
+        .await
+        .unwrap();
+
+    let uri = format!("/accounts/{}/balance", account.id);
+    let response = send(state(repo), Method::GET, &uri, None, None).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["available_balance"], "25.00");
+}
+
+#[tokio::test]
+async fn user_lookup_requires_valid_api_key() {
+    let repo = Arc::new(MockRepository::default());
+    let user = repo.create_user("bob", "secret").await.unwrap();
+    repo.add_api_key("bob-key", user.id);
+    let state = state(repo);
+
+    for key in [None, Some("wrong-key")] {
+        let response = send(
+            state.clone(),
+            Method::GET,
+            "/users/by-username/BOB",
+            key,
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let response = send(
+        state,
+        Method::GET,
+        "/users/by-username/BOB",
+        Some("bob-key"),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["id"], user.id.to_string());
+}
+
+#[tokio::test]
+async fn health_without_database_is_unavailable() {
+    let state = state(Arc::new(MockRepository::default()));
+
+    for uri in ["/health", "/health/detailed"] {
+        let response = send(state.clone(), Method::GET, uri, None, None).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json_body(response).await["status"], "unavailable");
+    }
+}
+
+#[tokio::test]
+async fn deposit_into_missing_account_is_404() {
+    let state = state(Arc::new(MockRepository::default()));
+    let body = serde_json::json!({ "account_id": Uuid::new_v4(), "amount": "10" });
+
+    let response = send(state, Method::POST, "/addmoney", None, Some(body)).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        json_body(response).await["error"]["code"],
+        "ACCOUNT_NOT_FOUND"
+    );
+}
+
+#[tokio::test]
+async fn deposit_failure_is_500_not_200() {
+    let repo = Arc::new(MockRepository::default());
+    let user = repo.create_user("carol", "secret").await.unwrap();
+    let account = repo
+        .create_account(user.id, Some("EUR"), AccountType::Checking, Decimal::ZERO)
+        .await
+        .unwrap();
+    let state = state(repo.clone());
+    let body = serde_json::json!({ "account_id": account.id, "amount": "10" });
+
+    let response = send(
+        state.clone(),
+        Method::POST,
+        "/addmoney",
+        None,
+        Some(body.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["balance"], "10");
+
+    repo.fail_writes();
+    let response = send(state, Method::POST, "/addmoney", None, Some(body)).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(json_body(response).await["error"]["code"], "DATABASE_ERROR");
+}
+
+#[tokio::test]
+async fn transaction_detail_carries_cache_headers() {
+    let repo = Arc::new(MockRepository::default());
+    let user = repo.create_user("dave", "secret").await.unwrap();
+    repo.add_api_key("dave-key", user.id);
+    let account = repo
+        .create_account(user.id, Some("EUR"), AccountType::Checking, Decimal::ZERO)
+        .await
+        .unwrap();
+    let transfer = repo.add_transfer(account.id, Uuid::new_v4(), Decimal::new(10, 0));
+    let state = state(repo);
+    let uri = format!("/transactions/{transfer}");
+
+    let response = send(state.clone(), Method::GET, &uri, Some("dave-key"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "private, max-age=60"
+    );
+    let etag = response.headers()[header::ETAG].clone();
+
+    // Zhodny ETag vrati 304 s rovnakymi hlavickami cache
+    let mut conditional = request(Method::GET, &uri, Some("dave-key"), None);
+    conditional
+        .headers_mut()
+        .insert(header::IF_NONE_MATCH, etag.clone());
+    let response = app(state).oneshot(conditional).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "private, max-age=60"
+    );
+}
+
+#[tokio::test]
+async fn transfer_body_without_required_fields_is_422_with_field_list() {
+    let state = state(Arc::new(MockRepository::default()));
+
+    for uri in ["/transactions", "/transactions/initiate"] {
+        let response = send(
+            state.clone(),
+            Method::POST,
+            uri,
+            None,
+            Some(serde_json::json!({})),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let error = &json_body(response).await["error"];
+        assert_eq!(error["code"], "MISSING_FIELDS");
+        assert_eq!(
+            error["fields"],
+            serde_json::json!(["from_account", "to_account", "amount"])
+        );
+        assert_eq!(
+            error["message"],
+            "Missing required fields: from_account, to_account, amount"
+        );
+    }
+
+    // null sa povazuje za chybajuce pole
+    let partial = serde_json::json!({ "from_account": Uuid::new_v4(), "amount": null });
+    let response = send(
+        state.clone(),
+        Method::POST,
+        "/transactions",
+        None,
+        Some(partial),
+    )
+    .await;
+    assert_eq!(
+        json_body(response).await["error"]["fields"],
+        serde_json::json!(["to_account", "amount"])
+    );
+
+    // Vsetky polia su pritomne, ale suma nie je cislo
+    let invalid = serde_json::json!({
+        "from_account": Uuid::new_v4(),
+        "to_account": Uuid::new_v4(),
+        "amount": "ten",
+    });
+    let response = send(state, Method::POST, "/transactions", None, Some(invalid)).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let error = &json_body(response).await["error"];
+    assert_eq!(error["code"], "INVALID_BODY");
+    assert!(error.get("fields").is_none());
+}
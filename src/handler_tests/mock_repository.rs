@@ -0,0 +1,583 @@
+// Repository v pamati pre testy handlerov bez databazy
+use async_trait::async_trait;
+use bank_backend::*;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Data ulozene v MockRepository
+#[derive(Default)]
+struct MockData {
+    /// Pouzivatelia s heslom v plain texte (hashovanie sa v testoch handlerov neoveruje)
+    users: HashMap<Uuid, (PublicUser, String)>,
+    accounts: HashMap<Uuid, PubAccount>,
+    /// API kluc -> pouzivatel
+    api_keys: HashMap<String, Uuid>,
+    /// Prevody (odosielatel, prijemca, suma)
+    transfers: HashMap<Uuid, (Uuid, Uuid, Decimal)>,
+    /// Zapisy zlyhaju ako pri vypadku databazy
+    fail_writes: bool,
+}
+
+/// Repository bez databazy - pouzivatelia, ucty a API kluce su len v pamati
+///
+/// Pokryva operacie, ktore potrebuju testy handlerov; ostatne metody zlyhaju
+/// s `unimplemented!`, aby test omylom nezavisel od spravania, ktore mock nema.
+#[derive(Default)]
+pub struct MockRepository {
+    data: Mutex<MockData>,
+}
+
+impl MockRepository {
+    fn data(&self) -> MutexGuard<'_, MockData> {
+        self.data.lock().unwrap()
+    }
+
+    /// Prida API kluc pouzivatela (X-API-Key)
+    pub fn add_api_key(&self, key: &str, user_id: Uuid) {
+        self.data().api_keys.insert(key.to_string(), user_id);
+    }
+
+    /// Zaznamena prevod medzi uctami bez zmeny zostatkov
+    pub fn add_transfer(&self, from_account: Uuid, to_account: Uuid, amount: Decimal) -> Uuid {
+        let id = Uuid::new_v4();
+        self.data()
+            .transfers
+            .insert(id, (from_account, to_account, amount));
+        id
+    }
+
+    /// Dalsie zapisy zlyhaju s chybou databazy (vypadok spojenia)
+    pub fn fail_writes(&self) {
+        self.data().fail_writes = true;
+    }
+}
+
+#[async_trait]
+impl Repository for MockRepository {
+    async fn create_user(&self, username: &str, password: &str) -> Result<PublicUser, BankError> {
+        let mut data = self.data();
+        if data
+            .users
+            .values()
+            .any(|(user, _)| user.username == username)
+        {
+            return Err(BankError::AlreadyExists("username"));
+        }
+        let user = PublicUser {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+        };
+        data.users
+            .insert(user.id, (user.clone(), password.to_string()));
+        Ok(user)
+    }
+
+    async fn import_users(
+        &self,
+        _csv: &str,
+        _policy: DuplicatePolicy,
+    ) -> Result<ImportReport, BankError> {
+        unimplemented!("import_users")
+    }
+
+    async fn get_user(&self, user_id: Uuid) -> Result<PublicUser, sqlx::Error> {
+        self.data()
+            .users
+            .get(&user_id)
+            .map(|(user, _)| user.clone())
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn get_user_by_username(&self, username: &str) -> Result<PublicUser, BankError> {
+        self.data()
+            .users
+            .values()
+            .find(|(user, _)| user.username.eq_ignore_ascii_case(username.trim()))
+            .map(|(user, _)| user.clone())
+            .ok_or(BankError::UserNotFound)
+    }
+
+    async fn delete_user(&self, _user_id: Uuid, _cancel_scheduled: bool) -> Result<u64, BankError> {
+        unimplemented!("delete_user")
+    }
+
+    async fn login_user(&self, username: &str, password: &str) -> Result<PublicUser, String> {
+        let data = self.data();
+        let (user, stored) = data
+            .users
+            .values()
+            .find(|(user, _)| user.username == username)
+            .ok_or_else(|| "User not found".to_string())?;
+        if stored != password {
+            return Err("Invalid password".to_string());
+        }
+        Ok(user.clone())
+    }
+
+    async fn record_login_attempt(
+        &self,
+        _username: &str,
+        _success: bool,
+        _client: &ClientInfo,
+    ) -> Result<(), BankError> {
+        unimplemented!("record_login_attempt")
+    }
+
+    async fn list_login_attempts(
+        &self,
+        _user_id: Uuid,
+        _query: &LoginHistoryQuery,
+    ) -> Result<LoginHistoryPage, BankError> {
+        unimplemented!("list_login_attempts")
+    }
+
+    async fn export_user_data(&self, _user_id: Uuid) -> Result<ExportStream, BankError> {
+        unimplemented!("export_user_data")
+    }
+
+    async fn create_account(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        account_type: AccountType,
+        initial_deposit: Decimal,
+    ) -> Result<PubAccount, BankError> {
+        if initial_deposit < Decimal::ZERO {
+            return Err(BankError::InvalidAmount);
+        }
+        let currency = normalize_currency(currency.unwrap_or("EUR"))?;
+        let mut data = self.data();
+        if !data.users.contains_key(&user_id) {
+            return Err(BankError::UserNotFound);
+        }
+        let account = PubAccount {
+            id: Uuid::new_v4(),
+            account_number: None,
+            user_id,
+            balance: initial_deposit,
+            currency,
+            account_type,
+            status: AccountStatus::Active,
+            display_balance: None,
+        };
+        data.accounts.insert(account.id, account.clone());
+        Ok(account)
+    }
+
+    async fn decide_account_approval(
+        &self,
+        _account_id: Uuid,
+        _approve: bool,
+    ) -> Result<PubAccount, BankError> {
+        unimplemented!("decide_account_approval")
+    }
+
+    async fn get_account(
+        &self,
+        _user_id: Uuid,
+        _filter: &AccountFilter,
+    ) -> Result<AccountList, sqlx::Error> {
+        unimplemented!("get_account")
+    }
+
+    async fn get_accounts_batch(
+        &self,
+        _user_id: Uuid,
+        _ids: &[Uuid],
+    ) -> Result<AccountBatch, BankError> {
+        unimplemented!("get_accounts_batch")
+    }
+
+    async fn get_account_by_id(&self, account_id: Uuid) -> Result<PubAccount, BankError> {
+        self.data()
+            .accounts
+            .get(&account_id)
+            .cloned()
+            .ok_or(BankError::AccountNotFound)
+    }
+
+    async fn get_account_by_number(&self, _number: &str) -> Result<PubAccount, BankError> {
+        unimplemented!("get_account_by_number")
+    }
+
+    async fn close_account(
+        &self,
+        _account_id: Uuid,
+        _user_id: Uuid,
+        _sweep_to: Option<Uuid>,
+        _cancel_scheduled: bool,
+    ) -> Result<FinalStatement, BankError> {
+        unimplemented!("close_account")
+    }
+
+    async fn merge_accounts(
+        &self,
+        _user_id: Uuid,
+        _source: Uuid,
+        _target: Uuid,
+        _cancel_scheduled: bool,
+    ) -> Result<AccountMerge, BankError> {
+        unimplemented!("merge_accounts")
+    }
+
+    async fn set_low_balance_threshold(
+        &self,
+        _account_id: Uuid,
+        _user_id: Uuid,
+        _threshold: Option<Decimal>,
+    ) -> Result<LowBalanceThreshold, BankError> {
+        unimplemented!("set_low_balance_threshold")
+    }
+
+    async fn get_account_balance(&self, account_id: Uuid) -> Result<AccountBalance, BankError> {
+        let account = self.get_account_by_id(account_id).await?;
+        Ok(AccountBalance {
+            account_id,
+            balance: account.balance,
+            available_balance: account.balance,
+            holds: Vec::new(),
+        })
+    }
+
+    async fn get_balances_by_currency(
+        &self,
+        _user_id: Uuid,
+    ) -> Result<BTreeMap<String, Decimal>, BankError> {
+        unimplemented!("get_balances_by_currency")
+    }
+
+    async fn total_balance(&self, _user_id: Uuid) -> Result<Decimal, BankError> {
+        unimplemented!("total_balance")
+    }
+
+    async fn add_money(
+        &self,
+        account_id: Uuid,
+        money: Decimal,
+        _idempotency_key: Option<&str>,
+    ) -> Result<PubAccount, BankError> {
+        if money <= Decimal::ZERO {
+            return Err(BankError::InvalidAmount);
+        }
+        let mut data = self.data();
+        if data.fail_writes {
+            return Err(BankError::Database(sqlx::Error::PoolTimedOut));
+        }
+        let account = data
+            .accounts
+            .get_mut(&account_id)
+            .ok_or(BankError::AccountNotFound)?;
+        ensure_account_active(account.status)?;
+        account.balance += money;
+        Ok(account.clone())
+    }
+
+    async fn add_money_batch(
+        &self,
+        _credits: Vec<(Uuid, Decimal)>,
+    ) -> Result<DepositBatch, BankError> {
+        unimplemented!("add_money_batch")
+    }
+
+    async fn make_transaction(
+        &self,
+        _from_account: Uuid,
+        _to_account: Uuid,
+        _amount: Decimal,
+        _notes: TransferNotes<'_>,
+        _idempotency_key: Option<&str>,
+        _splits: &[TransactionSplit],
+    ) -> Result<Transaction, BankError> {
+        unimplemented!("make_transaction")
+    }
+
+    async fn set_account_pin(
+        &self,
+        _account_id: Uuid,
+        _user_id: Uuid,
+        _pin: Option<&str>,
+    ) -> Result<AccountPin, BankError> {
+        unimplemented!("set_account_pin")
+    }
+
+    async fn verify_transfer_pin(
+        &self,
+        _from_account: Uuid,
+        _amount: Decimal,
+        _pin: Option<&str>,
+    ) -> Result<(), BankError> {
+        unimplemented!("verify_transfer_pin")
+    }
+
+    async fn make_transaction_from_multiple(
+        &self,
+        _user_id: Uuid,
+        _sources: &[Uuid],
+        _to_account: Uuid,
+        _amount: Decimal,
+        _sender_note: Option<&str>,
+        _receiver_note: Option<&str>,
+    ) -> Result<MultiSourceTransfer, BankError> {
+        unimplemented!("make_transaction_from_multiple")
+    }
+
+    async fn make_batch_transfer(
+        &self,
+        _user_id: Uuid,
+        _from_account: Uuid,
+        _transfers: &[BatchTransferItem],
+    ) -> Result<TransferBatch, BankError> {
+        unimplemented!("make_batch_transfer")
+    }
+
+    async fn reverse_batch(&self, _batch_id: Uuid) -> Result<BatchReversal, BankError> {
+        unimplemented!("reverse_batch")
+    }
+
+    async fn preview_transfer(
+        &self,
+        _user_id: Uuid,
+        _from_account: Uuid,
+        _to_account: Uuid,
+        _amount: Decimal,
+    ) -> Result<TransferPreview, BankError> {
+        unimplemented!("preview_transfer")
+    }
+
+    async fn initiate_transfer(
+        &self,
+        _from_account: Uuid,
+        _to_account: Uuid,
+        _amount: Decimal,
+        _sender_note: Option<&str>,
+        _receiver_note: Option<&str>,
+    ) -> Result<TransferInitiation, BankError> {
+        unimplemented!("initiate_transfer")
+    }
+
+    async fn confirm_transfer(&self, _token: &str) -> Result<Transaction, BankError> {
+        unimplemented!("confirm_transfer")
+    }
+
+    async fn cancel_transfer(
+        &self,
+        _user_id: Uuid,
+        _transfer_id: Uuid,
+    ) -> Result<CancelledTransfer, BankError> {
+        unimplemented!("cancel_transfer")
+    }
+
+    async fn request_email_verification(
+        &self,
+        _user_id: Uuid,
+        _email: &str,
+    ) -> Result<EmailVerificationSent, BankError> {
+        unimplemented!("request_email_verification")
+    }
+
+    async fn verify_email(&self, _token: &str) -> Result<EmailStatus, BankError> {
+        unimplemented!("verify_email")
+    }
+
+    async fn get_transaction(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Transaction, BankError> {
+        let data = self.data();
+        let &(from_account, to_account, amount) = data
+            .transfers
+            .get(&transaction_id)
+            .ok_or(BankError::TransactionNotFound)?;
+        let owns = |account_id| {
+            data.accounts
+                .get(&account_id)
+                .is_some_and(|account| account.user_id == user_id)
+        };
+        if !owns(from_account) && !owns(to_account) {
+            return Err(BankError::Forbidden);
+        }
+        Ok(Transaction {
+            id: transaction_id,
+            reference: None,
+            from_account: Some(from_account),
+            to_account: Some(to_account),
+            amount,
+            currency: None,
+            credited_amount: None,
+            credited_currency: None,
+            rate: None,
+            created_at: None,
+            note: None,
+            parties: None,
+            splits: Vec::new(),
+        })
+    }
+
+    async fn add_transaction_tag(
+        &self,
+        _transaction_id: Uuid,
+        _user_id: Uuid,
+        _tag: &str,
+    ) -> Result<TransactionTags, BankError> {
+        unimplemented!("add_transaction_tag")
+    }
+
+    async fn remove_transaction_tag(
+        &self,
+        _transaction_id: Uuid,
+        _user_id: Uuid,
+        _tag: &str,
+    ) -> Result<TransactionTags, BankError> {
+        unimplemented!("remove_transaction_tag")
+    }
+
+    async fn filter_transactions_by_tag(
+        &self,
+        _account_id: Uuid,
+        _tag: &str,
+        _transactions: &mut Vec<Transaction>,
+    ) -> Result<(), BankError> {
+        unimplemented!("filter_transactions_by_tag")
+    }
+
+    async fn create_recurring_transfer(
+        &self,
+        _user_id: Uuid,
+        _request: &CreateRecurringTransferRequest,
+    ) -> Result<RecurringTransfer, BankError> {
+        unimplemented!("create_recurring_transfer")
+    }
+
+    async fn get_notification_prefs(&self, _user_id: Uuid) -> Result<NotificationPrefs, BankError> {
+        unimplemented!("get_notification_prefs")
+    }
+
+    async fn update_notification_prefs(
+        &self,
+        _user_id: Uuid,
+        _prefs: &NotificationPrefsUpdate,
+    ) -> Result<NotificationPrefs, BankError> {
+        unimplemented!("update_notification_prefs")
+    }
+
+    async fn list_notifications(&self, _user_id: Uuid) -> Result<Vec<Notification>, BankError> {
+        unimplemented!("list_notifications")
+    }
+
+    async fn list_recurring_transfers(
+        &self,
+        _user_id: Uuid,
+    ) -> Result<Vec<RecurringTransfer>, BankError> {
+        unimplemented!("list_recurring_transfers")
+    }
+
+    async fn get_transaction_history(
+        &self,
+        _account_id: Uuid,
+        _include_archived: bool,
+        _order: SortOrder,
+    ) -> Result<Vec<Transaction>, BankError> {
+        unimplemented!("get_transaction_history")
+    }
+
+    async fn expand_transaction_parties(
+        &self,
+        _transactions: &mut [Transaction],
+    ) -> Result<(), BankError> {
+        unimplemented!("expand_transaction_parties")
+    }
+
+    async fn account_stats(
+        &self,
+        _account_id: Uuid,
+        _from: Option<DateTime<Utc>>,
+        _to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStats, BankError> {
+        unimplemented!("account_stats")
+    }
+
+    async fn system_stats(
+        &self,
+        _from: Option<DateTime<Utc>>,
+        _to: Option<DateTime<Utc>>,
+    ) -> Result<SystemStats, BankError> {
+        unimplemented!("system_stats")
+    }
+
+    async fn list_counterparties(
+        &self,
+        _account_id: Uuid,
+        _user_id: Uuid,
+    ) -> Result<Vec<Counterparty>, BankError> {
+        unimplemented!("list_counterparties")
+    }
+
+    async fn account_statement(
+        &self,
+        _account_id: Uuid,
+        _from: Option<DateTime<Utc>>,
+        _to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStatement, BankError> {
+        unimplemented!("account_statement")
+    }
+
+    async fn check_balance_consistency(&self) -> Result<Vec<BalanceDiscrepancy>, BankError> {
+        unimplemented!("check_balance_consistency")
+    }
+
+    async fn create_api_key(&self, _user_id: Uuid) -> Result<CreatedApiKey, BankError> {
+        unimplemented!("create_api_key")
+    }
+
+    async fn revoke_api_key(&self, _user_id: Uuid, _key_id: Uuid) -> Result<(), BankError> {
+        unimplemented!("revoke_api_key")
+    }
+
+    async fn list_api_keys(&self, _user_id: Uuid) -> Result<Vec<ApiKeyInfo>, BankError> {
+        unimplemented!("list_api_keys")
+    }
+
+    async fn authenticate_api_key(&self, key: &str) -> Result<Uuid, BankError> {
+        self.data()
+            .api_keys
+            .get(key)
+            .copied()
+            .ok_or(BankError::Unauthorized)
+    }
+
+    async fn apply_interest(&self, _rate: Decimal) -> Result<InterestRun, BankError> {
+        unimplemented!("apply_interest")
+    }
+
+    async fn rounding_audit(&self) -> Result<Vec<RoundingAudit>, BankError> {
+        unimplemented!("rounding_audit")
+    }
+
+    async fn list_negative_balance_alerts(
+        &self,
+        _query: &NegativeBalanceAlertQuery,
+    ) -> Result<Vec<NegativeBalanceAlert>, BankError> {
+        unimplemented!("list_negative_balance_alerts")
+    }
+
+    async fn search_transactions(
+        &self,
+        _search: &TransactionSearch,
+    ) -> Result<TransactionPage, BankError> {
+        unimplemented!("search_transactions")
+    }
+
+    async fn reconcile_balances(
+        &self,
+        _entries: &[ReconcileEntry],
+        _force: bool,
+        _actor: Option<&str>,
+        _client: &ClientInfo,
+    ) -> Result<Reconciliation, BankError> {
+        unimplemented!("reconcile_balances")
+    }
+}
@@ -0,0 +1,77 @@
+// health.rs
+use crate::models::{DatabaseConnections, HealthReport, PoolStats};
+use sqlx::{PgPool, query};
+use std::time::{Duration, Instant};
+
+/// Najdlhsie cakanie na spojenie pri kontrole stavu - nedostupna databaza
+/// sa ma prejavit rychlo, nie az po REQUEST_TIMEOUT_SECS
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Overi, ze databaza odpoveda (`SELECT 1`)
+pub async fn ping_database(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let mut conn = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, pool.acquire())
+        .await
+        .map_err(|_| sqlx::Error::PoolTimedOut)??;
+    query!("SELECT 1 AS one").fetch_one(&mut *conn).await?;
+    Ok(())
+}
+
+/// Zostavi podrobny stav servera pre monitoring kapacity
+///
+/// # Parametre
+/// - pool: zdielany pool servera (AppState::pool)
+/// - max_connections: najvyssi pocet spojeni poolu (DB_MAX_CONNECTIONS)
+///
+/// # Navratova hodnota
+/// Vracia HealthReport s dobou odozvy databazy, stavom zdielaneho poolu
+/// a poctom spojeni do databazy podla Postgresu
+///
+/// # Poznamka
+/// Doba odozvy meria len `SELECT 1` na uz ziskanom spojeni, bez cakania na volne
+/// spojenie v pooli. Stav poolu sa cita pred ziskanim spojenia, aby meranie
+/// neratalo samo seba medzi pouzivane spojenia.
+///
+/// Cez zdielany pool idu vsetky operacie nad datami (PgRepository) aj ulohy na pozadi,
+/// `connections` z pg_stat_activity zahrna aj ine instancie s rovnakou databazou.
+pub async fn health_report(
+    pool: &PgPool,
+    max_connections: u32,
+) -> Result<HealthReport, sqlx::Error> {
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    let pool_stats = PoolStats {
+        size,
+        idle,
+        in_use: size.saturating_sub(idle),
+        max_connections,
+    };
+
+    let mut conn = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, pool.acquire())
+        .await
+        .map_err(|_| sqlx::Error::PoolTimedOut)??;
+
+    let started = Instant::now();
+    query!("SELECT 1 AS one").fetch_one(&mut *conn).await?;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let connections = query!(
+        r#"SELECT COUNT(*) AS "total!",
+                  COUNT(*) FILTER (WHERE state = 'active') AS "active!",
+                  COUNT(*) FILTER (WHERE state = 'idle') AS "idle!"
+           FROM pg_stat_activity
+           WHERE datname = current_database() AND backend_type = 'client backend'"#
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(HealthReport {
+        status: "ok",
+        database_latency_ms: latency_ms,
+        pool: pool_stats,
+        connections: DatabaseConnections {
+            total: connections.total,
+            active: connections.active,
+            idle: connections.idle,
+        },
+    })
+}
@@ -0,0 +1,42 @@
+// http_cache.rs
+use axum::http::{HeaderMap, header};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Slaby ETag odpovede podla jej obsahu, napr. `W/"3f2a...c91e"` (SHA-256 serializovaneho tela)
+///
+/// # Poznamka
+/// ETag je slaby, pretoze ten isty obsah moze ist klientovi v inej podobe
+/// (kompresia, JSON_PRETTY, format sum podla hlavicky) - vyznam odpovede sa nemeni.
+pub fn etag(body: &[u8]) -> String {
+    format!("W/\"{:x}\"", Sha256::digest(body))
+}
+
+/// Ci hlavicka If-None-Match zodpoveda ETag-u (slabe porovnanie podla RFC 9110)
+///
+/// # Parametre
+/// - headers: hlavicky poziadavky
+/// - etag: aktualny ETag zdroja (vysledok `etag`)
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let current = opaque(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == current)
+}
+
+/// Hodnota Cache-Control pre odpoved viazanu na pouzivatela
+///
+/// # Parametre
+/// - max_age: doba, pocas ktorej moze klient odpoved pouzit bez overenia
+///   (nula = `no-cache`, klient sa musi vzdy spytat cez If-None-Match)
+pub fn private_cache_control(max_age: Duration) -> String {
+    match max_age.as_secs() {
+        0 => "private, no-cache".to_string(),
+        secs => format!("private, max-age={secs}"),
+    }
+}
@@ -0,0 +1,194 @@
+// idempotency.rs
+use crate::config::Config;
+use sqlx::{PgConnection, PgPool, query};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Nastavenia platnosti klucov idempotencie
+#[derive(Debug, Clone)]
+pub struct IdempotencyConfig {
+    /// Ako dlho kluc chrani pred opakovanim (po uplynuti sa poziadavka spracuje ako nova)
+    pub ttl: Duration,
+    /// Interval medzi spusteniami cistenia
+    pub interval: Duration,
+}
+
+impl IdempotencyConfig {
+    /// Prevezme nastavenia z konfiguracie
+    ///
+    /// # Konfiguracia
+    /// - Config::idempotency_key_ttl: platnost kluca (0 = klucy platia navzdy)
+    /// - Config::idempotency_cleanup_interval: interval cistenia
+    ///
+    /// # Navratova hodnota
+    /// Vracia None ak platnost klucov nie je nastavena
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.idempotency_key_ttl.is_zero() {
+            return None;
+        }
+
+        Some(Self {
+            ttl: config.idempotency_key_ttl,
+            interval: config.idempotency_cleanup_interval,
+        })
+    }
+}
+
+/// Komu kluc idempotencie patri - vklady a prevody maju oddelene kluce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyScope {
+    /// Vklad - kluc je jedinecny pre ucet prijemcu
+    Deposit { to_account: Uuid },
+    /// Prevod - kluc je jedinecny pre ucet odosielatela
+    Transfer { from_account: Uuid },
+}
+
+/// Uvolni kluce idempotencie starsie ako `ttl`
+///
+/// # Navratova hodnota
+/// Vracia pocet uvolnenych klucov
+///
+/// # Poznamka
+/// Transakcie zostavaju, len sa im vymaze idempotency_key - tym prestane platit
+/// aj unikatny index kluca (vklady aj prevody) a rovnaky kluc mozno pouzit znova.
+/// Rovnako sa uvolnia kluce archivovanych transakcii.
+pub async fn purge_expired_idempotency_keys(
+    pool: &PgPool,
+    ttl: Duration,
+) -> Result<u64, sqlx::Error> {
+    let ttl_secs = ttl.as_secs_f64();
+
+    let purged = query!(
+        "UPDATE transactions SET idempotency_key = NULL
+         WHERE idempotency_key IS NOT NULL
+           AND created_at < NOW() - make_interval(secs => $1)",
+        ttl_secs
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let archived = query!(
+        "UPDATE archived_transactions SET idempotency_key = NULL
+         WHERE idempotency_key IS NOT NULL
+           AND created_at < NOW() - make_interval(secs => $1)",
+        ttl_secs
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(purged + archived)
+}
+
+/// Uvolni jeden kluc, ak uz vyprsal - volane pred kontrolou opakovania
+///
+/// # Poznamka
+/// Vdaka tomu plati TTL presne, aj ked cistenie na pozadi este nebezalo.
+/// Uvolni kluc aktivnej aj archivovanej transakcie. Bez nastavenej platnosti
+/// (IDEMPOTENCY_KEY_TTL_SECS) nerobi nic.
+pub async fn release_expired_key(
+    conn: &mut PgConnection,
+    config: &Config,
+    scope: IdempotencyScope,
+    key: &str,
+) -> Result<(), sqlx::Error> {
+    let Some(config) = IdempotencyConfig::from_config(config) else {
+        return Ok(());
+    };
+    let ttl_secs = config.ttl.as_secs_f64();
+
+    match scope {
+        IdempotencyScope::Deposit { to_account } => {
+            query!(
+                "UPDATE transactions SET idempotency_key = NULL
+             WHERE to_account = $1 AND idempotency_key = $2 AND reference IS NULL
+               AND created_at < NOW() - make_interval(secs => $3)",
+                to_account,
+                key,
+                ttl_secs
+            )
+            .execute(&mut *conn)
+            .await?;
+            query!(
+                "UPDATE archived_transactions SET idempotency_key = NULL
+             WHERE to_account = $1 AND idempotency_key = $2 AND reference IS NULL
+               AND created_at < NOW() - make_interval(secs => $3)",
+                to_account,
+                key,
+                ttl_secs
+            )
+            .execute(conn)
+            .await?
+        }
+        IdempotencyScope::Transfer { from_account } => {
+            query!(
+                "UPDATE transactions SET idempotency_key = NULL
+             WHERE from_account = $1 AND idempotency_key = $2 AND reference IS NOT NULL
+               AND created_at < NOW() - make_interval(secs => $3)",
+                from_account,
+                key,
+                ttl_secs
+            )
+            .execute(&mut *conn)
+            .await?;
+            query!(
+                "UPDATE archived_transactions SET idempotency_key = NULL
+             WHERE from_account = $1 AND idempotency_key = $2 AND reference IS NOT NULL
+               AND created_at < NOW() - make_interval(secs => $3)",
+                from_account,
+                key,
+                ttl_secs
+            )
+            .execute(conn)
+            .await?
+        }
+    };
+
+    Ok(())
+}
+
+/// Ci uz existuje archivovany vklad s danym klucom
+///
+/// # Poznamka
+/// Unikatny index kluca plati len pre aktivne transakcie, archiv sa preto
+/// kontroluje zvlast pred zapisom vkladu.
+pub async fn archived_deposit_exists(
+    conn: &mut PgConnection,
+    to_account: Uuid,
+    key: &str,
+) -> Result<bool, sqlx::Error> {
+    let row = query!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM archived_transactions
+               WHERE to_account = $1 AND idempotency_key = $2 AND reference IS NULL
+           ) AS "exists!""#,
+        to_account,
+        key
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.exists)
+}
+
+/// Spusti cistenie vyprsanych klucov idempotencie na pozadi (ak je nastavena platnost)
+pub fn spawn_idempotency_cleanup_job(config: &Config, pool: PgPool) {
+    let Some(config) = IdempotencyConfig::from_config(config) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+
+        loop {
+            interval.tick().await;
+
+            match purge_expired_idempotency_keys(&pool, config.ttl).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Released {count} expired idempotency keys"),
+                Err(e) => tracing::error!("Idempotency key cleanup failed: {e}"),
+            }
+        }
+    });
+}
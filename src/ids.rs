@@ -0,0 +1,69 @@
+// ids.rs
+use crate::config::Config;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Strategia generovania identifikatorov transakcii
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdStrategy {
+    /// Nahodne UUID (v4)
+    #[default]
+    Random,
+    /// Casovo zoradene UUID (v7) - lepsia lokalita vkladania do B-tree indexu
+    TimeOrdered,
+}
+
+impl FromStr for IdStrategy {
+    type Err = String;
+
+    /// Strategia z hodnoty TRANSACTION_ID_STRATEGY (v4 | v7)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "v4" => Ok(IdStrategy::Random),
+            "v7" => Ok(IdStrategy::TimeOrdered),
+            _ => Err(format!("expected v4 or v7, got '{value}'")),
+        }
+    }
+}
+
+impl IdStrategy {
+    /// Vygeneruje nove UUID podla strategie
+    pub fn generate(&self) -> Uuid {
+        match self {
+            IdStrategy::Random => Uuid::new_v4(),
+            IdStrategy::TimeOrdered => Uuid::now_v7(),
+        }
+    }
+}
+
+/// Vygeneruje identifikator novej transakcie podla strategie z konfiguracie
+pub fn new_transaction_id(config: &Config) -> Uuid {
+    config.transaction_id_strategy.generate()
+}
+
+/// Predpona referencneho cisla prevodu
+pub const REFERENCE_PREFIX: &str = "TRX";
+
+/// Zostavi referencne cislo prevodu v tvare `TRX-<rok>-<poradie>`
+///
+/// # Parametre
+/// - year: rok vytvorenia prevodu
+/// - seq: poradove cislo zo sekvencie transfer_reference_seq
+///
+/// # Poznamka
+/// Poradie je doplnene nulami na 6 cifier (TRX-2024-000001). Unikatnost
+/// zarucuje databazova sekvencia, nie rok - sekvencia sa kazdy rok nenuluje.
+pub fn format_reference(year: i32, seq: i64) -> String {
+    format!("{REFERENCE_PREFIX}-{year}-{seq:06}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_is_zero_padded_to_six_digits() {
+        assert_eq!(format_reference(2026, 1), "TRX-2026-000001");
+        assert_eq!(format_reference(2026, 1_234_567), "TRX-2026-1234567");
+    }
+}
@@ -0,0 +1,162 @@
+// interest.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::ids::new_transaction_id;
+use crate::models::{InterestRun, InterestSummary, RoundingAudit};
+use crate::rounding::{record_remainder, split_remainder};
+use rust_decimal::Decimal;
+use sqlx::{PgPool, query};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Maximalny pocet desatinnych miest sadzby
+///
+/// Zostatok ma 2 desatinne miesta, takze presny urok ma najviac 10 -
+/// presne tolko, kolko uchovaju stlpce total_interest a rounding_account.balance.
+pub const MAX_RATE_SCALE: u32 = 8;
+
+/// Pripise urok na vsetky aktivne ucty s kladnym zostatkom
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - config: konfiguracia (sposob zaokruhlenia, ROUNDING_MODE)
+/// - rate: sadzba za obdobie (napr. 0.0125 = 1,25 %)
+///
+/// # Navratova hodnota
+/// Vracia InterestRun so suhrnom podla meny
+///
+/// # Poznamka
+/// Urok kazdeho uctu sa zaokruhli na centy smerom k nule a zvysok sa pripocita
+/// na zaokruhlovaci ucet danej meny, takze pre kazdu menu plati
+/// `total_interest = total_credited + rounding_remainder`.
+/// Pripisanie sa zapise do historie ako vklad (from_account = NULL).
+/// Cely beh prebieha v jednej databazovej transakcii.
+///
+/// # Chyby
+/// - BankError::InvalidAmount: sadzba nie je kladna alebo ma viac ako 8 desatinnych miest
+pub async fn apply_interest(
+    pool: &PgPool,
+    config: &Config,
+    rate: Decimal,
+) -> Result<InterestRun, BankError> {
+    if rate <= Decimal::ZERO || rate.scale() > MAX_RATE_SCALE {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let accounts = query!(
+        "SELECT id, balance, currency FROM accounts
+         WHERE status = 'active' AND balance > 0
+         ORDER BY id
+         FOR UPDATE"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut summaries: BTreeMap<String, InterestSummary> = BTreeMap::new();
+
+    for account in accounts {
+        let exact = account.balance * rate;
+        let (credited, remainder) = split_remainder(config.rounding_mode, exact);
+
+        let summary = summaries
+            .entry(account.currency.clone())
+            .or_insert_with(|| InterestSummary {
+                currency: account.currency.clone(),
+                accounts: 0,
+                total_interest: Decimal::ZERO,
+                total_credited: Decimal::ZERO,
+                rounding_remainder: Decimal::ZERO,
+            });
+        summary.total_interest += exact;
+        summary.total_credited += credited;
+        summary.rounding_remainder += remainder;
+
+        // Urok mensi ako cent sa cely presunie na zaokruhlovaci ucet
+        if credited.is_zero() {
+            continue;
+        }
+        summary.accounts += 1;
+
+        query!(
+            "UPDATE accounts SET balance = balance + $1 WHERE id = $2",
+            credited,
+            account.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        query!(
+            "INSERT INTO transactions (id, from_account, to_account, amount, currency)
+             VALUES ($1, NULL, $2, $3, $4)",
+            new_transaction_id(config),
+            account.id,
+            credited,
+            account.currency
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let run_id = Uuid::new_v4();
+
+    for summary in summaries.values() {
+        record_remainder(&mut tx, &summary.currency, summary.rounding_remainder).await?;
+
+        query!(
+            "INSERT INTO interest_runs
+                 (id, run_id, currency, rate, accounts, total_interest, total_credited, rounding_remainder)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            Uuid::new_v4(),
+            run_id,
+            summary.currency,
+            rate,
+            summary.accounts,
+            summary.total_interest,
+            summary.total_credited,
+            summary.rounding_remainder
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(InterestRun {
+        run_id,
+        rate,
+        currencies: summaries.into_values().collect(),
+    })
+}
+
+/// Overi, ze zaokruhlovaci ucet pokryva rozdiel medzi vypocitanym a pripisanym urokom
+///
+/// # Navratova hodnota
+/// Vracia RoundingAudit pre kazdu menu, v ktorej prebehlo pripisanie uroku
+pub async fn rounding_audit(pool: &PgPool) -> Result<Vec<RoundingAudit>, BankError> {
+    let rows = query!(
+        r#"SELECT r.currency AS "currency!",
+                  SUM(r.total_interest) AS "total_interest!",
+                  SUM(r.total_credited) AS "total_credited!",
+                  COALESCE(MAX(a.balance), 0) AS "rounding_balance!"
+           FROM interest_runs r
+           LEFT JOIN rounding_account a ON a.currency = r.currency
+           GROUP BY r.currency
+           ORDER BY r.currency"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let audits = rows
+        .into_iter()
+        .map(|row| RoundingAudit {
+            balanced: row.total_interest == row.total_credited + row.rounding_balance,
+            currency: row.currency,
+            total_interest: row.total_interest,
+            total_credited: row.total_credited,
+            rounding_balance: row.rounding_balance,
+        })
+        .collect();
+    Ok(audits)
+}
@@ -0,0 +1,143 @@
+// isolation.rs
+use crate::error::BankError;
+use sqlx::{PgConnection, query};
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Predvoleny pocet opakovani prevodu po konflikte serializacie
+pub const DEFAULT_SERIALIZATION_RETRIES: u32 = 3;
+
+/// Zakladna pauza pred opakovanim, s kazdym dalsim pokusom sa nasobi
+const SERIALIZATION_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Uroven izolacie databazovej transakcie prevodu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferIsolation {
+    /// Predvolena uroven Postgresu - prevody chrania zamky riadkov (FOR UPDATE)
+    #[default]
+    ReadCommitted,
+    /// Prevod sa sprava, akoby subezne prevody bezali jeden po druhom;
+    /// pri konflikte Postgres transakciu zrusi (SQLSTATE 40001)
+    Serializable,
+}
+
+impl TransferIsolation {
+    /// Prikaz, ktory nastavi tuto uroven pre aktualnu transakciu
+    fn statement(self) -> &'static str {
+        match self {
+            TransferIsolation::ReadCommitted => "SET TRANSACTION ISOLATION LEVEL READ COMMITTED",
+            TransferIsolation::Serializable => "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+        }
+    }
+
+    /// Nastavi uroven izolacie pre prave zacatu transakciu
+    ///
+    /// # Poznamka
+    /// Musi byt prvy prikaz transakcie, po inom dotaze ho Postgres odmietne.
+    pub async fn apply(self, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+        query(self.statement()).execute(conn).await?;
+        Ok(())
+    }
+}
+
+impl FromStr for TransferIsolation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value
+            .trim()
+            .to_lowercase()
+            .replace(['_', ' '], "-")
+            .as_str()
+        {
+            "read-committed" => Ok(TransferIsolation::ReadCommitted),
+            "serializable" => Ok(TransferIsolation::Serializable),
+            _ => Err(format!(
+                "expected read-committed or serializable, got '{value}'"
+            )),
+        }
+    }
+}
+
+/// Spusti operaciu a pri konflikte serializacie ju zopakuje
+///
+/// # Parametre
+/// - retries: najvyssi pocet opakovani (Config::transfer_serialization_retries, 0 = neopakovat)
+/// - attempt: vytvori novy pokus - kazdy pokus musi zacat vlastnu databazovu transakciu
+///
+/// # Navratova hodnota
+/// Vysledok prveho pokusu, ktory neskoncil BankError::SerializationFailure,
+/// alebo tuto chybu po vycerpani `retries` opakovani
+///
+/// # Poznamka
+/// Zruseny pokus nezanechal v databaze ziadne zmeny, preto ho mozno bezpecne zopakovat.
+pub async fn retry_on_serialization_failure<T, F, Fut>(
+    retries: u32,
+    mut attempt: F,
+) -> Result<T, BankError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, BankError>>,
+{
+    let mut retry = 0;
+
+    loop {
+        match attempt().await {
+            Err(BankError::SerializationFailure) if retry < retries => {
+                retry += 1;
+                tokio::time::sleep(SERIALIZATION_RETRY_BACKOFF * retry).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Pokus, ktory prvych `failures` volani skonci konfliktom serializacie
+    async fn flaky(calls: &Cell<u32>, failures: u32) -> Result<u32, BankError> {
+        calls.set(calls.get() + 1);
+        if calls.get() <= failures {
+            Err(BankError::SerializationFailure)
+        } else {
+            Ok(calls.get())
+        }
+    }
+
+    #[tokio::test]
+    async fn conflicts_are_retried_until_success() {
+        let calls = Cell::new(0);
+        let result = retry_on_serialization_failure(3, || flaky(&calls, 2)).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn conflict_is_returned_after_retries_run_out() {
+        let calls = Cell::new(0);
+        let result = retry_on_serialization_failure(2, || flaky(&calls, 5)).await;
+        assert!(matches!(result, Err(BankError::SerializationFailure)));
+        assert_eq!(calls.get(), 3);
+
+        let calls = Cell::new(0);
+        let result = retry_on_serialization_failure(0, || flaky(&calls, 1)).await;
+        assert!(matches!(result, Err(BankError::SerializationFailure)));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry_on_serialization_failure(3, || {
+            calls.set(calls.get() + 1);
+            async { Err(BankError::InsufficientFunds) }
+        })
+        .await;
+        assert!(matches!(result, Err(BankError::InsufficientFunds)));
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -1,7 +1,79 @@
+pub mod account_number;
+pub mod account_rules;
+pub mod amount_bounds;
+pub mod amount_format;
+pub mod archive;
+pub mod auth;
+pub mod balance_alerts;
+pub mod client_ip;
+pub mod config;
 pub mod crud;
+pub mod currency;
 pub mod db;
+pub mod error;
+pub mod export;
+pub mod health;
+pub mod http_cache;
+pub mod idempotency;
+pub mod ids;
+pub mod interest;
+pub mod isolation;
+pub mod locale;
+pub mod log_ids;
+pub mod middleware;
 pub mod models;
+pub mod notifications;
+pub mod ofx;
+pub mod password;
+pub mod rate_limit;
+pub mod rates;
+pub mod reconcile;
+pub mod recurring;
+pub mod repository;
+pub mod request_id;
+pub mod rounding;
+pub mod sanitize;
+pub mod state;
+pub mod statement;
+pub mod transfer_limit;
+pub mod validation;
 
+pub use account_number::*;
+pub use account_rules::*;
+pub use amount_bounds::*;
+pub use amount_format::*;
+pub use archive::*;
+pub use auth::*;
+pub use balance_alerts::*;
+pub use client_ip::*;
+pub use config::*;
 pub use crud::*;
+pub use currency::*;
 pub use db::*;
+pub use error::*;
+pub use export::*;
+pub use health::*;
+pub use http_cache::*;
+pub use idempotency::*;
+pub use ids::*;
+pub use interest::*;
+pub use isolation::*;
+pub use locale::*;
+pub use log_ids::*;
+pub use middleware::*;
 pub use models::*;
+pub use notifications::*;
+pub use ofx::*;
+pub use password::*;
+pub use rate_limit::*;
+pub use rates::*;
+pub use reconcile::*;
+pub use recurring::*;
+pub use repository::*;
+pub use request_id::*;
+pub use rounding::*;
+pub use sanitize::*;
+pub use state::*;
+pub use statement::*;
+pub use transfer_limit::*;
+pub use validation::*;
@@ -0,0 +1,120 @@
+// locale.rs
+use crate::error::BankError;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Hlavicka s ocakavanym formatom sum v tele poziadavky
+pub const AMOUNT_LOCALE_HEADER: &str = "x-amount-locale";
+
+/// Format zapisu sumy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountLocale {
+    /// Desatinna bodka, tisicky oddelene ciarkou (1,234.56)
+    DotDecimal,
+    /// Desatinna ciarka, tisicky oddelene bodkou alebo medzerou (1.234,56 / 1 234,56)
+    CommaDecimal,
+}
+
+impl AmountLocale {
+    /// Urci format podla hodnoty hlavicky X-Amount-Locale
+    ///
+    /// Akceptuje jazykovy kod alebo locale (napr. "en", "en-US", "sk", "de_DE").
+    /// Neznamy jazyk vrati None.
+    pub fn from_header(value: &str) -> Option<Self> {
+        let language = value
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+
+        match language.as_str() {
+            "en" | "ja" | "zh" | "ko" | "he" => Some(AmountLocale::DotDecimal),
+            "sk" | "cs" | "de" | "fr" | "es" | "it" | "nl" | "pl" | "pt" | "hu" | "ru" | "uk" => {
+                Some(AmountLocale::CommaDecimal)
+            }
+            _ => None,
+        }
+    }
+
+    fn separators(&self) -> (char, &'static [char]) {
+        match self {
+            AmountLocale::DotDecimal => ('.', &[',']),
+            AmountLocale::CommaDecimal => (',', &['.', ' ', '\u{a0}']),
+        }
+    }
+
+    /// Prevedie lokalizovany zapis sumy na Decimal
+    ///
+    /// # Poznamka
+    /// Oddelovace tisicok musia oddelovat skupiny po 3 cifrach, inak je zapis
+    /// povazovany za nejednoznacny (napr. "1.23" v CommaDecimal).
+    ///
+    /// # Chyby
+    /// - BankError::InvalidAmount: zapis nezodpoveda formatu
+    pub fn parse(&self, value: &str) -> Result<Decimal, BankError> {
+        let (decimal_sep, group_seps) = self.separators();
+        let value = value.trim();
+
+        let (sign, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", value),
+        };
+
+        let (integer, fraction) = match unsigned.split_once(decimal_sep) {
+            Some((i, f)) => (i, Some(f)),
+            None => (unsigned, None),
+        };
+
+        let groups: Vec<&str> = integer.split(group_seps).collect();
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+        let valid_groups = match groups.as_slice() {
+            [single] => is_digits(single),
+            [first, rest @ ..] => {
+                is_digits(first)
+                    && first.len() <= 3
+                    && rest.iter().all(|g| is_digits(g) && g.len() == 3)
+            }
+            [] => false,
+        };
+        if !valid_groups || fraction.is_some_and(|f| !is_digits(f)) {
+            return Err(BankError::InvalidAmount);
+        }
+
+        let normalized = match fraction {
+            Some(f) => format!("{sign}{}.{f}", groups.concat()),
+            None => format!("{sign}{}", groups.concat()),
+        };
+        Decimal::from_str(&normalized).map_err(|_| BankError::InvalidAmount)
+    }
+}
+
+/// Prepise vsetky textove polia `amount` v JSON hodnote na kanonicky tvar (bodka)
+///
+/// # Chyby
+/// - BankError::InvalidAmount: niektora suma nezodpoveda formatu
+pub fn normalize_amounts(
+    value: &mut serde_json::Value,
+    locale: AmountLocale,
+) -> Result<(), BankError> {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                match field {
+                    serde_json::Value::String(text) if key == "amount" => {
+                        *text = locale.parse(text)?.to_string();
+                    }
+                    _ => normalize_amounts(field, locale)?,
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                normalize_amounts(item, locale)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
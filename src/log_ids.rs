@@ -0,0 +1,99 @@
+// log_ids.rs
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Pocet znakov ID ponechanych pri skrateni (prva skupina UUID)
+const TRUNCATED_ID_LENGTH: usize = 8;
+
+/// Pocet hexadecimalnych znakov hashu ID v logu
+const HASHED_ID_LENGTH: usize = 16;
+
+/// Ako sa ID uctov a pouzivatelov zapisuju do logu
+///
+/// # Poznamka
+/// Tyka sa len vystupu logu - odpovede API aj databaza obsahuju ID vzdy cele.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogIdMasking {
+    /// Cele UUID
+    #[default]
+    Off,
+    /// Len prva skupina UUID, napr. `49da5bbd...`
+    Truncate,
+    /// Zaciatok SHA-256 hashu UUID, napr. `sha256:3f2a9c...` - rovnake ID ma vzdy
+    /// rovnaky hash, takze riadky logu o tom istom ucte sa daju sparovat
+    Hash,
+}
+
+impl LogIdMasking {
+    /// Podoba ID v logu podla tohto sposobu maskovania
+    pub fn format(self, id: Uuid) -> String {
+        match self {
+            LogIdMasking::Off => id.to_string(),
+            LogIdMasking::Truncate => {
+                let mut buffer = Uuid::encode_buffer();
+                let simple = id.simple().encode_lower(&mut buffer);
+                format!("{}...", &simple[..TRUNCATED_ID_LENGTH])
+            }
+            LogIdMasking::Hash => {
+                let digest = format!("{:x}", Sha256::digest(id.as_bytes()));
+                format!("sha256:{}", &digest[..HASHED_ID_LENGTH])
+            }
+        }
+    }
+}
+
+impl FromStr for LogIdMasking {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "off" | "none" => Ok(LogIdMasking::Off),
+            "truncate" => Ok(LogIdMasking::Truncate),
+            "hash" => Ok(LogIdMasking::Hash),
+            _ => Err(format!("expected off, truncate or hash, got '{value}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "49da5bbd-3c1e-4f7a-9b2d-8e6f0a1c2d3e";
+
+    fn id() -> Uuid {
+        ID.parse().unwrap()
+    }
+
+    #[test]
+    fn off_logs_the_whole_id() {
+        assert_eq!(LogIdMasking::Off.format(id()), ID);
+    }
+
+    #[test]
+    fn truncate_keeps_only_the_first_group() {
+        let masked = LogIdMasking::Truncate.format(id());
+        assert_eq!(masked, "49da5bbd...");
+        assert!(!masked.contains("3c1e"));
+    }
+
+    #[test]
+    fn hash_is_stable_and_hides_the_id() {
+        let masked = LogIdMasking::Hash.format(id());
+        assert!(masked.starts_with("sha256:"));
+        assert_eq!(masked.len(), "sha256:".len() + HASHED_ID_LENGTH);
+        assert!(!masked.contains("49da5bbd"));
+        // Rovnake ID dava rovnaky hash, ine ID iny
+        assert_eq!(masked, LogIdMasking::Hash.format(id()));
+        assert_ne!(masked, LogIdMasking::Hash.format(Uuid::nil()));
+    }
+
+    #[test]
+    fn parses_config_values() {
+        assert_eq!(" Hash ".parse(), Ok(LogIdMasking::Hash));
+        assert_eq!("truncate".parse(), Ok(LogIdMasking::Truncate));
+        assert_eq!("none".parse(), Ok(LogIdMasking::Off));
+        assert!("mask".parse::<LogIdMasking>().is_err());
+    }
+}
@@ -1,46 +1,245 @@
 use axum::{
     Router,
-    extract::{Json, Path},
-    http::StatusCode,
-    routing::{delete, get, post},
+    body::StreamBody,
+    extract::{Json, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
 };
 use bank_backend::*;
+use chrono::Utc;
+use rust_decimal::Decimal;
 use serde_json::json;
+use std::net::SocketAddr;
 use uuid::Uuid;
 
 /// Hlavna funkcia - spustenie HTTP servera
-/// Server bezi na adrese 127.0.0.1:3000 a poskytuje REST API pre bankovy system
+/// Server bezi na adrese BIND_ADDR (predvolene 127.0.0.1:3000) a poskytuje REST API pre bankovy system
 #[tokio::main]
 async fn main() {
-    // Konfigurovanie routing pre REST API endpointy
-    let app = Router::new()
+    // Logovanie na standardny vystup (uroven INFO a vyssie)
+    tracing_subscriber::fmt().with_target(false).init();
+
+    // Nacitanie a overenie konfiguracie - pri chybe sa vypisu vsetky problemy naraz
+    let config = Config::from_env().unwrap_or_else(|e| {
+        tracing::error!("{e}");
+        std::process::exit(1);
+    });
+
+    // Slabe parametre Argon2 server nezastavia (okrem ARGON2_STRICT), ale musia byt vidiet
+    for weakness in config.password.argon2_weaknesses() {
+        tracing::warn!("weak password hashing - {weakness}");
+    }
+
+    // Aplikovanie databazovych migracii pred spustenim servera
+    // (databaza moze startovat neskor nez aplikacia, pripojenie sa opakuje)
+    let pool = create_pool_with_retry(&config).await;
+    run_migrations(&pool)
+        .await
+        .expect("Failed to run database migrations");
+
+    let bind_addr = config.bind_addr;
+    let state = AppState::new(config, pool.clone());
+
+    // Archivacia starych transakcii na pozadi (ak je nakonfigurovana)
+    spawn_archival_job(&state.config, pool.clone());
+
+    // Vykonavanie splatnych trvalych prikazov na pozadi
+    spawn_recurring_job(state.config.clone(), state.rates.clone(), pool.clone());
+
+    // Denne kontrolne body zostatkov pre vypisy (ak su zapnute)
+    spawn_snapshot_job(&state.config, pool.clone());
+
+    // Uvolnovanie vyprsanych klucov idempotencie (ak je nastavena ich platnost)
+    spawn_idempotency_cleanup_job(&state.config, pool);
+
+    let app = app(state);
+
+    // Spustenie HTTP servera (BIND_ADDR, predvolene 127.0.0.1:3000)
+    axum::Server::bind(&bind_addr)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
+
+/// Konfigurovanie routing pre REST API endpointy s middleware
+///
+/// # Parametre
+/// - state: zdielany stav (AppState::new, v testoch AppState::from_repository)
+fn app(state: AppState) -> Router {
+    Router::new()
         // Registracia noveho pouzivatela
         .route("/register", post(create_user_handler))
         // Prihlasenie existujuceho pouzivatela
         .route("/login", post(login_user_handler))
+        // Kontrola stavu servera a databazy (bez prihlasenia)
+        .route("/health", get(health_handler))
+        // Stav poolu spojeni a doba odozvy databazy pre monitoring (bez prihlasenia)
+        .route("/health/detailed", get(detailed_health_handler))
+        // Povolene hodnoty vyctovych poli (typy uctov, meny, druhy transakcii, stavy)
+        .route("/meta/enums", get(meta_enums_handler))
+        // Poziadavky na heslo pri registracii (dlzka, triedy znakov)
+        .route("/meta/password-policy", get(password_policy_handler))
         // Ziskanie informacii o pouzivatelovi podla ID
         .route("/users/:id", get(get_user_handler))
+        // Vyhladanie pouzivatela podla mena, napr. pred prevodom (X-API-Key)
+        .route(
+            "/users/by-username/:username",
+            get(get_user_by_username_handler),
+        )
         // Zmazanie pouzivatela podla ID
         .route("/users/:id", delete(delete_user_handler))
+        // Sucet zostatkov pouzivatela podla meny
+        .route("/users/:id/balances", get(get_balances_handler))
+        .route("/users/:id/total-balance", get(get_total_balance_handler))
         // Vytvorenie noveho bankoveho uctu
         .route("/accounts", post(create_account_handler))
         // Ziskanie informacii o ucte podla ID
         .route("/accounts/:id", get(get_account_handler))
+        // Hromadne ziskanie vlastnych uctov podla ID (X-API-Key)
+        .route("/accounts/batch", post(get_accounts_batch_handler))
+        // Vyhladanie uctu podla cisla uctu (napr. pred prevodom)
+        .route(
+            "/account-numbers/:number",
+            get(get_account_by_number_handler),
+        )
+        // Zatvorenie uctu so zaverecnym vypisom (vlastnik, X-API-Key)
+        .route("/accounts/:id/close", post(close_account_handler))
+        .route("/accounts/:id/merge", post(merge_accounts_handler))
+        // Hranica nizkeho zostatku pre notifikaciu (vlastnik, X-API-Key)
+        .route(
+            "/accounts/:id/low-balance-threshold",
+            put(set_low_balance_threshold_handler),
+        )
+        // PIN uctu pre velke prevody (X-API-Key)
+        .route("/accounts/:id/pin", put(set_account_pin_handler))
         // Ziskanie historie transakci pre dany ucet
         .route(
             "/accounts/:id/transactions",
             get(get_transaction_history_handler),
         )
+        // Export historie transakcii vo formate OFX (pre uctovne programy)
+        .route(
+            "/accounts/:id/transactions.ofx",
+            get(export_transactions_ofx_handler),
+        )
+        // Suhrnne statistiky uctu za casove okno
+        .route("/accounts/:id/stats", get(account_stats_handler))
+        // Protistrany uctu (caste platby, vlastnik, X-API-Key)
+        .route(
+            "/accounts/:id/counterparties",
+            get(list_counterparties_handler),
+        )
+        // Vypis uctu za obdobie s pociatocnym a konecnym zostatkom
+        .route("/accounts/:id/statement", get(account_statement_handler))
+        // Zostatok a dostupny zostatok (bez blokovanych vkladov)
+        .route("/accounts/:id/balance", get(get_account_balance_handler))
         // Vytvorenie novej transakcie (prevod penazi)
         .route("/transactions", post(make_transaction_handler))
+        // Prevod cerpajuci z viacerych vlastnych uctov (X-API-Key)
+        .route(
+            "/transactions/multi-source",
+            post(make_multi_source_transfer_handler),
+        )
+        // Hromadny prevod z jedneho uctu viacerym prijemcom (X-API-Key)
+        .route("/transactions/batch", post(make_batch_transfer_handler))
+        // Nahlad prevodu (poplatok, vysledny zostatok) bez jeho vykonania
+        .route("/transactions/preview", post(preview_transfer_handler))
+        // Prevod s potvrdenim (velke sumy) a jeho potvrdenie
+        .route("/transactions/initiate", post(initiate_transfer_handler))
+        .route("/transactions/confirm", post(confirm_transfer_handler))
+        // Zrusenie cakajuceho prevodu alebo trvaleho prikazu (X-API-Key)
+        .route("/transactions/:id/cancel", post(cancel_transfer_handler))
+        // Detail transakcie (len pre jej ucastnika, X-API-Key)
+        .route("/transactions/:id", get(get_transaction_handler))
+        // Stitky transakcie (ucastnik transakcie, X-API-Key)
+        .route("/transactions/:id/tags", post(add_transaction_tag_handler))
+        .route(
+            "/transactions/:id/tags/:tag",
+            delete(remove_transaction_tag_handler),
+        )
+        // Trvale prikazy prihlaseneho pouzivatela (X-API-Key)
+        .route(
+            "/me/recurring-transfers",
+            get(list_recurring_transfers_handler).post(create_recurring_transfer_handler),
+        )
+        // Nastavenia notifikacii a schranka notifikacii (X-API-Key)
+        .route(
+            "/me/notification-prefs",
+            get(get_notification_prefs_handler).put(update_notification_prefs_handler),
+        )
+        .route("/me/notifications", get(list_notifications_handler))
+        // Overenie e-mailovej adresy: vydanie tokenu (X-API-Key) a jeho potvrdenie
+        .route(
+            "/me/email-verification",
+            post(request_email_verification_handler),
+        )
+        .route("/verify-email", post(verify_email_handler))
+        // Historia prihlaseni (X-API-Key)
+        .route("/me/login-history", get(login_history_handler))
+        // Export vsetkych udajov prihlaseneho pouzivatela (GDPR, X-API-Key)
+        .route("/me/export", get(export_user_data_handler))
         // Pridanie penazi na ucet
-        .route("/addmoney", post(add_money_handler));
-
-    // Spustenie HTTP servera na porte 3000
-    axum::Server::bind(&"127.0.0.1:3000".parse().unwrap())
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+        .route("/addmoney", post(add_money_handler))
+        // Vytvorenie API kluca pomocou mena a hesla
+        .route("/api-keys", post(create_api_key_handler))
+        // Sprava vlastnych API klucov (X-API-Key)
+        .route(
+            "/me/api-keys",
+            get(list_api_keys_handler).post(rotate_api_key_handler),
+        )
+        .route("/me/api-keys/:id", delete(revoke_api_key_handler))
+        // Zapnutie/vypnutie rezimu len na citanie (administrator)
+        .route("/admin/read-only", post(set_read_only_handler))
+        // Kontrola zostatkov voci historii transakcii (administrator)
+        .route("/admin/consistency", get(consistency_handler))
+        // Hromadny import pouzivatelov z CSV (administrator)
+        .route("/admin/import/users", post(import_users_handler))
+        // Pripisanie uroku a kontrola zaokruhlovacieho uctu (administrator)
+        .route("/admin/interest", post(apply_interest_handler))
+        .route("/admin/interest/audit", get(rounding_audit_handler))
+        .route(
+            "/admin/alerts/negative-balance",
+            get(negative_balance_alerts_handler),
+        )
+        // Schvalenie/zamietnutie uctov cakajucich na schvalenie (administrator)
+        .route("/admin/accounts/:id/approve", post(approve_account_handler))
+        .route("/admin/accounts/:id/reject", post(reject_account_handler))
+        // Suhrnne statistiky systemu (administrator)
+        .route("/admin/stats", get(system_stats_handler))
+        // Vyhladavanie transakcii vsetkych uctov (administrator)
+        .route("/admin/transactions", get(search_transactions_handler))
+        // Oprava zostatkov podla externeho zdroja (administrator)
+        .route("/admin/reconcile", post(reconcile_handler))
+        // Hromadny vklad na viacero uctov v jednej transakcii (administrator)
+        .route("/admin/deposits/batch", post(add_money_batch_handler))
+        // Storno celej hromadnej davky prevodov (administrator)
+        .route("/admin/batches/:id/reverse", post(reverse_batch_handler))
+        // Limity poziadaviek podla trasy (RATE_LIMIT_DEFAULT, RATE_LIMITS)
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+        // Format sum v odpovedi (amount_format/amount_scale alebo hlavicka Accept)
+        .layer(middleware::from_fn(formatted_amounts))
+        // Odsadeny JSON na ladenie (JSON_PRETTY)
+        .layer(middleware::from_fn_with_state(state.clone(), pretty_json))
+        // Kompresia vacsich odpovedi (gzip/brotli podla Accept-Encoding)
+        .layer(compression_layer(&state.config))
+        // Odmietnutie zapisov v rezime udrzby
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            read_only_guard,
+        ))
+        // Prevod lokalizovanych sum (X-Amount-Locale) na kanonicky zapis
+        .layer(middleware::from_fn(localized_amounts))
+        // Casovy limit spracovania poziadavky (504 po REQUEST_TIMEOUT_SECS)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            request_timeout,
+        ))
+        // ID poziadavky v logoch, chybach a hlavicke odpovede (REQUEST_ID_HEADER)
+        .layer(middleware::from_fn_with_state(state.clone(), request_id))
+        // Zdielany stav (zdroj kurzov a pod.)
+        .with_state(state)
 }
 
 /// Handler pre registraciu noveho pouzivatela
@@ -54,17 +253,42 @@ async fn main() {
 ///
 /// # Vystupy
 /// - 200 OK: uspesne vytvoreny pouzivatel (vracia PublicUser)
-/// - 400 Bad Request: chyba pri vytvarani (napr. uz existuje)
+/// - 422 Unprocessable Entity: heslo nevyhovuje GET /meta/password-policy (kod
+///   INVALID_BODY s popisom nesplnenych poziadaviek)
+/// - 409 Conflict: meno je obsadene (kod ALREADY_EXISTS)
 async fn create_user_handler(
+    State(state): State<AppState>,
     Json(payload): Json<RegisterRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match create_user(&payload.username, &payload.password).await {
-        Ok(user) => Ok(Json(json!(user))),
-        Err(_e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": "Failed to create user"})),
-        )),
-    }
+) -> Result<Json<serde_json::Value>, BankError> {
+    let user = state
+        .repo
+        .create_user(&payload.username, &payload.password)
+        .await?;
+    Ok(Json(json!(user)))
+}
+
+/// Handler pre vyhladanie pouzivatela podla mena
+///
+/// # Endpoint
+/// GET /users/by-username/:username
+///
+/// # Hlavicky
+/// - X-API-Key: kluc prihlaseneho pouzivatela (zabrani anonymnemu zistovaniu mien)
+///
+/// # Parametre
+/// - username: meno pouzivatela, na velkosti pismen nezalezi
+///
+/// # Vystupy
+/// - 200 OK: PublicUser (len id a meno)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 404 Not Found: pouzivatel neexistuje alebo meno nie je jednoznacne (USER_NOT_FOUND)
+async fn get_user_by_username_handler(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(username): Path<String>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let user = state.repo.get_user_by_username(&username).await?;
+    Ok(Json(json!(user)))
 }
 
 /// Handler pre ziskanie informacii o pouzivatelovi
@@ -79,9 +303,10 @@ async fn create_user_handler(
 /// - 200 OK: uspesne ziskane udaje (vracia PublicUser)
 /// - 404 Not Found: pouzivatel neexistuje
 async fn get_user_handler(
+    State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match get_user(user_id).await {
+    match state.repo.get_user(user_id).await {
         Ok(user) => Ok(Json(json!(user))),
         Err(_) => Err((
             StatusCode::NOT_FOUND,
@@ -97,27 +322,67 @@ async fn get_user_handler(
 ///
 /// # Parametre
 /// - id: UUID pouzivatela
+/// - cancel_scheduled: `?cancel_scheduled=true` zrusi nepotvrdene prevody a trvale prikazy uctov
 ///
 /// # Vystupy
 /// - 200 OK: pouzivatel uspesne zmazany
 /// - 404 Not Found: pouzivatel neexistuje
+/// - 409 Conflict: niektory ucet ma nenulovy zostatok (ACCOUNT_HAS_FUNDS), cakajuce prevody
+///   (ACCOUNT_HAS_HOLDS) alebo trvale prikazy (ACCOUNT_HAS_SCHEDULED_TRANSFERS)
 /// - 500 Internal Server Error: chyba pri mazani
 async fn delete_user_handler(
+    State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match delete_user(user_id).await {
-        Ok(rows) if rows > 0 => Ok(Json(json!({"message": "User deleted"}))),
-        Ok(_) => Err((
-            StatusCode::NOT_FOUND,
-            Json(json!({"error": "User not found"})),
-        )),
-        Err(_) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": "Failed to delete user"})),
-        )),
+    Query(query): Query<DeleteUserQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    match state
+        .repo
+        .delete_user(user_id, query.cancel_scheduled)
+        .await?
+    {
+        0 => Err(BankError::UserNotFound),
+        _ => Ok(Json(json!({"message": "User deleted"}))),
     }
 }
 
+/// Handler pre sucet zostatkov pouzivatela podla meny
+///
+/// # Endpoint
+/// GET /users/:id/balances
+///
+/// # Parametre
+/// - id: UUID pouzivatela
+///
+/// # Vystupy
+/// - 200 OK: mapa mena -> sucet zostatkov, napr. {"EUR": "150.00", "USD": "20.00"}
+/// - 404 Not Found: pouzivatel neexistuje
+async fn get_balances_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let balances = state.repo.get_balances_by_currency(user_id).await?;
+    Ok(Json(json!(balances)))
+}
+
+/// Handler pre celkovy zostatok pouzivatela
+///
+/// # Endpoint
+/// GET /users/:id/total-balance
+///
+/// # Parametre
+/// - id: UUID pouzivatela
+///
+/// # Vystupy
+/// - 200 OK: {"user_id": "...", "total": "170.00"} (bez uctov "0")
+/// - 404 Not Found: pouzivatel neexistuje
+async fn get_total_balance_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let total = state.repo.total_balance(user_id).await?;
+    Ok(Json(json!({"user_id": user_id, "total": total})))
+}
+
 /// Handler pre vytvorenie noveho bankoveho uctu
 ///
 /// # Endpoint
@@ -125,20 +390,33 @@ async fn delete_user_handler(
 ///
 /// # Vstupy
 /// - user_id: UUID pouzivatela, pre ktoreho sa ma ucet vytvorit
+/// - currency: volitelna mena uctu (ISO 4217), predvolene EUR
+/// - type: volitelny typ uctu (checking, savings, business), predvolene checking
+/// - initial_deposit: volitelny pociatocny zostatok, predvolene 0
 ///
 /// # Vystupy
-/// - 200 OK: ucet uspesne vytvoreny (vracia PubAccount)
-/// - 400 Bad Request: chyba pri vytvarani uctu
+/// - 200 OK: ucet uspesne vytvoreny (vracia PubAccount; typy z ACCOUNT_TYPES_REQUIRING_APPROVAL
+///   maju stav pending), v historii je zaznam o otvoreni uctu
+/// - 400 Bad Request: pouzivatel neexistuje (REFERENCE_NOT_FOUND), neplatna mena
+///   alebo neplatny pociatocny zostatok (INVALID_AMOUNT)
+/// - 409 Conflict: nenulovy pociatocny zostatok na ucte, ktory caka na schvalenie (ACCOUNT_PENDING)
+/// - 422 Unprocessable Entity: pouzivatel dosiahol maximalny pocet uctov (LIMIT_EXCEEDED)
+///   alebo pociatocny zostatok je mimo AMOUNT_BOUNDS (AMOUNT_OUT_OF_BOUNDS)
+/// - 500 Internal Server Error: chyba pri vytvarani uctu
 async fn create_account_handler(
+    State(state): State<AppState>,
     Json(payload): Json<CreateAccountRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match create_account(payload.user_id).await {
-        Ok(account) => Ok(Json(json!(account))),
-        Err(_) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": "Failed to create account"})),
-        )),
-    }
+) -> Result<Json<serde_json::Value>, BankError> {
+    let account = state
+        .repo
+        .create_account(
+            payload.user_id,
+            payload.currency.as_deref(),
+            payload.account_type,
+            payload.initial_deposit.unwrap_or_default(),
+        )
+        .await?;
+    Ok(Json(json!(account)))
 }
 
 /// Handler pre ziskanie informacii o ucte
@@ -148,20 +426,261 @@ async fn create_account_handler(
 ///
 /// # Parametre
 /// - id: UUID uctu alebo pouzivatela
+/// - status, type, currency (query, volitelne): filtre, napr. `?status=active&type=savings&currency=EUR`
+/// - display_currency (query, volitelne): ku kazdemu uctu prida `display_balance`
+///   so zostatkom prepocitanym cez RateProvider (ulozeny zostatok sa nemeni)
 ///
 /// # Vystupy
-/// - 200 OK: uspesne ziskane udaje o ucte(och)
-/// - 400 Bad Request: chyba pri ziskavani udajov
+/// - 200 OK: {"accounts": [...], "truncated": bool} - najviac MAX_ACCOUNTS_LISTED uctov
+/// - 400 Bad Request: chyba pri ziskavani udajov, neplatna mena zobrazenia (INVALID_CURRENCY)
+/// - 422 Unprocessable Entity: kurz nie je k dispozicii (RATE_UNAVAILABLE)
+///
+/// # Hlavicky
+/// - X-API-Key, X-Admin-Token (volitelne): pri REDACT_ACCOUNT_NUMBERS=true vidi
+///   plne cisla uctov len vlastnik a administrator, ostatni len posledne 4 znaky
 async fn get_account_handler(
+    State(state): State<AppState>,
+    viewer: Viewer,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match get_account(user_id).await {
-        Ok(account) => Ok(Json(json!(account))),
-        Err(_) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": "Failed to get account"})),
-        )),
+    Query(mut params): Query<AccountListQuery>,
+) -> Result<Json<serde_json::Value>, Response> {
+    params.filter.include_empty = params.include_empty;
+
+    let display_currency = params
+        .display_currency
+        .as_deref()
+        .map(normalize_currency)
+        .transpose()
+        .map_err(IntoResponse::into_response)?;
+
+    let mut list = state
+        .repo
+        .get_account(user_id, &params.filter)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Failed to get account"})),
+            )
+                .into_response()
+        })?;
+
+    if let Some(currency) = display_currency {
+        convert_for_display(
+            state.rates.as_ref(),
+            &mut list.accounts,
+            &currency,
+            state.config.rounding_mode,
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+    }
+    if state.redact_account_numbers {
+        viewer.redact_accounts(&mut list.accounts);
     }
+    Ok(Json(json!(list)))
+}
+
+/// Handler pre hromadne ziskanie uctov
+///
+/// # Endpoint
+/// POST /accounts/batch
+///
+/// # Hlavicky
+/// - X-API-Key: kluc pouzivatela
+///
+/// # Vstupy
+/// - ids: zoznam UUID uctov
+///
+/// # Vystupy
+/// - 200 OK: {"accounts": [...], "not_found": [...], "forbidden": [...]}
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 422 Unprocessable Entity: prilis vela ID (LIMIT_EXCEEDED)
+async fn get_accounts_batch_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<AccountBatchRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let batch = state
+        .repo
+        .get_accounts_batch(auth.user_id, &payload.ids)
+        .await?;
+    Ok(Json(json!(batch)))
+}
+
+/// Handler pre vyhladanie uctu podla cisla uctu
+///
+/// # Endpoint
+/// GET /account-numbers/:number
+///
+/// # Vystupy
+/// - 200 OK: {"id", "account_number", "currency", "status"} - bez zostatku a vlastnika
+/// - 400 Bad Request: cislo nema platny tvar alebo kontrolne cislice (INVALID_ACCOUNT_NUMBER)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+async fn get_account_by_number_handler(
+    State(state): State<AppState>,
+    Path(number): Path<String>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let account = state.repo.get_account_by_number(&number).await?;
+    Ok(Json(json!({
+        "id": account.id,
+        "account_number": account.account_number,
+        "currency": account.currency,
+        "status": account.status
+    })))
+}
+
+/// Handler pre zatvorenie uctu
+///
+/// # Endpoint
+/// POST /accounts/:id/close
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu
+///
+/// # Vstupy
+/// - sweep_to: volitelny ucet, na ktory sa prevedie zostatok
+/// - cancel_scheduled: true = zrusit nepotvrdene prevody a trvale prikazy uctu (predvolene false)
+///
+/// # Vystupy
+/// - 200 OK: FinalStatement (zaverecny vypis)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet patri inemu pouzivatelovi
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 409 Conflict: ucet je uz zatvoreny (ACCOUNT_CLOSED), ma cakajuce prevody
+///   (ACCOUNT_HAS_HOLDS), trvale prikazy (ACCOUNT_HAS_SCHEDULED_TRANSFERS),
+///   zostatok bez sweep_to (ACCOUNT_HAS_FUNDS) alebo zaporny zostatok (ACCOUNT_OVERDRAWN)
+async fn close_account_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<CloseAccountRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let statement = state
+        .repo
+        .close_account(
+            account_id,
+            auth.user_id,
+            payload.sweep_to,
+            payload.cancel_scheduled,
+        )
+        .await?;
+    Ok(Json(json!(statement)))
+}
+
+/// Handler pre zlucenie uctu do ineho vlastneho uctu
+///
+/// # Endpoint
+/// POST /accounts/:id/merge
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika oboch uctov
+///
+/// # Vstupy
+/// - target_account: ucet s rovnakou menou, na ktory prejde zostatok
+/// - cancel_scheduled: true = zrusit nepotvrdene prevody a trvale prikazy uctu (predvolene false)
+///
+/// # Vystupy
+/// - 200 OK: AccountMerge (cielovy ucet po zluceni, prevod zostatku)
+/// - 400 Bad Request: rovnaky ucet alebo rozne meny (INVALID_BODY)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: niektory ucet patri inemu pouzivatelovi
+/// - 404 Not Found: niektory ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 409 Conflict: niektory ucet nie je aktivny alebo su na zlucovanom ucte
+///   cakajuce prevody (ACCOUNT_HAS_HOLDS) ci trvale prikazy (ACCOUNT_HAS_SCHEDULED_TRANSFERS),
+///   zlucovany ucet ma zaporny zostatok (ACCOUNT_OVERDRAWN)
+async fn merge_accounts_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<MergeAccountRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let merge = state
+        .repo
+        .merge_accounts(
+            auth.user_id,
+            account_id,
+            payload.target_account,
+            payload.cancel_scheduled,
+        )
+        .await?;
+    Ok(Json(json!(merge)))
+}
+
+/// Handler pre nastavenie hranice nizkeho zostatku
+///
+/// # Endpoint
+/// PUT /accounts/:id/low-balance-threshold
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu
+///
+/// # Vstupy
+/// - threshold: hranica zostatku alebo null (upozornenie vypnute)
+///
+/// # Vystupy
+/// - 200 OK: LowBalanceThreshold
+/// - 400 Bad Request: zaporna hranica (INVALID_AMOUNT)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet patri inemu pouzivatelovi
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+async fn set_low_balance_threshold_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<LowBalanceThresholdRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let threshold = state
+        .repo
+        .set_low_balance_threshold(account_id, auth.user_id, payload.threshold)
+        .await?;
+    Ok(Json(json!(threshold)))
+}
+
+/// Handler pre nastavenie PIN-u uctu
+///
+/// # Endpoint
+/// PUT /accounts/:id/pin
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu
+///
+/// # Vstupy
+/// - pin: 4 az 8 cislic alebo null (PIN sa odstrani)
+///
+/// # Vystupy
+/// - 200 OK: AccountPin ({"account_id", "pin_set"}), PIN sa nevracia
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet patri inemu pouzivatelovi
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 422 Unprocessable Entity: PIN nema 4 az 8 cislic (INVALID_BODY)
+async fn set_account_pin_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<AccountPinRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let pin = state
+        .repo
+        .set_account_pin(account_id, auth.user_id, payload.pin.as_deref())
+        .await?;
+    Ok(Json(json!(pin)))
+}
+
+/// Handler pre zostatok uctu s blokovanymi vkladmi
+///
+/// # Endpoint
+/// GET /accounts/:id/balance
+///
+/// # Vystupy
+/// - 200 OK: AccountBalance (balance, available_balance, holds)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+async fn get_account_balance_handler(
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let balance = state.repo.get_account_balance(account_id).await?;
+    Ok(Json(json!(balance)))
 }
 
 /// Handler pre pridanie penazi na ucet
@@ -172,17 +691,28 @@ async fn get_account_handler(
 /// # Vstupy
 /// - account_id: UUID uctu
 /// - amount: suma na pridanie (musi byt kladna)
+/// - idempotency_key: volitelny kluc pre bezpecne opakovanie poziadavky
 ///
 /// # Vystupy
 /// - 200 OK: peniaze uspesne pridane (vracia aktualizovany PubAccount)
-/// - Chybova odpoved: nepodarilo sa pridat peniaze
+/// - 400 Bad Request: suma nie je kladna (INVALID_AMOUNT)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 422 Unprocessable Entity: suma je prilis velka (AMOUNT_TOO_LARGE)
+/// - 500 Internal Server Error: chyba databazy
 async fn add_money_handler(
+    State(state): State<AppState>,
     Json(payload): Json<AddMoneyRequest>,
-) -> impl axum::response::IntoResponse {
-    match add_money(payload.account_id, payload.amount).await {
-        Ok(account) => axum::response::Json(json!(account)),
-        Err(_e) => axum::response::Json(json!({"error": "Failed to add money"})),
-    }
+) -> Result<Json<serde_json::Value>, BankError> {
+    let account = state
+        .repo
+        .add_money(
+            payload.account_id,
+            payload.amount,
+            payload.idempotency_key.as_deref(),
+        )
+        .await?;
+
+    Ok(Json(json!(account)))
 }
 
 /// Handler pre vytvorenie transakcie (prevod penazi medzi uctami)
@@ -194,6 +724,12 @@ async fn add_money_handler(
 /// - from_account: UUID uctu odosielatela
 /// - to_account: UUID uctu prijemcu
 /// - amount: suma prevodu (musi byt kladna)
+/// - sender_note, receiver_note: volitelne sukromne poznamky odosielatela a prijemcu
+/// - idempotency_key: volitelny kluc pre bezpecne opakovanie poziadavky
+/// - splits: volitelne rozdelenie do kategorii [{category, amount}], sucet = amount
+/// - pin: PIN uctu odosielatela, povinny od TRANSFER_PIN_THRESHOLD, ak ma ucet PIN
+/// - expand (query, volitelne): `accounts` - odpoved obsahuje aj vsetky ucty
+///   vlastnika uctu odosielatela so zostatkami po prevode
 ///
 /// # Validacie
 /// - Overuje ci ma odosielatel dostatocny zostatok
@@ -201,66 +737,1336 @@ async fn add_money_handler(
 /// - Pouziva databazovu transakciu pre ACID vlastnosti
 ///
 /// # Vystupy
-/// - 200 OK: transakcia uspesne vytvorena (vracia Transaction)
-/// - 400 Bad Request: nedostatocny zostatok, neplatne ucty, atd.
+/// - 200 OK: transakcia uspesne vytvorena (vracia Transaction, pri `expand=accounts`
+///   TransferWithAccounts - {"transaction", "accounts", "truncated"})
+/// - 400 Bad Request: suma nie je kladna (INVALID_AMOUNT), neplatna poznamka (INVALID_NOTE)
+/// - 403 Forbidden: odosielatel nema overeny e-mail (EMAIL_NOT_VERIFIED, pri REQUIRE_VERIFIED_EMAIL),
+///   ucet odosielatela je mladsi ako MIN_ACCOUNT_AGE_SECS (ACCOUNT_TOO_NEW),
+///   chybajuci alebo nespravny PIN (PIN_REQUIRED, INVALID_PIN), suma od
+///   TRANSFER_CONFIRMATION_THRESHOLD - treba /transactions/initiate (CONFIRMATION_REQUIRED)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 409 Conflict: rovnaky prevod bez idempotency_key v okne DUPLICATE_TRANSFER_WINDOW_SECS
+///   (DUPLICATE_TRANSFER)
+/// - 422 Unprocessable Entity: nedostatocny zostatok (INSUFFICIENT_FUNDS), vycerpany
+///   mesacny limit vyberov typu uctu (WITHDRAWAL_LIMIT_REACHED), chybajuce povinne polia (MISSING_FIELDS), neplatna hodnota pola (INVALID_BODY)
+///
+/// # Hlavicky
+/// - X-API-Key, X-Admin-Token (volitelne): pri `expand=accounts` a REDACT_ACCOUNT_NUMBERS=true
+///   vidi plne cisla uctov len vlastnik a administrator (ako pri GET /accounts/:id)
+///
+/// # Poznamka
+/// Ucty sa nacitaju hned po potvrdeni prevodu, takze zostatky uz prevod obsahuju
+/// (pripadne aj operacie, ktore prebehli medzitym).
 async fn make_transaction_handler(
-    Json(payload): Json<TransactionRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match make_transaction(payload.from_account, payload.to_account, payload.amount).await {
-        Ok(transaction) => Ok(Json(json!(transaction))),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": e.to_string()})),
-        )),
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Query(params): Query<TransferQuery>,
+    ValidatedJson(payload): ValidatedJson<TransactionRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    state
+        .repo
+        .verify_transfer_pin(payload.from_account, payload.amount, payload.pin.as_deref())
+        .await?;
+
+    let _permit = state.account_transfer_permit(payload.from_account).await?;
+    let transaction = state
+        .repo
+        .make_transaction(
+            payload.from_account,
+            payload.to_account,
+            payload.amount,
+            TransferNotes {
+                sender: payload.sender_note.as_deref(),
+                receiver: payload.receiver_note.as_deref(),
+            },
+            payload.idempotency_key.as_deref(),
+            &payload.splits,
+        )
+        .await?;
+
+    if !params.expand_accounts() {
+        return Ok(Json(json!(transaction)));
     }
+
+    let sender = state.repo.get_account_by_id(payload.from_account).await?;
+    let mut accounts = state
+        .repo
+        .get_account(sender.user_id, &AccountFilter::default())
+        .await?;
+    if state.redact_account_numbers {
+        viewer.redact_accounts(&mut accounts.accounts);
+    }
+
+    Ok(Json(json!(TransferWithAccounts {
+        transaction,
+        accounts,
+    })))
 }
 
-/// Handler pre prihlasenie pouzivatela
+/// Handler pre nahlad prevodu
 ///
 /// # Endpoint
-/// POST /login
+/// POST /transactions/preview
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu odosielatela
 ///
 /// # Vstupy
-/// - username: pouzivatelske meno
-/// - password: heslo
+/// - from_account: UUID uctu odosielatela
+/// - to_account: UUID uctu prijemcu
+/// - amount: suma prevodu
 ///
 /// # Vystupy
-/// - 200 OK: uspesne prihlasenie (vracia pouzivatela a jeho ucty)
-/// - 401 Unauthorized: nespravne prihlasovacie udaje
-async fn login_user_handler(
-    Json(payload): Json<LoginRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match login_user(&payload.username, &payload.password).await {
-        Ok(user) => {
-            let accounts = get_account(user.id).await.unwrap_or_default();
-            Ok(Json(json!({
-                "user": user,
-                "accounts": accounts
-            })))
-        }
-        Err(e) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": e})))),
+/// - 200 OK: TransferPreview (aj ked by prevod zlyhal - dovod je v poli `error`)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - 404 Not Found: ucet odosielatela neexistuje (ACCOUNT_NOT_FOUND)
+async fn preview_transfer_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<TransferPreviewRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let preview = state
+        .repo
+        .preview_transfer(
+            auth.user_id,
+            payload.from_account,
+            payload.to_account,
+            payload.amount,
+        )
+        .await?;
+    Ok(Json(json!(preview)))
+}
+
+/// Handler pre prevod z viacerych uctov
+///
+/// # Endpoint
+/// POST /transactions/multi-source
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika vsetkych zdrojovych uctov
+///
+/// # Vstupy
+/// - sources: zdrojove ucty v poradi cerpania
+/// - to_account: UUID uctu prijemcu
+/// - amount: celkova suma (musi byt kladna)
+/// - sender_note, receiver_note: volitelne poznamky
+/// - pin: PIN zdrojovych uctov (povinny od TRANSFER_PIN_THRESHOLD pre zdroje s PIN-om)
+///
+/// # Vystupy
+/// - 200 OK: MultiSourceTransfer s ciastkovymi transakciami
+/// - 400 Bad Request: suma nie je kladna (INVALID_AMOUNT)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: zdrojovy ucet patri inemu pouzivatelovi, celkova suma od
+///   TRANSFER_CONFIRMATION_THRESHOLD (CONFIRMATION_REQUIRED), chybajuci alebo
+///   nespravny PIN (PIN_REQUIRED, INVALID_PIN)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 409 Conflict: niektory ucet nie je aktivny
+/// - 422 Unprocessable Entity: zdroje spolu nestacia (INSUFFICIENT_FUNDS),
+///   prazdne alebo duplicitne zdroje (INVALID_BODY)
+async fn make_multi_source_transfer_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<MultiSourceTransferRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Z kazdeho zdroja moze odist az cela suma - PIN sa overuje voci celkovej sume
+    for source in &payload.sources {
+        state
+            .repo
+            .verify_transfer_pin(*source, payload.amount, payload.pin.as_deref())
+            .await?;
     }
+
+    let _permit = state.transfer_permit(auth.user_id)?;
+    let transfer = state
+        .repo
+        .make_transaction_from_multiple(
+            auth.user_id,
+            &payload.sources,
+            payload.to_account,
+            payload.amount,
+            payload.sender_note.as_deref(),
+            payload.receiver_note.as_deref(),
+        )
+        .await?;
+
+    Ok(Json(json!(transfer)))
 }
 
-/// Handler pre ziskanie historie transakci uctu
+/// Handler pre hromadny prevod
 ///
 /// # Endpoint
-/// GET /accounts/:id/transactions
+/// POST /transactions/batch
 ///
-/// # Parametre
-/// - id: UUID uctu
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu odosielatela
+///
+/// # Vstupy
+/// - from_account: UUID uctu odosielatela
+/// - transfers: [{to_account, amount, sender_note?, receiver_note?}], najviac MAX_BATCH_TRANSFERS
+/// - pin: PIN uctu odosielatela (povinny, ak sucet davky dosiahne TRANSFER_PIN_THRESHOLD)
 ///
 /// # Vystupy
-/// - 200 OK: zoznam vsetkych transakci (odosielatel alebo prijemca)
-/// - 400 Bad Request: chyba pri ziskavani transakci
-async fn get_transaction_history_handler(
-    Path(account_id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match get_transaction_history(account_id).await {
-        Ok(transactions) => Ok(Json(json!(transactions))),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": e.to_string()})),
-        )),
+/// - 200 OK: TransferBatch s batch_id a transakciami davky
+/// - 400 Bad Request: niektora suma nie je kladna (INVALID_AMOUNT)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet odosielatela patri inemu pouzivatelovi, neovereny e-mail
+///   (EMAIL_NOT_VERIFIED), prilis novy ucet (ACCOUNT_TOO_NEW), prevod davky
+///   od TRANSFER_CONFIRMATION_THRESHOLD (CONFIRMATION_REQUIRED), chybajuci alebo
+///   nespravny PIN (PIN_REQUIRED, INVALID_PIN)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 409 Conflict: niektory ucet nie je aktivny
+/// - 422 Unprocessable Entity: zostatok nestaci na celu davku (INSUFFICIENT_FUNDS),
+///   prazdna alebo prilis velka davka (INVALID_BODY)
+async fn make_batch_transfer_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<BatchTransferRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Davka je jeden odchod penazi z uctu - PIN sa overuje voci jej suctu
+    let total: Decimal = payload.transfers.iter().map(|t| t.amount).sum();
+    state
+        .repo
+        .verify_transfer_pin(payload.from_account, total, payload.pin.as_deref())
+        .await?;
+
+    let _permit = state.transfer_permit(auth.user_id)?;
+    let batch = state
+        .repo
+        .make_batch_transfer(auth.user_id, payload.from_account, &payload.transfers)
+        .await?;
+
+    Ok(Json(json!(batch)))
+}
+
+/// Handler pre detail jednej transakcie
+///
+/// # Endpoint
+/// GET /transactions/:id
+///
+/// # Hlavicky
+/// - X-API-Key: kluc pouzivatela, ktory vlastni ucet odosielatela alebo prijemcu
+///
+/// - If-None-Match (volitelne): ETag z predchadzajucej odpovede
+///
+/// # Vystupy
+/// - 200 OK: Transaction s poznamkou strany pouzivatela, hlavicky ETag a Cache-Control
+///   (`private, max-age=TRANSACTION_CACHE_MAX_AGE_SECS`)
+/// - 304 Not Modified: ETag sa zhoduje s If-None-Match (bez tela)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: pouzivatel nie je ucastnikom transakcie
+/// - 404 Not Found: transakcia neexistuje (TRANSACTION_NOT_FOUND)
+///
+/// # Poznamka
+/// Transakcia sa po zapise nemeni, ETag vsak vychadza z obsahu odpovede,
+/// takze zmena poznamky alebo rozdelenia sumy ho zmeni tiez.
+async fn get_transaction_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Path(transaction_id): Path<Uuid>,
+) -> Result<Response, BankError> {
+    let transaction = state
+        .repo
+        .get_transaction(transaction_id, auth.user_id)
+        .await?;
+
+    let body = json!(transaction);
+    let etag = etag(body.to_string().as_bytes());
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (
+            header::CACHE_CONTROL,
+            private_cache_control(state.config.transaction_cache_max_age),
+        ),
+    ];
+
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
     }
+    Ok((cache_headers, Json(body)).into_response())
+}
+
+/// Handler pre pridanie stitku k transakcii
+///
+/// # Endpoint
+/// POST /transactions/:id/tags
+///
+/// # Hlavicky
+/// - X-API-Key: kluc ucastnika transakcie
+///
+/// # Vstupy
+/// - tag: stitok, napr. "rent" (pismena, cislice, '-', '_', najviac 32 znakov)
+///
+/// # Vystupy
+/// - 200 OK: {"transaction_id", "tags"} - vsetky stitky pouzivatela pre transakciu
+/// - 400 Bad Request: neplatny stitok (INVALID_TAG)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: pouzivatel nie je ucastnikom transakcie
+/// - 404 Not Found: transakcia neexistuje (TRANSACTION_NOT_FOUND)
+async fn add_transaction_tag_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(transaction_id): Path<Uuid>,
+    Json(payload): Json<TagRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let tags = state
+        .repo
+        .add_transaction_tag(transaction_id, auth.user_id, &payload.tag)
+        .await?;
+    Ok(Json(json!(tags)))
+}
+
+/// Handler pre odstranenie stitku z transakcie
+///
+/// # Endpoint
+/// DELETE /transactions/:id/tags/:tag
+///
+/// # Vystupy
+/// - 200 OK: {"transaction_id", "tags"} - zostavajuce stitky
+/// - 400 Bad Request: neplatny stitok (INVALID_TAG)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: pouzivatel nie je ucastnikom transakcie
+/// - 404 Not Found: transakcia neexistuje (TRANSACTION_NOT_FOUND)
+async fn remove_transaction_tag_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((transaction_id, tag)): Path<(Uuid, String)>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let tags = state
+        .repo
+        .remove_transaction_tag(transaction_id, auth.user_id, &tag)
+        .await?;
+    Ok(Json(json!(tags)))
 }
+
+/// Handler pre vytvorenie trvaleho prikazu
+///
+/// # Endpoint
+/// POST /me/recurring-transfers
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu odosielatela
+///
+/// # Vstupy
+/// - from_account, to_account, amount: parametre prevodu
+/// - frequency: daily | weekly | monthly
+/// - start_at: volitelny cas prveho prevodu (RFC 3339), predvolene hned
+/// - sender_note, receiver_note: volitelne poznamky
+///
+/// # Vystupy
+/// - 200 OK: RecurringTransfer
+/// - 400 Bad Request: suma nie je kladna, neplatna poznamka alebo neexistujuci prijemca
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - 404 Not Found: ucet odosielatela neexistuje
+async fn create_recurring_transfer_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateRecurringTransferRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let transfer = state
+        .repo
+        .create_recurring_transfer(auth.user_id, &payload)
+        .await?;
+    Ok(Json(json!(transfer)))
+}
+
+/// Handler pre zoznam trvalych prikazov
+///
+/// # Endpoint
+/// GET /me/recurring-transfers
+///
+/// # Vystupy
+/// - 200 OK: zoznam RecurringTransfer
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn list_recurring_transfers_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let transfers = state.repo.list_recurring_transfers(auth.user_id).await?;
+    Ok(Json(json!(transfers)))
+}
+
+/// Handler pre nastavenia notifikacii
+///
+/// # Endpoint
+/// GET /me/notification-prefs
+///
+/// # Vystupy
+/// - 200 OK: {"incoming_transfer", "large_withdrawal", "new_device_login"}
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn get_notification_prefs_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let prefs = state.repo.get_notification_prefs(auth.user_id).await?;
+    Ok(Json(json!(prefs)))
+}
+
+/// Handler pre zmenu nastaveni notifikacii
+///
+/// # Endpoint
+/// PUT /me/notification-prefs
+///
+/// # Vstupy
+/// - incoming_transfer, large_withdrawal, new_device_login: volitelne bool,
+///   chybajuce pole sa nemeni
+///
+/// # Vystupy
+/// - 200 OK: vysledne nastavenia
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn update_notification_prefs_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<NotificationPrefsUpdate>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let prefs = state
+        .repo
+        .update_notification_prefs(auth.user_id, &payload)
+        .await?;
+    Ok(Json(json!(prefs)))
+}
+
+/// Handler pre schranku notifikacii
+///
+/// # Endpoint
+/// GET /me/notifications
+///
+/// # Vystupy
+/// - 200 OK: zoznam Notification od najnovsej
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn list_notifications_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let notifications = state.repo.list_notifications(auth.user_id).await?;
+    Ok(Json(json!(notifications)))
+}
+
+/// Handler pre zacatie prevodu s potvrdenim
+///
+/// # Endpoint
+/// POST /transactions/initiate
+///
+/// # Vstupy
+/// - rovnake ako POST /transactions
+///
+/// # Vystupy
+/// - 200 OK: {"status": "completed", "transaction": ...} pre sumy pod hranicou
+/// - 200 OK: {"status": "pending", "id", "token", "expires_at"} ak je potrebne potvrdenie
+/// - 403 Forbidden: chybajuci alebo nespravny PIN (PIN_REQUIRED, INVALID_PIN)
+/// - 404 Not Found: ucet neexistuje
+/// - 422 Unprocessable Entity: chybajuce povinne polia (MISSING_FIELDS), zadane splits (INVALID_BODY)
+async fn initiate_transfer_handler(
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<TransactionRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Cakajuci prevod rozdelenie neuklada - pouzite /transactions
+    if !payload.splits.is_empty() {
+        return Err(BankError::InvalidBody(
+            "splits are not supported for transfers requiring confirmation".to_string(),
+        ));
+    }
+
+    state
+        .repo
+        .verify_transfer_pin(payload.from_account, payload.amount, payload.pin.as_deref())
+        .await?;
+
+    let _permit = state.account_transfer_permit(payload.from_account).await?;
+    let initiation = state
+        .repo
+        .initiate_transfer(
+            payload.from_account,
+            payload.to_account,
+            payload.amount,
+            payload.sender_note.as_deref(),
+            payload.receiver_note.as_deref(),
+        )
+        .await?;
+
+    Ok(Json(json!(initiation)))
+}
+
+/// Handler pre potvrdenie cakajuceho prevodu
+///
+/// # Endpoint
+/// POST /transactions/confirm
+///
+/// # Vstupy
+/// - token: token z /transactions/initiate
+///
+/// # Vystupy
+/// - 200 OK: prevod vykonany (vracia Transaction)
+/// - 403 Forbidden: odosielatel nema overeny e-mail (EMAIL_NOT_VERIFIED, pri REQUIRE_VERIFIED_EMAIL),
+///   ucet odosielatela je mladsi ako MIN_ACCOUNT_AGE_SECS (ACCOUNT_TOO_NEW)
+/// - 404 Not Found: token neexistuje alebo uz bol pouzity
+/// - 410 Gone: platnost tokenu vyprsala
+/// - 422 Unprocessable Entity: nedostatocny zostatok v case potvrdenia
+async fn confirm_transfer_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ConfirmTransferRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let transaction = state.repo.confirm_transfer(&payload.token).await?;
+    Ok(Json(json!(transaction)))
+}
+
+/// Handler pre zrusenie prevodu pred jeho vykonanim
+///
+/// # Endpoint
+/// POST /transactions/:id/cancel
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu odosielatela
+///
+/// # Parametre
+/// - id: ID cakajuceho prevodu (z /transactions/initiate) alebo trvaleho prikazu
+///
+/// # Vystupy
+/// - 200 OK: CancelledTransfer ({"id", "kind": "pending" | "recurring", ...})
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - 404 Not Found: prevod neexistuje (TRANSFER_NOT_FOUND)
+/// - 409 Conflict: prevod uz bol vykonany alebo zruseny (TRANSFER_NOT_PENDING)
+/// - 410 Gone: platnost cakajuceho prevodu vyprsala (TRANSFER_EXPIRED)
+async fn cancel_transfer_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let cancelled = state
+        .repo
+        .cancel_transfer(auth.user_id, transfer_id)
+        .await?;
+    Ok(Json(json!(cancelled)))
+}
+
+/// Handler pre vydanie overovacieho tokenu e-mailu
+///
+/// # Endpoint
+/// POST /me/email-verification
+///
+/// # Hlavicky
+/// - X-API-Key: kluc pouzivatela
+///
+/// # Vstupy
+/// - email: adresa, ktora sa ma overit
+///
+/// # Vystupy
+/// - 200 OK: EmailVerificationSent (adresa a cas platnosti, token pre /verify-email
+///   pride ako notifikacia email_verification)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 422 Unprocessable Entity: neplatna e-mailova adresa
+async fn request_email_verification_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<EmailVerificationRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let sent = state
+        .repo
+        .request_email_verification(auth.user_id, &payload.email)
+        .await?;
+    Ok(Json(json!(sent)))
+}
+
+/// Handler pre overenie e-mailu tokenom
+///
+/// # Endpoint
+/// POST /verify-email
+///
+/// # Vstupy
+/// - token: token z /me/email-verification
+///
+/// # Vystupy
+/// - 200 OK: EmailStatus s overenou adresou
+/// - 400 Bad Request: token neexistuje, uz bol pouzity alebo vyprsal
+async fn verify_email_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let status = state.repo.verify_email(&payload.token).await?;
+    Ok(Json(json!(status)))
+}
+
+/// Handler pre prihlasenie pouzivatela
+///
+/// # Endpoint
+/// POST /login
+///
+/// # Vstupy
+/// - username: pouzivatelske meno
+/// - password: heslo
+///
+/// # Vystupy
+/// - 200 OK: uspesne prihlasenie (vracia pouzivatela a jeho ucty)
+/// - 401 Unauthorized: nespravne prihlasovacie udaje
+///
+/// # Poznamka
+/// Kazdy pokus sa zaznamena do auditu prihlaseni (pozri login_with_audit)
+async fn login_user_handler(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    match login_with_audit(&state, &client, &payload).await {
+        Ok(user) => {
+            let list = state
+                .repo
+                .get_account(user.id, &AccountFilter::default())
+                .await
+                .unwrap_or_default();
+            Ok(Json(json!({
+                "user": user,
+                "accounts": list.accounts,
+                "accounts_truncated": list.truncated
+            })))
+        }
+        Err(e) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": e})))),
+    }
+}
+
+/// Overi prihlasovacie udaje a zaznamena pokus do auditu prihlaseni
+///
+/// # Poznamka
+/// Chyba pri zapise auditu sa len zaloguje - prihlasenie kvoli nej nezlyha.
+async fn login_with_audit(
+    state: &AppState,
+    client: &ClientInfo,
+    payload: &LoginRequest,
+) -> Result<PublicUser, String> {
+    let result = state
+        .repo
+        .login_user(&payload.username, &payload.password)
+        .await;
+
+    if let Err(e) = state
+        .repo
+        .record_login_attempt(&payload.username, result.is_ok(), client)
+        .await
+    {
+        tracing::error!("{}Failed to record login attempt: {e}", log_prefix());
+    }
+
+    result
+}
+
+/// Handler pre historiu prihlaseni
+///
+/// # Endpoint
+/// GET /me/login-history?success=&limit=&offset=
+///
+/// # Parametre
+/// - success (query, volitelne): true = len uspesne, false = len neuspesne pokusy
+/// - limit (query, volitelne): pocet pokusov na stranke (1-500, predvolene 100)
+/// - offset (query, volitelne): pocet preskocenych pokusov (predvolene 0)
+///
+/// # Vystupy
+/// - 200 OK: LoginHistoryPage (total a stranka pokusov od najnovsieho)
+/// - 400 Bad Request: neplatny limit alebo offset
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn login_history_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<LoginHistoryQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let page = state.repo.list_login_attempts(auth.user_id, &query).await?;
+    Ok(Json(json!(page)))
+}
+
+/// Handler pre ziskanie historie transakci uctu
+///
+/// # Endpoint
+/// GET /accounts/:id/transactions
+///
+/// # Parametre
+/// - id: UUID uctu
+/// - include_archived (query, volitelne): ak je true, zahrnie aj archivovane transakcie
+/// - expand (query, volitelne): `parties` prida from_username a to_username
+/// - tag (query, volitelne): len transakcie, ktore vlastnik uctu oznacil danym stitkom
+/// - order (query, volitelne): `asc` od najstarsej, `desc` od najnovsej (predvolene);
+///   pri rovnakom case rozhoduje id
+///
+/// # Vystupy
+/// - 200 OK: zoznam vsetkych transakci (odosielatel alebo prijemca), prazdny ak ucet nema transakcie
+/// - 400 Bad Request: neplatny stitok (INVALID_TAG)
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+/// - 500 Internal Server Error: chyba pri ziskavani transakci
+async fn get_transaction_history_handler(
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Query(params): Query<HistoryQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let mut transactions = state
+        .repo
+        .get_transaction_history(
+            account_id,
+            params.include_archived.unwrap_or(false),
+            params.order,
+        )
+        .await?;
+
+    if let Some(tag) = params.tag.as_deref() {
+        state
+            .repo
+            .filter_transactions_by_tag(account_id, tag, &mut transactions)
+            .await?;
+    }
+
+    if params.expand_parties() {
+        state
+            .repo
+            .expand_transaction_parties(&mut transactions)
+            .await?;
+    }
+
+    Ok(Json(json!(transactions)))
+}
+
+/// Handler pre export historie transakcii do OFX
+///
+/// # Endpoint
+/// GET /accounts/:id/transactions.ofx
+///
+/// # Parametre
+/// - id: UUID uctu
+/// - include_archived (query, volitelne): ak je true, zahrnie aj archivovane transakcie
+///
+/// # Vystupy
+/// - 200 OK: OFX 2.2 subor (application/x-ofx) so STMTTRN pre kazdu transakciu
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+async fn export_transactions_ofx_handler(
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Query(params): Query<HistoryQuery>,
+) -> Result<impl IntoResponse, BankError> {
+    let account = state.repo.get_account_by_id(account_id).await?;
+    let transactions = state
+        .repo
+        .get_transaction_history(
+            account_id,
+            params.include_archived.unwrap_or(false),
+            params.order,
+        )
+        .await?;
+
+    let ofx = render_ofx(&account, &transactions, Utc::now());
+    let disposition = format!("attachment; filename=\"{account_id}.ofx\"");
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, OFX_CONTENT_TYPE.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        ofx,
+    ))
+}
+
+/// Handler pre statistiky uctu
+///
+/// # Endpoint
+/// GET /accounts/:id/stats?from=&to=
+///
+/// # Parametre
+/// - id: UUID uctu
+/// - from, to (query, volitelne): casove okno, napr. 2026-01-01T00:00:00
+///
+/// # Vystupy
+/// - 200 OK: AccountStats (prijmy, vydavky, cista zmena, pocet transakcii)
+/// - 404 Not Found: ucet neexistuje
+async fn account_stats_handler(
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Hranice okna su zadane bez casovej zony a chapu sa ako UTC
+    let from = params.from.map(|d| d.and_utc());
+    let to = params.to.map(|d| d.and_utc());
+
+    let stats = state.repo.account_stats(account_id, from, to).await?;
+    Ok(Json(json!(stats)))
+}
+
+/// Handler pre zoznam protistran uctu
+///
+/// # Endpoint
+/// GET /accounts/:id/counterparties
+///
+/// # Hlavicky
+/// - X-API-Key: kluc vlastnika uctu
+///
+/// # Vystupy
+/// - 200 OK: zoznam Counterparty (pocet transakcii, posledna transakcia), najcastejsie prve
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 403 Forbidden: ucet patri inemu pouzivatelovi
+/// - 404 Not Found: ucet neexistuje (ACCOUNT_NOT_FOUND)
+///
+/// # Poznamka
+/// Pri REDACT_ACCOUNT_NUMBERS=true su cisla cudzich uctov maskovane a ich vlastnik
+/// (user_id) sa vynecha.
+async fn list_counterparties_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let mut counterparties = state
+        .repo
+        .list_counterparties(account_id, auth.user_id)
+        .await?;
+
+    if state.redact_account_numbers {
+        Viewer::User(auth.user_id).redact_counterparties(&mut counterparties);
+    }
+    Ok(Json(json!(counterparties)))
+}
+
+/// Handler pre vypis uctu za obdobie
+///
+/// # Endpoint
+/// GET /accounts/:id/statement?from=&to=
+///
+/// # Parametre
+/// - id: UUID uctu
+/// - from, to (query, volitelne): obdobie, napr. 2026-01-01T00:00:00
+///
+/// # Vystupy
+/// - 200 OK: AccountStatement (pociatocny a konecny zostatok, transakcie obdobia)
+/// - 400 Bad Request: from je po to
+/// - 404 Not Found: ucet neexistuje
+async fn account_statement_handler(
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Hranice obdobia su zadane bez casovej zony a chapu sa ako UTC
+    let from = params.from.map(|d| d.and_utc());
+    let to = params.to.map(|d| d.and_utc());
+
+    let statement = state.repo.account_statement(account_id, from, to).await?;
+    Ok(Json(json!(statement)))
+}
+
+/// Handler pre vytvorenie API kluca pomocou prihlasovacich udajov
+///
+/// # Endpoint
+/// POST /api-keys
+///
+/// # Vstupy
+/// - username: pouzivatelske meno
+/// - password: heslo
+///
+/// # Vystupy
+/// - 200 OK: novy kluc (CreatedApiKey) - cely kluc sa zobrazi len raz
+/// - 401 Unauthorized: nespravne prihlasovacie udaje
+async fn create_api_key_handler(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let user = login_with_audit(&state, &client, &payload)
+        .await
+        .map_err(|_| BankError::Unauthorized)?;
+
+    let key = state.repo.create_api_key(user.id).await?;
+    Ok(Json(json!(key)))
+}
+
+/// Handler pre vytvorenie dalsieho API kluca (rotacia)
+///
+/// # Endpoint
+/// POST /me/api-keys
+///
+/// # Hlavicky
+/// - X-API-Key: platny API kluc
+///
+/// # Vystupy
+/// - 200 OK: novy kluc (CreatedApiKey), stary kluc ostava platny do zrusenia
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn rotate_api_key_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let key = state.repo.create_api_key(auth.user_id).await?;
+    Ok(Json(json!(key)))
+}
+
+/// Handler pre zoznam API klucov prihlaseneho pouzivatela
+///
+/// # Endpoint
+/// GET /me/api-keys
+///
+/// # Vystupy
+/// - 200 OK: zoznam ApiKeyInfo (bez samotnych klucov)
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn list_api_keys_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let keys = state.repo.list_api_keys(auth.user_id).await?;
+    Ok(Json(json!(keys)))
+}
+
+/// Handler pre zrusenie API kluca
+///
+/// # Endpoint
+/// DELETE /me/api-keys/:id
+///
+/// # Vystupy
+/// - 200 OK: kluc zruseny
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+/// - 404 Not Found: kluc neexistuje alebo patri inemu pouzivatelovi
+async fn revoke_api_key_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    state.repo.revoke_api_key(auth.user_id, key_id).await?;
+    Ok(Json(json!({"message": "API key revoked"})))
+}
+
+/// Handler pre zapnutie/vypnutie rezimu len na citanie
+///
+/// # Endpoint
+/// POST /admin/read-only
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// - enabled: true = zapisove operacie vratia 503 READ_ONLY
+///
+/// # Vystupy
+/// - 200 OK: novy stav rezimu
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn set_read_only_handler(
+    _admin: AdminGuard,
+    State(state): State<AppState>,
+    Json(payload): Json<ReadOnlyRequest>,
+) -> Json<serde_json::Value> {
+    state.set_read_only(payload.enabled);
+    Json(json!({"read_only": payload.enabled}))
+}
+
+/// Handler pre kontrolu konzistencie zostatkov
+///
+/// # Endpoint
+/// GET /admin/consistency
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vystupy
+/// - 200 OK: {"consistent": bool, "discrepancies": [BalanceDiscrepancy]}
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn consistency_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let discrepancies = state.repo.check_balance_consistency().await?;
+    Ok(Json(json!({
+        "consistent": discrepancies.is_empty(),
+        "discrepancies": discrepancies
+    })))
+}
+
+/// Handler pre suhrnne statistiky systemu
+///
+/// # Endpoint
+/// GET /admin/stats?from=&to=
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// - from, to (volitelne): okno objemu transakcii v UTC, napr. 2026-01-01T00:00:00
+///
+/// # Vystupy
+/// - 200 OK: SystemStats (pocet pouzivatelov a uctov, zostatky a objem transakcii podla meny)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn system_stats_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    // Hranice okna su zadane bez casovej zony a chapu sa ako UTC
+    let from = params.from.map(|d| d.and_utc());
+    let to = params.to.map(|d| d.and_utc());
+
+    let stats = state.repo.system_stats(from, to).await?;
+    Ok(Json(json!(stats)))
+}
+
+/// Handler pre hromadny import pouzivatelov z CSV
+///
+/// # Endpoint
+/// POST /admin/import/users?on_duplicate=skip|abort
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// - telo: CSV s riadkami `username,password` (volitelna hlavicka)
+/// - on_duplicate (query): skip = duplicity sa nahlasia (predvolene), abort = import sa stornuje
+///
+/// # Vystupy
+/// - 200 OK: ImportReport s vysledkom kazdeho riadku
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn import_users_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Query(params): Query<ImportQuery>,
+    csv: String,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let report = state.repo.import_users(&csv, params.on_duplicate).await?;
+    Ok(Json(json!(report)))
+}
+
+/// Handler pre pripisanie uroku na vsetky aktivne ucty
+///
+/// # Endpoint
+/// POST /admin/interest
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// - rate: sadzba za obdobie, napr. "0.0125"
+///
+/// # Vystupy
+/// - 200 OK: InterestRun so suhrnom podla meny
+/// - 400 Bad Request: sadzba nie je kladna alebo ma viac ako 8 desatinnych miest (INVALID_AMOUNT)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn apply_interest_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Json(payload): Json<InterestRequest>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let run = state.repo.apply_interest(payload.rate).await?;
+    Ok(Json(json!(run)))
+}
+
+/// Handler pre kontrolu zaokruhlovacieho uctu
+///
+/// # Endpoint
+/// GET /admin/interest/audit
+///
+/// # Vystupy
+/// - 200 OK: zoznam RoundingAudit podla meny (balanced = knihy sedia)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn rounding_audit_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let audit = state.repo.rounding_audit().await?;
+    Ok(Json(json!(audit)))
+}
+
+/// Handler pre upozornenia na zaporny zostatok
+///
+/// # Endpoint
+/// GET /admin/alerts/negative-balance
+///
+/// # Parametre
+/// - account (query, volitelne): len upozornenia daneho uctu
+/// - limit (query, volitelne): pocet upozorneni (predvolene 100, najviac 500)
+///
+/// # Vystupy
+/// - 200 OK: zoznam NegativeBalanceAlert od najnovsieho
+/// - 400 Bad Request: limit mimo rozsahu (INVALID_BODY)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+async fn negative_balance_alerts_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Query(params): Query<NegativeBalanceAlertQuery>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let alerts = state.repo.list_negative_balance_alerts(&params).await?;
+    Ok(Json(json!(alerts)))
+}
+
+/// Handler pre schvalenie uctu cakajuceho na schvalenie
+///
+/// # Endpoint
+/// POST /admin/accounts/:id/approve
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vystupy
+/// - 200 OK: PubAccount v stave active
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 404 Not Found: ucet neexistuje
+/// - 409 Conflict: ucet necaka na schvalenie (ACCOUNT_NOT_PENDING)
+async fn approve_account_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let account = state.repo.decide_account_approval(account_id, true).await?;
+    Ok(Json(json!(account)))
+}
+
+/// Handler pre storno celej hromadnej davky
+///
+/// # Endpoint
+/// POST /admin/batches/:id/reverse
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vystupy
+/// - 200 OK: BatchReversal so storno transakciami
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 404 Not Found: davka neexistuje (BATCH_NOT_FOUND)
+/// - 409 Conflict: davka uz bola stornovana (BATCH_ALREADY_REVERSED) alebo niektory ucet nie je aktivny
+/// - 422 Unprocessable Entity: niektory prijemca nema dost penazi (INSUFFICIENT_FUNDS),
+///   nestornuje sa nic
+async fn reverse_batch_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Path(batch_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let reversal = state.repo.reverse_batch(batch_id).await?;
+    Ok(Json(json!(reversal)))
+}
+
+/// Handler pre zamietnutie uctu cakajuceho na schvalenie
+///
+/// # Endpoint
+/// POST /admin/accounts/:id/reject
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vystupy
+/// - 200 OK: PubAccount v stave closed
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 404 Not Found: ucet neexistuje
+/// - 409 Conflict: ucet necaka na schvalenie (ACCOUNT_NOT_PENDING)
+async fn reject_account_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let account = state
+        .repo
+        .decide_account_approval(account_id, false)
+        .await?;
+    Ok(Json(json!(account)))
+}
+
+/// Handler pre vyhladavanie transakcii vsetkych uctov
+///
+/// # Endpoint
+/// GET /admin/transactions?account=&from=&to=&min_amount=&kind=&limit=&offset=
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// - account: UUID uctu (odosielatel alebo prijemca)
+/// - from, to: casovy rozsah (RFC 3339, `to` nie je vratane)
+/// - min_amount: minimalna suma
+/// - kind: deposit | withdrawal | transfer
+/// - limit (1-500, predvolene 50), offset (predvolene 0)
+///
+/// # Vystupy
+/// - 200 OK: TransactionPage {total, limit, offset, transactions}
+/// - 400 Bad Request: neplatne query parametre alebo zaporna min_amount (INVALID_AMOUNT)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 422 Unprocessable Entity: limit/offset mimo rozsahu alebo from po to (INVALID_BODY)
+async fn search_transactions_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Query(search): Query<TransactionSearch>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let page = state.repo.search_transactions(&search).await?;
+    Ok(Json(json!(page)))
+}
+
+/// Handler pre opravu zostatkov podla autoritativneho zdroja
+///
+/// # Endpoint
+/// POST /admin/reconcile?force=true
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+/// - X-Admin-Actor (volitelne): kto opravu vykonava, zapise sa do auditu
+///
+/// # Vstupy
+/// JSON pole [{account_id, expected_balance}]; force=true opravi aj zablokovane ucty
+///
+/// # Vystupy
+/// - 200 OK: Reconciliation s rozdielom a vyrovnavacou transakciou pre kazdy ucet
+/// - 400 Bad Request: prazdny zoznam, opakovany ucet alebo neplatny zostatok
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 404 Not Found: niektory ucet neexistuje (neopravi sa ziadny)
+/// - 409 Conflict: ucet je zatvoreny, caka na schvalenie alebo je zablokovany bez force
+async fn reconcile_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Query(params): Query<ReconcileQuery>,
+    Json(entries): Json<Vec<ReconcileEntry>>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let actor = headers
+        .get("x-admin-actor")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    let reconciliation = state
+        .repo
+        .reconcile_balances(&entries, params.force.unwrap_or(false), actor, &client)
+        .await?;
+    Ok(Json(json!(reconciliation)))
+}
+
+/// Handler pre hromadny vklad (napr. vyplata miezd)
+///
+/// # Endpoint
+/// POST /admin/deposits/batch
+///
+/// # Hlavicky
+/// - X-Admin-Token: administratorsky token (ADMIN_TOKEN)
+///
+/// # Vstupy
+/// JSON pole [{account_id, amount}]; ucet sa moze opakovat, kazda polozka je samostatny vklad
+///
+/// # Vystupy
+/// - 200 OK: DepositBatch s transakciou kazdeho vkladu a zostatkami uctov
+/// - 400 Bad Request: niektora suma nie je kladna (INVALID_AMOUNT)
+/// - 403 Forbidden: chybajuci alebo neplatny token
+/// - 404 Not Found: niektory ucet neexistuje (nepripise sa nic)
+/// - 409 Conflict: niektory ucet nie je aktivny (nepripise sa nic)
+/// - 422 Unprocessable Entity: prazdny zoznam, prilis velka suma alebo suma mimo AMOUNT_BOUNDS
+async fn add_money_batch_handler(
+    State(state): State<AppState>,
+    _admin: AdminGuard,
+    Json(items): Json<Vec<BatchDepositItem>>,
+) -> Result<Json<serde_json::Value>, BankError> {
+    let credits = items
+        .into_iter()
+        .map(|item| (item.account_id, item.amount))
+        .collect();
+    let batch = state.repo.add_money_batch(credits).await?;
+    Ok(Json(json!(batch)))
+}
+
+/// Handler pre export udajov pouzivatela (GDPR)
+///
+/// # Endpoint
+/// GET /me/export
+///
+/// # Hlavicky
+/// - X-API-Key: kluc pouzivatela (exportuje sa vzdy len vlastny profil)
+///
+/// # Vystupy
+/// - 200 OK: JSON dokument {exported_at, user, accounts, transactions} posielany po castiach
+/// - 401 Unauthorized: chybajuci alebo neplatny kluc
+async fn export_user_data_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<impl IntoResponse, BankError> {
+    let stream = state.repo.export_user_data(auth.user_id).await?;
+    let disposition = format!("attachment; filename=\"{}.json\"", auth.user_id);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        StreamBody::new(stream),
+    ))
+}
+
+/// Handler pre kontrolu stavu servera
+///
+/// # Endpoint
+/// GET /health
+///
+/// # Vystupy
+/// - 200 OK: {"status": "ok"} - server bezi a databaza odpoveda
+/// - 503 Service Unavailable: {"status": "unavailable"} - databaza neodpoveda
+async fn health_handler(State(state): State<AppState>) -> Response {
+    let result = match &state.pool {
+        Some(pool) => ping_database(pool).await,
+        None => Err(sqlx::Error::PoolClosed),
+    };
+    match result {
+        Ok(()) => Json(json!({"status": "ok"})).into_response(),
+        Err(e) => {
+            tracing::error!("{}Health check failed: {e}", log_prefix());
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({"status": "unavailable"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Handler pre podrobny stav servera
+///
+/// # Endpoint
+/// GET /health/detailed
+///
+/// # Vystupy
+/// - 200 OK: HealthReport (database_latency_ms, pool {size, idle, in_use, max_connections},
+///   connections {total, active, idle})
+/// - 503 Service Unavailable: {"status": "unavailable"} - databaza neodpoveda
+async fn detailed_health_handler(State(state): State<AppState>) -> Response {
+    let result = match &state.pool {
+        Some(pool) => health_report(pool, state.config.db_max_connections).await,
+        None => Err(sqlx::Error::PoolClosed),
+    };
+    match result {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => {
+            tracing::error!("{}Health check failed: {e}", log_prefix());
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({"status": "unavailable"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Handler pre zoznam povolenych hodnot vyctovych poli
+///
+/// # Endpoint
+/// GET /meta/enums
+///
+/// # Vystupy
+/// - 200 OK: {"account_type", "currency", "transaction_kind", "account_status"} - polia retazcov
+///
+/// # Poznamka
+/// Hodnoty sa beru priamo z enumov servera, klient ich nemusi mat natvrdo zapisane.
+/// Endpoint nevyzaduje prihlasenie.
+async fn meta_enums_handler() -> Json<EnumValues> {
+    Json(EnumValues {
+        account_type: &AccountType::ALL,
+        currency: iso_4217_codes(),
+        transaction_kind: &TransactionKind::ALL,
+        account_status: &AccountStatus::ALL,
+    })
+}
+
+/// Handler pre poziadavky na heslo noveho pouzivatela
+///
+/// # Endpoint
+/// GET /meta/password-policy
+///
+/// # Vystupy
+/// - 200 OK: PasswordPolicy - min_length, require_uppercase, require_lowercase,
+///   require_digit, require_symbol
+///
+/// # Poznamka
+/// Vracia tu istu PasswordPolicy, ktoru vynucuje create_user (aj import pouzivatelov),
+/// klient moze heslo overit este pred odoslanim. Endpoint nevyzaduje prihlasenie.
+async fn password_policy_handler(State(state): State<AppState>) -> Json<PasswordPolicy> {
+    Json(state.config.password_policy)
+}
+
+#[cfg(test)]
+mod handler_tests;
@@ -0,0 +1,437 @@
+// middleware.rs
+use crate::amount_format::{AmountFormat, AmountFormatQuery};
+use crate::auth::{API_KEY_HEADER, AuthUser};
+use crate::client_ip::ClientIp;
+use crate::config::Config;
+use crate::error::BankError;
+use crate::locale::{AMOUNT_LOCALE_HEADER, AmountLocale, normalize_amounts};
+use crate::rate_limit::RateLimitSubject;
+use crate::request_id::{RequestId, is_valid_request_id, new_request_id, with_request_id};
+use crate::state::AppState;
+use axum::{
+    body::{Body, Bytes, HttpBody, boxed},
+    extract::{FromRequest, MatchedPath, Query, State},
+    http::{HeaderValue, Method, Request, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{DefaultPredicate, Predicate, SizeAbove},
+};
+
+/// Middleware pre ID poziadavky (sledovanie poziadavky napriec sluzbami)
+///
+/// Prevezme ID z hlavicky REQUEST_ID_HEADER (predvolene `X-Request-Id`), alebo
+/// vygeneruje nove, ak chyba ci je neplatne (pozri is_valid_request_id). ID ulozi
+/// do rozsireni poziadavky (RequestId), pocas spracovania ho pridava do logov
+/// (log_prefix) a chybovych odpovedi a vrati ho v rovnakej hlavicke odpovede.
+///
+/// # Poznamka
+/// Je to najvonkajsia vrstva, takze ID dostanu aj odpovede ostatnych middleware
+/// (napr. 504 TIMEOUT, 429 RATE_LIMITED).
+pub async fn request_id<B>(
+    State(state): State<AppState>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let header = &state.config.request_id_header;
+    let id = request
+        .headers()
+        .get(header)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| is_valid_request_id(v))
+        .map(str::to_string)
+        .unwrap_or_else(new_request_id);
+
+    request.extensions_mut().insert(RequestId(id.clone()));
+    let mut response = with_request_id(id.clone(), next.run(request)).await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(header.clone(), value);
+    }
+    response
+}
+
+/// Middleware pre rezim udrzby (len na citanie)
+///
+/// Ak je rezim zapnuty, zapisove poziadavky vratia 503 READ_ONLY.
+/// Citanie (GET/HEAD/OPTIONS), prihlasenie a administratorske endpointy
+/// (vratane vypnutia rezimu) funguju dalej.
+pub async fn read_only_guard<B>(
+    State(state): State<AppState>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let is_read = matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    );
+    let path = request.uri().path();
+    let is_exempt = path == "/login" || path.starts_with("/admin/");
+
+    if state.is_read_only() && !is_read && !is_exempt {
+        return BankError::ReadOnly.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Middleware pre limity poziadaviek (token bucket)
+///
+/// Limit trasy (pozri RateLimits) sa uplatnuje zvlast pre adresu klienta a zvlast
+/// pre pouzivatela s platnym `X-API-Key` - poziadavka musi prejst oboma. Po
+/// prekroceni vrati 429 RATE_LIMITED s hlavickou `Retry-After`.
+///
+/// # Poznamka
+/// Pouziva sa cez `route_layer`, aby bola znama sablona trasy (MatchedPath).
+/// Neplatny API kluc sa tu neodmieta - 401 vrati az handler. Overeny pouzivatel
+/// sa ulozi do extensions poziadavky, extractor AuthUser ho prevezme bez
+/// dalsieho dotazu do databazy.
+pub async fn rate_limit<B>(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let Some(path) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+    else {
+        return next.run(request).await;
+    };
+    let method = request.method().clone();
+    let Some(limit) = state.rate_limiter.limit_for(&method, &path) else {
+        return next.run(request).await;
+    };
+
+    let mut subjects: Vec<RateLimitSubject> = ip.map(RateLimitSubject::Ip).into_iter().collect();
+    if let Some(key) = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        && let Ok(user_id) = state.repo.authenticate_api_key(key).await
+    {
+        subjects.push(RateLimitSubject::User(user_id));
+        request.extensions_mut().insert(AuthUser { user_id });
+    }
+
+    for subject in subjects {
+        if let Err(retry_after_secs) = state.rate_limiter.check(&method, &path, limit, subject) {
+            return BankError::RateLimited { retry_after_secs }.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Middleware pre casovy limit spracovania poziadavky
+///
+/// Ak handler nedobehne do AppState::request_timeout (napr. zaseknuty dotaz
+/// cakajuci na zamok), klient dostane 504 TIMEOUT namiesto visiaceho spojenia.
+///
+/// # Poznamka
+/// Po vyprsani sa future handlera zahodi. Otvorena databazova transakcia
+/// sa tym nepotvrdi - sqlx pri drop-e spusti ROLLBACK, takze ziadne
+/// ciastocne zmeny (napr. odpisanie bez pripisania) nezostanu.
+pub async fn request_timeout<B>(
+    State(state): State<AppState>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    match tokio::time::timeout(state.request_timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => BankError::Timeout.into_response(),
+    }
+}
+
+/// Middleware pre lokalizovane sumy (napr. "1.234,56")
+///
+/// Ak poziadavka obsahuje hlavicku `X-Amount-Locale` (napr. "sk", "de", "en"),
+/// textove polia `amount` v JSON tele sa prevedu na kanonicky zapis s bodkou
+/// este pred handlerom. Bez hlavicky sa telo nemeni a plati prisny format
+/// s desatinnou bodkou.
+///
+/// # Chyby
+/// - 400 INVALID_AMOUNT: neznamy locale alebo suma nezodpoveda formatu
+pub async fn localized_amounts(request: Request<Body>, next: Next<Body>) -> Response {
+    let Some(hint) = request.headers().get(AMOUNT_LOCALE_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(locale) = hint.to_str().ok().and_then(AmountLocale::from_header) else {
+        return BankError::InvalidAmount.into_response();
+    };
+
+    let (mut parts, body) = request.into_parts();
+    let bytes = match Bytes::from_request(Request::new(body), &()).await {
+        Ok(bytes) => bytes,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    // Telo, ktore nie je JSON, prepusti bez zmeny - chybu vrati extractor handlera
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(mut json) => {
+            if let Err(e) = normalize_amounts(&mut json, locale) {
+                return e.into_response();
+            }
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(json.to_string())
+        }
+        Err(_) => Body::from(bytes),
+    };
+
+    next.run(Request::from_parts(parts, body)).await
+}
+
+/// Middleware pre format sum v odpovedi
+///
+/// Klient si moze zvolit tvar sum (retazec/cislo) a pevny pocet desatinnych miest
+/// query parametrami `amount_format` a `amount_scale` alebo parametrami `amounts`
+/// a `scale` v hlavicke Accept (`application/json; amounts=number; scale=2`).
+/// Bez nich sa odpoved nemeni (sumy ako retazce v ulozenej presnosti).
+///
+/// # Poznamka
+/// Prepisuju sa len JSON odpovede so znamou dlzkou - prudove odpovede
+/// (napr. GDPR export) sa posielaju bez zmeny.
+///
+/// # Chyby
+/// - 400 INVALID_AMOUNT_FORMAT: neznamy tvar alebo neplatny pocet desatinnych miest
+pub async fn formatted_amounts(request: Request<Body>, next: Next<Body>) -> Response {
+    let query = Query::<AmountFormatQuery>::try_from_uri(request.uri())
+        .map(|Query(query)| query)
+        .unwrap_or_default();
+    let accept = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok());
+
+    let format = match AmountFormat::from_request(&query, accept) {
+        Ok(Some(format)) => format,
+        Ok(None) => return next.run(request).await,
+        Err(e) => return e.into_response(),
+    };
+
+    let response = next.run(request).await;
+    rewrite_json_body(response, |mut json| {
+        format.apply(&mut json);
+        Some(json.to_string())
+    })
+    .await
+}
+
+/// Middleware pre odsadeny JSON (ladenie)
+///
+/// Ak je v konfiguracii zapnute JSON_PRETTY, JSON odpovede so znamou dlzkou
+/// (vratane chyb) sa preformatuju s odsadenim. Predvolene sa odpoved nemeni
+/// a posiela sa kompaktny JSON.
+pub async fn pretty_json(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let response = next.run(request).await;
+    if !state.config.json_pretty {
+        return response;
+    }
+
+    rewrite_json_body(response, |json| serde_json::to_string_pretty(&json).ok()).await
+}
+
+/// Prepise JSON telo odpovede (spolocna cast formatted_amounts a pretty_json)
+///
+/// `rewrite` dostane nacitane telo a vrati nove, alebo None ak sa ma poslat povodne.
+/// Odpovede, ktore nie su JSON alebo nemaju znamu dlzku (prudove), a telo, ktore nie je
+/// platny JSON, sa posielaju bez zmeny. Poradie klucov objektov sa zachova
+/// (serde_json s preserve_order).
+async fn rewrite_json_body(
+    response: Response,
+    rewrite: impl FnOnce(serde_json::Value) -> Option<String>,
+) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if !is_json || response.body().size_hint().exact().is_none() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match Bytes::from_request(Request::new(body), &()).await {
+        Ok(bytes) => bytes,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(rewrite)
+    {
+        Some(rewritten) => {
+            parts
+                .headers
+                .insert(header::CONTENT_LENGTH, HeaderValue::from(rewritten.len()));
+            rewritten.into()
+        }
+        None => bytes,
+    };
+
+    Response::from_parts(parts, boxed(Body::from(body)))
+}
+
+/// Vytvori vrstvu pre kompresiu odpovedi (gzip/brotli)
+///
+/// Odpoved sa komprimuje len ak klient posle Accept-Encoding a telo je vacsie
+/// ako COMPRESSION_MIN_SIZE bajtov (predvolene 1024) - male odpovede by sa
+/// kompresiou zbytocne zvacsili.
+pub fn compression_layer(config: &Config) -> CompressionLayer<impl Predicate + use<>> {
+    CompressionLayer::new()
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(config.compression_min_size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        http::StatusCode,
+        middleware::from_fn_with_state,
+        routing::{get, post},
+    };
+    use sqlx::postgres::PgPoolOptions;
+    use tower::ServiceExt;
+
+    /// Stav s predvolenou konfiguraciou - databaza sa nepouziva (lenivy pool)
+    fn state() -> AppState {
+        let config = Config::from_lookup(|name| {
+            (name == "DATABASE_URL").then(|| "postgres://localhost/unused".to_string())
+        })
+        .unwrap();
+        let pool = PgPoolOptions::new()
+            .connect_lazy(&config.database_url)
+            .unwrap();
+        AppState::new(config, pool)
+    }
+
+    /// Router s middleware request_id
+    fn app() -> Router {
+        let state = state();
+
+        Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route(
+                "/missing",
+                get(|| async { BankError::AccountNotFound.into_response() }),
+            )
+            .layer(from_fn_with_state(state.clone(), request_id))
+            .with_state(state)
+    }
+
+    async fn send(path: &str, request_id: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(path);
+        if let Some(id) = request_id {
+            request = request.header("x-request-id", id);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// Telo odpovede ako JSON
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = Bytes::from_request(Request::new(response.into_body()), &())
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn echoes_provided_request_id() {
+        let response = send("/ok", Some("trace-123")).await;
+        assert_eq!(response.headers()["x-request-id"], "trace-123");
+    }
+
+    #[tokio::test]
+    async fn generates_request_id_when_absent_or_invalid() {
+        for provided in [None, Some("not valid!")] {
+            let response = send("/ok", provided).await;
+            let id = response.headers()["x-request-id"].to_str().unwrap();
+            assert!(is_valid_request_id(id));
+            assert_ne!(Some(id), provided);
+        }
+    }
+
+    #[tokio::test]
+    async fn error_body_carries_request_id() {
+        let response = send("/missing", Some("trace-456")).await;
+        let body = json_body(response).await;
+        assert_eq!(body["error"]["request_id"], "trace-456");
+    }
+
+    #[tokio::test]
+    async fn writes_in_read_only_mode_are_503() {
+        let state = state();
+        state.set_read_only(true);
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }).post(|| async { "written" }))
+            .route("/admin/read-only", post(|| async { "toggled" }))
+            .layer(from_fn_with_state(state.clone(), read_only_guard))
+            .with_state(state);
+        let request = |method: Method, path: &str| {
+            Request::builder()
+                .method(method)
+                .uri(path)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let write = app
+            .clone()
+            .oneshot(request(Method::POST, "/ok"))
+            .await
+            .unwrap();
+        assert_eq!(write.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json_body(write).await["error"]["code"], "READ_ONLY");
+
+        // Citanie a administracia funguju dalej
+        let read = app
+            .clone()
+            .oneshot(request(Method::GET, "/ok"))
+            .await
+            .unwrap();
+        assert_eq!(read.status(), StatusCode::OK);
+        let admin = app
+            .oneshot(request(Method::POST, "/admin/read-only"))
+            .await
+            .unwrap();
+        assert_eq!(admin.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn large_bodies_are_gzip_encoded() {
+        let config = state().config.clone();
+        let app = Router::new()
+            .route("/large", get(|| async { "a".repeat(4096) }))
+            .route("/small", get(|| async { "a".repeat(100) }))
+            .layer(compression_layer(&config));
+        let request = |path: &str| {
+            Request::builder()
+                .uri(path)
+                .header(header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let large = app.clone().oneshot(request("/large")).await.unwrap();
+        assert_eq!(large.headers()[header::CONTENT_ENCODING], "gzip");
+        let body = Bytes::from_request(Request::new(large.into_body()), &())
+            .await
+            .unwrap();
+        assert!(body.starts_with(&[0x1f, 0x8b]));
+        assert!(body.len() < 4096);
+
+        // Pod COMPRESSION_MIN_SIZE sa telo posle bez kompresie
+        let small = app.oneshot(request("/small")).await.unwrap();
+        assert!(small.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+}
@@ -1,6 +1,6 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::types::chrono::NaiveDateTime;
+use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 use uuid::Uuid;
 
 /// Verejne udaje pouzivatela (bez hesla)
@@ -13,16 +13,152 @@ pub struct PublicUser {
     pub username: String,
 }
 
+/// Stav bankoveho uctu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum AccountStatus {
+    /// Bezny aktivny ucet
+    Active,
+    /// Docasne zablokovany ucet
+    Frozen,
+    /// Zatvoreny ucet (nemoze byt znovu otvoreny)
+    Closed,
+    /// Ucet caka na schvalenie administratorom
+    Pending,
+}
+
+impl AccountStatus {
+    /// Vsetky stavy uctu v poradi deklaracie
+    pub const ALL: [AccountStatus; 4] = [
+        AccountStatus::Active,
+        AccountStatus::Frozen,
+        AccountStatus::Closed,
+        AccountStatus::Pending,
+    ];
+}
+
+/// Typ bankoveho uctu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum AccountType {
+    /// Bezny ucet
+    #[default]
+    Checking,
+    /// Sporiaci ucet
+    Savings,
+    /// Podnikatelsky ucet
+    Business,
+}
+
+impl AccountType {
+    /// Vsetky typy uctu v poradi deklaracie
+    pub const ALL: [AccountType; 3] = [
+        AccountType::Checking,
+        AccountType::Savings,
+        AccountType::Business,
+    ];
+
+    /// Nazov typu tak, ako sa uklada v databaze a zadava v konfiguracii
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccountType::Checking => "checking",
+            AccountType::Savings => "savings",
+            AccountType::Business => "business",
+        }
+    }
+}
+
 /// Verejne udaje bankoveho uctu
 /// Obsahuje informacie o zostatku a vlastnikovi uctu
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PubAccount {
     /// Unikatny identifikator uctu
     pub id: Uuid,
+    /// Citatelne cislo uctu (None pri uctoch zalozenych pred zavedenim cisel)
+    pub account_number: Option<String>,
     /// Identifikator vlastnika uctu (vzah k PublicUser)
     pub user_id: Uuid,
     /// Zostatok na ucte (presne desatinne cislo)
     pub balance: Decimal,
+    /// Mena uctu (ISO 4217, napr. EUR)
+    pub currency: String,
+    /// Typ uctu
+    #[serde(rename = "type")]
+    pub account_type: AccountType,
+    /// Stav uctu
+    pub status: AccountStatus,
+    /// Zostatok prepocitany do meny zobrazenia (len pri `?display_currency=`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_balance: Option<DisplayBalance>,
+}
+
+/// Zostatok prepocitany do inej meny - len na zobrazenie, ulozeny zostatok sa nemeni
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayBalance {
+    /// Mena zobrazenia (ISO 4217)
+    pub currency: String,
+    /// Prepocitany zostatok zaokruhleny na centy
+    pub amount: Decimal,
+    /// Pouzity kurz (jednotky meny zobrazenia za jednotku meny uctu)
+    pub rate: Decimal,
+}
+
+/// Parametre dotazu zoznamu uctov (filtre a mena zobrazenia)
+#[derive(Debug, Default, Deserialize)]
+pub struct AccountListQuery {
+    #[serde(flatten)]
+    pub filter: AccountFilter,
+    /// Mena, do ktorej sa prepocitaju zostatky (napr. USD)
+    pub display_currency: Option<String>,
+    /// false = vynechat ucty s nulovym zostatkom (pozri AccountFilter::include_empty)
+    pub include_empty: Option<bool>,
+}
+
+/// Zoznam uctov pouzivatela s priznakom orezania
+#[derive(Debug, Default, Serialize)]
+pub struct AccountList {
+    /// Ucty pouzivatela (najviac MAX_ACCOUNTS_LISTED)
+    pub accounts: Vec<PubAccount>,
+    /// true, ak ma pouzivatel viac uctov, nez sa vratilo
+    pub truncated: bool,
+}
+
+/// Filtre zoznamu uctov (`?status=active&type=savings&currency=EUR`)
+#[derive(Debug, Default, Deserialize)]
+pub struct AccountFilter {
+    /// Len ucty v danom stave
+    pub status: Option<AccountStatus>,
+    /// Len ucty daneho typu
+    #[serde(rename = "type")]
+    pub account_type: Option<AccountType>,
+    /// Len ucty v danej mene (ISO 4217, na velkosti pismen nezalezi)
+    pub currency: Option<String>,
+    /// false = vynechat ucty s nulovym zostatkom, teda aj zatvorene (predvolene true)
+    ///
+    /// Z query sa cita cez AccountListQuery::include_empty - vo flatten strukture
+    /// serde nevie previest text "false" na bool.
+    #[serde(skip)]
+    pub include_empty: Option<bool>,
+}
+
+/// Poziadavka na hromadne ziskanie uctov
+#[derive(Debug, Deserialize)]
+pub struct AccountBatchRequest {
+    /// Identifikatory pozadovanych uctov
+    pub ids: Vec<Uuid>,
+}
+
+/// Vysledok hromadneho ziskania uctov
+#[derive(Debug, Default, Serialize)]
+pub struct AccountBatch {
+    /// Ucty, ktore patria volajucemu
+    pub accounts: Vec<PubAccount>,
+    /// Identifikatory neexistujucich uctov
+    pub not_found: Vec<Uuid>,
+    /// Identifikatory uctov inych pouzivatelov
+    pub forbidden: Vec<Uuid>,
 }
 
 /// Poziadavka na vytvorenie noveho bankoveho uctu
@@ -30,6 +166,13 @@ pub struct PubAccount {
 pub struct CreateAccountRequest {
     /// Identifikator pouzivatela, pre ktoreho sa ma vytvorit ucet
     pub user_id: Uuid,
+    /// Mena uctu (ISO 4217), predvolene DEFAULT_CURRENCY (EUR)
+    pub currency: Option<String>,
+    /// Typ uctu, predvolene checking
+    #[serde(rename = "type", default)]
+    pub account_type: AccountType,
+    /// Pociatocny zostatok (nezaporny, najviac 2 desatinne miesta), predvolene 0
+    pub initial_deposit: Option<Decimal>,
 }
 
 /// Poziadavka na registraciu noveho pouzivatela
@@ -56,14 +199,118 @@ pub struct LoginRequest {
 pub struct Transaction {
     /// Unikatny identifikator transakcie
     pub id: Uuid,
-    /// Identifikator uctu odosielatela
-    pub from_account: Uuid,
-    /// Identifikator uctu prijemcu
-    pub to_account: Uuid,
+    /// Citatelne referencne cislo prevodu (None pri vklade)
+    pub reference: Option<String>,
+    /// Identifikator uctu odosielatela (None pri vklade alebo ak bol ucet zmazany)
+    pub from_account: Option<Uuid>,
+    /// Identifikator uctu prijemcu (None ak bol ucet zmazany)
+    pub to_account: Option<Uuid>,
     /// Suma prevodu (presne desatinne cislo)
     pub amount: Decimal,
-    /// Cas vytvorenia transakcie
-    pub created_at: Option<NaiveDateTime>,
+    /// Mena sumy - mena uctu odosielatela, pri vklade mena uctu prijemcu
+    /// (None pri starych transakciach zmazanych uctov)
+    pub currency: Option<String>,
+    /// Suma pripisana prijemcovi v mene jeho uctu - len pri prevode na ucet v inej mene
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credited_amount: Option<Decimal>,
+    /// Mena pripisanej sumy (mena uctu prijemcu) - len pri prevode na ucet v inej mene
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credited_currency: Option<String>,
+    /// Pouzity kurz (mena odosielatela -> mena prijemcu) - len pri prepocte kurzom
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<Decimal>,
+    /// Cas vytvorenia transakcie (UTC, serializovany ako RFC 3339 so `Z`)
+    pub created_at: Option<DateTime<Utc>>,
+    /// Sukromna poznamka strany, ktora transakciu ziskava (odosielatel alebo prijemca)
+    pub note: Option<String>,
+    /// Mena vlastnikov uctov, len pri `?expand=parties` (inak sa neserializuju)
+    #[serde(flatten)]
+    pub parties: Option<TransactionParties>,
+    /// Rozdelenie sumy do kategorii (len ak bolo zadane pri prevode)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub splits: Vec<TransactionSplit>,
+}
+
+/// Cast sumy prevodu priradena ku kategorii
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionSplit {
+    /// Kategoria (rovnake pravidla ako stitok, napr. "rent")
+    pub category: String,
+    /// Suma kategorie (kladna, spolu musia dat sumu prevodu)
+    pub amount: Decimal,
+}
+
+/// Mena vlastnikov uctov transakcie
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionParties {
+    /// Meno vlastnika uctu odosielatela (None pri vklade alebo ak bol ucet zmazany)
+    pub from_username: Option<String>,
+    /// Meno vlastnika uctu prijemcu (None ak bol ucet zmazany)
+    pub to_username: Option<String>,
+}
+
+/// Druh transakcie podla jej stran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionKind {
+    /// Vklad (bez odosielatela a referencneho cisla), napr. addmoney alebo urok
+    Deposit,
+    /// Odpis (bez referencneho cisla, s odosielatelom), napr. oprava zostatku
+    Withdrawal,
+    /// Prevod medzi uctami (s referencnym cislom)
+    Transfer,
+    /// Otvorenie uctu (pociatocny zostatok, aj nulovy) - prvy zaznam uctu
+    Opening,
+}
+
+impl TransactionKind {
+    /// Vsetky druhy transakcii v poradi deklaracie
+    pub const ALL: [TransactionKind; 4] = [
+        TransactionKind::Deposit,
+        TransactionKind::Withdrawal,
+        TransactionKind::Transfer,
+        TransactionKind::Opening,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionKind::Deposit => "deposit",
+            TransactionKind::Withdrawal => "withdrawal",
+            TransactionKind::Transfer => "transfer",
+            TransactionKind::Opening => "opening",
+        }
+    }
+}
+
+/// Filtre a strankovanie vyhladavania transakcii administratorom
+/// (`?account=...&from=...&to=...&min_amount=100&kind=transfer&limit=50&offset=0`)
+#[derive(Debug, Default, Deserialize)]
+pub struct TransactionSearch {
+    /// Len transakcie, v ktorych je ucet odosielatelom alebo prijemcom
+    pub account: Option<Uuid>,
+    /// Len transakcie vytvorene od tohto casu (vratane)
+    pub from: Option<DateTime<Utc>>,
+    /// Len transakcie vytvorene pred tymto casom
+    pub to: Option<DateTime<Utc>>,
+    /// Len transakcie so sumou aspon takouto
+    pub min_amount: Option<Decimal>,
+    /// Len transakcie daneho druhu
+    pub kind: Option<TransactionKind>,
+    /// Pocet transakcii na stranke (predvolene 50)
+    pub limit: Option<i64>,
+    /// Pocet preskocenych transakcii (predvolene 0)
+    pub offset: Option<i64>,
+}
+
+/// Stranka vysledkov vyhladavania transakcii
+#[derive(Debug, Serialize)]
+pub struct TransactionPage {
+    /// Pocet vsetkych transakcii vyhovujucich filtrom (bez ohladu na strankovanie)
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// Transakcie zoradene od najnovsich (bez sukromnych poznamok)
+    pub transactions: Vec<Transaction>,
 }
 
 /// Poziadavka na vytvorenie transakcie (prevod penazi)
@@ -75,6 +322,251 @@ pub struct TransactionRequest {
     pub to_account: Uuid,
     /// Suma prevodu (musi byt kladna)
     pub amount: Decimal,
+    /// Volitelna poznamka viditelna len odosielatelovi
+    pub sender_note: Option<String>,
+    /// Volitelna poznamka viditelna len prijemcovi
+    pub receiver_note: Option<String>,
+    /// Volitelny kluc pre bezpecne opakovanie poziadavky
+    pub idempotency_key: Option<String>,
+    /// Volitelne rozdelenie sumy do kategorii (sucet sa musi rovnat sume)
+    #[serde(default)]
+    pub splits: Vec<TransactionSplit>,
+    /// PIN uctu odosielatela - povinny od TRANSFER_PIN_THRESHOLD, ak ma ucet PIN
+    pub pin: Option<String>,
+}
+
+/// Query parametre prevodu (`?expand=accounts`)
+#[derive(Debug, Default, Deserialize)]
+pub struct TransferQuery {
+    /// Ciarkou oddelene rozsirenia odpovede, podporovane: `accounts`
+    pub expand: Option<String>,
+}
+
+impl TransferQuery {
+    /// Ci ma odpoved obsahovat aktualne ucty odosielatela (`expand=accounts`)
+    pub fn expand_accounts(&self) -> bool {
+        self.expand
+            .as_deref()
+            .is_some_and(|e| e.split(',').any(|part| part.trim() == "accounts"))
+    }
+}
+
+/// Prevod spolu so zostatkami vsetkych uctov odosielatela po prevode
+#[derive(Debug, Serialize)]
+pub struct TransferWithAccounts {
+    pub transaction: Transaction,
+    /// Ucty vlastnika uctu odosielatela (`accounts`, `truncated` ako pri GET /accounts/:id)
+    #[serde(flatten)]
+    pub accounts: AccountList,
+}
+
+/// Poziadavka na prevod z viacerych uctov
+#[derive(Debug, Deserialize)]
+pub struct MultiSourceTransferRequest {
+    /// Ucty odosielatela v poradi, v akom sa z nich ma cerpat
+    pub sources: Vec<Uuid>,
+    /// Identifikator uctu prijemcu
+    pub to_account: Uuid,
+    /// Celkova suma prevodu (musi byt kladna)
+    pub amount: Decimal,
+    /// Volitelna poznamka viditelna len odosielatelovi
+    pub sender_note: Option<String>,
+    /// Volitelna poznamka viditelna len prijemcovi
+    pub receiver_note: Option<String>,
+    /// PIN zdrojovych uctov - povinny od TRANSFER_PIN_THRESHOLD (celkova suma) pre zdroje s PIN-om
+    pub pin: Option<String>,
+}
+
+/// Poziadavka na nahlad prevodu
+#[derive(Debug, Deserialize)]
+pub struct TransferPreviewRequest {
+    /// Identifikator uctu odosielatela (musi patrit prihlasenemu pouzivatelovi)
+    pub from_account: Uuid,
+    /// Identifikator uctu prijemcu
+    pub to_account: Uuid,
+    /// Suma prevodu
+    pub amount: Decimal,
+}
+
+/// Nahlad prevodu - co by sa stalo, keby sa prevod vykonal teraz
+#[derive(Debug, Serialize)]
+pub struct TransferPreview {
+    pub from_account: Uuid,
+    pub to_account: Uuid,
+    /// Suma prevodu
+    pub amount: Decimal,
+    /// Mena uctu odosielatela
+    pub from_currency: String,
+    /// Mena uctu prijemcu (None ak ucet neexistuje)
+    pub to_currency: Option<String>,
+    /// Poplatok - o kolko sa zostatok odosielatela znizi navyse oproti sume
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Decimal>,
+    /// Suma pripisana prijemcovi, len ak sa meny uctov lisia
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_amount: Option<Decimal>,
+    /// Zostatok odosielatela po prevode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resulting_balance: Option<Decimal>,
+    /// Ci by prevod prebehol uspesne
+    pub would_succeed: bool,
+    /// Dovod neuspechu (rovnaky kod ako pri skutocnom prevode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<TransferPreviewError>,
+}
+
+/// Chyba, ktorou by skoncil skutocny prevod
+#[derive(Debug, Serialize)]
+pub struct TransferPreviewError {
+    pub code: String,
+    pub message: String,
+}
+
+/// Vysledok prevodu z viacerych uctov
+#[derive(Debug, Serialize)]
+pub struct MultiSourceTransfer {
+    /// Spolocny identifikator ciastkovych transakcii
+    pub transfer_group_id: Uuid,
+    pub to_account: Uuid,
+    /// Celkova prevedena suma
+    pub amount: Decimal,
+    /// Ciastkove transakcie v poradi cerpania (ucty bez potrebneho cerpania chybaju)
+    pub transactions: Vec<Transaction>,
+}
+
+/// Jeden prevod hromadnej davky
+#[derive(Debug, Deserialize)]
+pub struct BatchTransferItem {
+    /// Identifikator uctu prijemcu
+    pub to_account: Uuid,
+    /// Suma prevodu (musi byt kladna)
+    pub amount: Decimal,
+    /// Volitelna poznamka viditelna len odosielatelovi
+    pub sender_note: Option<String>,
+    /// Volitelna poznamka viditelna len prijemcovi
+    pub receiver_note: Option<String>,
+}
+
+/// Poziadavka na hromadny prevod z jedneho uctu viacerym prijemcom
+#[derive(Debug, Deserialize)]
+pub struct BatchTransferRequest {
+    /// Identifikator uctu odosielatela (musi patrit prihlasenemu pouzivatelovi)
+    pub from_account: Uuid,
+    /// Prevody davky v poradi vykonania
+    pub transfers: Vec<BatchTransferItem>,
+    /// PIN uctu odosielatela - povinny od TRANSFER_PIN_THRESHOLD (sucet davky), ak ma ucet PIN
+    pub pin: Option<String>,
+}
+
+/// Vysledok hromadneho prevodu
+#[derive(Debug, Serialize)]
+pub struct TransferBatch {
+    /// Spolocny identifikator transakcii davky
+    pub batch_id: Uuid,
+    pub from_account: Uuid,
+    /// Celkova prevedena suma
+    pub total: Decimal,
+    /// Transakcie davky v poradi vykonania
+    pub transactions: Vec<Transaction>,
+}
+
+/// Jeden vklad hromadneho vkladu (napr. vyplata mzdy)
+#[derive(Debug, Deserialize)]
+pub struct BatchDepositItem {
+    /// Identifikator uctu, na ktory sa vklada
+    pub account_id: Uuid,
+    /// Suma vkladu (musi byt kladna)
+    pub amount: Decimal,
+}
+
+/// Zaznam jedneho vkladu hromadneho vkladu
+#[derive(Debug, Serialize)]
+pub struct BatchDeposit {
+    /// Transakcia vkladu (bez odosielatela, rovnako ako POST /addmoney)
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub amount: Decimal,
+}
+
+/// Vysledok hromadneho vkladu
+#[derive(Debug, Serialize)]
+pub struct DepositBatch {
+    /// Vklady v poradi poziadavky
+    pub deposits: Vec<BatchDeposit>,
+    /// Ucty po pripisani vsetkych vkladov, kazdy raz v poradi prveho vyskytu
+    pub accounts: Vec<PubAccount>,
+}
+
+/// Vysledok stornovania hromadnej davky
+#[derive(Debug, Serialize)]
+pub struct BatchReversal {
+    pub batch_id: Uuid,
+    pub reversed_at: DateTime<Utc>,
+    /// Celkova vratena suma
+    pub total: Decimal,
+    /// Storno transakcie (od prijemcov spat odosielatelovi)
+    pub transactions: Vec<Transaction>,
+}
+
+/// Poziadavka na zatvorenie uctu
+#[derive(Debug, Default, Deserialize)]
+pub struct CloseAccountRequest {
+    /// Ucet, na ktory sa prevedie zostatok (povinny ak ma ucet zostatok)
+    pub sweep_to: Option<Uuid>,
+    /// Zrusit nepotvrdene prevody a trvale prikazy uctu namiesto odmietnutia
+    #[serde(default)]
+    pub cancel_scheduled: bool,
+}
+
+/// Poziadavka na zlucenie uctu do ineho uctu
+#[derive(Debug, Deserialize)]
+pub struct MergeAccountRequest {
+    /// Ucet, na ktory prejde zostatok (rovnaky vlastnik a mena)
+    pub target_account: Uuid,
+    /// Zrusit nepotvrdene prevody a trvale prikazy zlucovaneho uctu namiesto odmietnutia
+    #[serde(default)]
+    pub cancel_scheduled: bool,
+}
+
+/// Parametre zmazania pouzivatela (`?cancel_scheduled=true`)
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteUserQuery {
+    /// Zrusit nepotvrdene prevody a trvale prikazy uctov namiesto odmietnutia
+    #[serde(default)]
+    pub cancel_scheduled: bool,
+}
+
+/// Vysledok zlucenia uctov
+#[derive(Debug, Serialize)]
+pub struct AccountMerge {
+    /// Zluceny ucet (po zluceni zatvoreny, `merged_into` = cielovy ucet)
+    pub source_account: Uuid,
+    /// Cielovy ucet po zluceni
+    pub target: PubAccount,
+    pub merged_at: DateTime<Utc>,
+    /// Prevod zostatku na cielovy ucet (None ak bol zostatok nulovy)
+    pub transfer: Option<Transaction>,
+}
+
+/// Zaverecny vypis zatvoreneho uctu
+#[derive(Debug, Serialize)]
+pub struct FinalStatement {
+    /// Identifikator uctu
+    pub account_id: Uuid,
+    /// Mena uctu (ISO 4217)
+    pub currency: String,
+    /// Cas zatvorenia
+    pub closed_at: DateTime<Utc>,
+    /// Sucet vsetkych prijmov a vkladov
+    pub total_credits: Decimal,
+    /// Sucet vsetkych odoslanych platieb (vratane prevodu zostatku)
+    pub total_debits: Decimal,
+    /// Zostatok po zatvoreni (vzdy 0)
+    pub closing_balance: Decimal,
+    /// Prevod zostatku na iny ucet (None ak bol zostatok nulovy)
+    pub sweep: Option<Transaction>,
+    /// Vsetky transakcie uctu vratane archivovanych, najnovsie prve
+    pub transactions: Vec<Transaction>,
 }
 
 /// Poziadavka na pridanie penazi na ucet
@@ -85,4 +577,703 @@ pub struct AddMoneyRequest {
     pub account_id: Uuid,
     /// Suma, ktora sa ma pridat (musi byt kladna)
     pub amount: Decimal,
+    /// Volitelny kluc idempotencie - opakovany vklad s rovnakym klucom sa nepripise znovu
+    pub idempotency_key: Option<String>,
+}
+
+/// Poziadavka na pridanie stitku k transakcii
+#[derive(Debug, Deserialize)]
+pub struct TagRequest {
+    /// Stitok (kategoria), napr. "rent"
+    pub tag: String,
+}
+
+/// Stitky transakcie jedneho pouzivatela
+#[derive(Debug, Serialize)]
+pub struct TransactionTags {
+    /// Identifikator transakcie
+    pub transaction_id: Uuid,
+    /// Stitky zoradene abecedne
+    pub tags: Vec<String>,
+}
+
+/// Smer zoradenia podla casu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Od najstarsieho
+    Asc,
+    /// Od najnovsieho
+    #[default]
+    Desc,
+}
+
+/// Parametre dotazu pre historiu transakcii
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryQuery {
+    /// Zahrnut aj archivovane transakcie (predvolene false)
+    pub include_archived: Option<bool>,
+    /// Zoradenie podla casu vytvorenia (`asc` alebo `desc`, predvolene desc)
+    #[serde(default)]
+    pub order: SortOrder,
+    /// Ciarkou oddelene rozsirenia odpovede, podporovane: `parties`
+    pub expand: Option<String>,
+    /// Len transakcie so stitkom vlastnika uctu
+    pub tag: Option<String>,
+}
+
+impl HistoryQuery {
+    /// Ci ma odpoved obsahovat mena vlastnikov uctov (`expand=parties`)
+    pub fn expand_parties(&self) -> bool {
+        self.expand
+            .as_deref()
+            .is_some_and(|e| e.split(',').any(|part| part.trim() == "parties"))
+    }
+}
+
+/// Poziadavka na zapnutie/vypnutie rezimu len na citanie
+#[derive(Debug, Deserialize)]
+pub struct ReadOnlyRequest {
+    /// true = zapisove operacie budu odmietnute
+    pub enabled: bool,
+}
+
+/// Parametre dotazu pre statistiky uctu (casove okno)
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsQuery {
+    /// Zaciatok okna (vratane) v UTC, napr. 2026-01-01T00:00:00
+    pub from: Option<NaiveDateTime>,
+    /// Koniec okna (bez neho) v UTC
+    pub to: Option<NaiveDateTime>,
+}
+
+/// Sucet zostatkov uctov v jednej mene
+#[derive(Debug, Serialize)]
+pub struct CurrencyBalanceTotal {
+    /// Mena (ISO 4217)
+    pub currency: String,
+    /// Pocet uctov v tejto mene
+    pub account_count: i64,
+    /// Sucet zostatkov
+    pub total_balance: Decimal,
+}
+
+/// Objem transakcii v jednej mene za casove okno
+#[derive(Debug, Serialize)]
+pub struct CurrencyVolume {
+    /// Mena (ISO 4217), None = stare transakcie so zmazanymi uctami bez znamej meny
+    pub currency: Option<String>,
+    /// Pocet transakcii
+    pub transaction_count: i64,
+    /// Sucet sum transakcii
+    pub total_amount: Decimal,
+}
+
+/// Suhrnne statistiky celeho systemu pre administratora
+#[derive(Debug, Serialize)]
+pub struct SystemStats {
+    /// Pocet pouzivatelov
+    pub user_count: i64,
+    /// Pocet uctov (vratane zatvorenych)
+    pub account_count: i64,
+    /// Zostatky podla meny
+    pub balances: Vec<CurrencyBalanceTotal>,
+    /// Zaciatok okna objemu transakcii (None = bez obmedzenia)
+    pub from: Option<DateTime<Utc>>,
+    /// Koniec okna objemu transakcii (None = bez obmedzenia)
+    pub to: Option<DateTime<Utc>>,
+    /// Objem transakcii v okne podla meny
+    pub volume: Vec<CurrencyVolume>,
+}
+
+/// Suhrnne statistiky uctu za casove okno
+#[derive(Debug, Serialize)]
+pub struct AccountStats {
+    /// Identifikator uctu
+    pub account_id: Uuid,
+    /// Sucet prijatych platieb a vkladov
+    pub total_credits: Decimal,
+    /// Sucet odoslanych platieb
+    pub total_debits: Decimal,
+    /// Cista zmena zostatku (prijmy - vydavky)
+    pub net_change: Decimal,
+    /// Pocet transakcii v okne
+    pub transaction_count: i64,
+}
+
+/// Vypis uctu za obdobie s pociatocnym a konecnym zostatkom
+#[derive(Debug, Serialize)]
+pub struct AccountStatement {
+    /// Identifikator uctu
+    pub account_id: Uuid,
+    /// Mena uctu (ISO 4217)
+    pub currency: String,
+    /// Zaciatok obdobia (vratane), None = od zalozenia uctu
+    pub from: Option<DateTime<Utc>>,
+    /// Koniec obdobia (bez neho), None = po sucasnost
+    pub to: Option<DateTime<Utc>>,
+    /// Zostatok na zaciatku obdobia
+    pub opening_balance: Decimal,
+    /// Zostatok na konci obdobia
+    pub closing_balance: Decimal,
+    /// Sucet prijmov a vkladov v obdobi
+    pub total_credits: Decimal,
+    /// Sucet odoslanych platieb v obdobi
+    pub total_debits: Decimal,
+    /// Transakcie obdobia vratane archivovanych, najstarsie prve
+    pub transactions: Vec<Transaction>,
+}
+
+/// Ucet, s ktorym dany ucet obchodoval (protistrana)
+#[derive(Debug, Serialize)]
+pub struct Counterparty {
+    /// Identifikator uctu protistrany
+    pub account_id: Uuid,
+    /// Cislo uctu protistrany (maskovane pri REDACT_ACCOUNT_NUMBERS)
+    pub account_number: Option<String>,
+    /// Vlastnik uctu protistrany (pri REDACT_ACCOUNT_NUMBERS len pri vlastnych uctoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    /// Pocet vsetkych transakcii s protistranou
+    pub transaction_count: i64,
+    /// Pocet platieb odoslanych protistrane
+    pub sent_count: i64,
+    /// Pocet platieb prijatych od protistrany
+    pub received_count: i64,
+    /// Cas poslednej transakcie s protistranou
+    pub last_transacted_at: Option<DateTime<Utc>>,
+}
+
+/// Blokovany vklad, ktory este nie je k dispozicii
+#[derive(Debug, Serialize)]
+pub struct DepositHold {
+    pub transaction_id: Uuid,
+    pub amount: Decimal,
+    /// Od kedy mozno vklad pouzit
+    pub available_at: DateTime<Utc>,
+}
+
+/// Zostatok uctu vratane sumy, ktoru mozno hned pouzit
+#[derive(Debug, Serialize)]
+pub struct AccountBalance {
+    pub account_id: Uuid,
+    /// Uctovny zostatok (vratane blokovanych vkladov)
+    pub balance: Decimal,
+    /// Zostatok bez blokovanych vkladov - z neho sa odcitavaju prevody
+    pub available_balance: Decimal,
+    /// Blokovane vklady zoradene podla uvolnenia
+    pub holds: Vec<DepositHold>,
+}
+
+/// Vysledok zacatia prevodu
+///
+/// Prevody pod nastavenou hranicou sa vykonaju hned, vacsie cakaju na potvrdenie.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TransferInitiation {
+    /// Prevod bol vykonany okamzite
+    Completed { transaction: Box<Transaction> },
+    /// Prevod caka na potvrdenie tokenom
+    Pending {
+        /// Identifikator cakajuceho prevodu
+        id: Uuid,
+        /// Token na potvrdenie (POST /transactions/confirm)
+        token: String,
+        /// Cas vyprsania platnosti tokenu
+        expires_at: DateTime<Utc>,
+    },
+}
+
+/// Poziadavka na potvrdenie cakajuceho prevodu
+#[derive(Debug, Deserialize)]
+pub struct ConfirmTransferRequest {
+    /// Token ziskany pri zacati prevodu
+    pub token: String,
+}
+
+/// Druh zruseneho prevodu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelledTransferKind {
+    /// Prevod cakajuci na potvrdenie (/transactions/initiate)
+    Pending,
+    /// Trvaly prikaz (/me/recurring-transfers) - dalsie terminy sa nevykonaju
+    Recurring,
+}
+
+/// Zruseny cakajuci prevod alebo trvaly prikaz
+#[derive(Debug, Serialize)]
+pub struct CancelledTransfer {
+    pub id: Uuid,
+    pub kind: CancelledTransferKind,
+    pub from_account: Uuid,
+    pub to_account: Uuid,
+    pub amount: Decimal,
+}
+
+/// Poziadavka na overenie e-mailovej adresy
+#[derive(Debug, Deserialize)]
+pub struct EmailVerificationRequest {
+    /// Adresa, ktora sa ma overit
+    pub email: String,
+}
+
+/// Odoslany overovaci token e-mailu (samotny token prisiel ako notifikacia)
+#[derive(Debug, Serialize)]
+pub struct EmailVerificationSent {
+    /// Overovana adresa (normalizovana)
+    pub email: String,
+    /// Cas, do ktoreho treba adresu overit
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Poziadavka na potvrdenie e-mailovej adresy tokenom
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailRequest {
+    /// Token z POST /me/email-verification
+    pub token: String,
+}
+
+/// Stav e-mailovej adresy pouzivatela
+#[derive(Debug, Serialize)]
+pub struct EmailStatus {
+    pub user_id: Uuid,
+    /// Overena adresa (None kym ziadna nebola overena)
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
+
+/// Informacie o API kluci (bez samotneho kluca)
+#[derive(Debug, Serialize)]
+pub struct ApiKeyInfo {
+    /// Identifikator kluca
+    pub id: Uuid,
+    /// Zaciatok kluca pre rozlisenie v zozname
+    pub prefix: String,
+    /// Cas vytvorenia
+    pub created_at: DateTime<Utc>,
+    /// Cas zrusenia (None = aktivny)
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Novo vytvoreny API kluc - kluc sa zobrazi len raz
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKey {
+    /// Identifikator kluca
+    pub id: Uuid,
+    /// Cely kluc pre hlavicku X-API-Key (neda sa znovu ziskat)
+    pub key: String,
+    /// Zaciatok kluca pre rozlisenie v zozname
+    pub prefix: String,
+    /// Cas vytvorenia
+    pub created_at: DateTime<Utc>,
+}
+
+/// Poziadavka na pripisanie uroku
+#[derive(Debug, Deserialize)]
+pub struct InterestRequest {
+    /// Urokova sadzba za obdobie, napr. "0.0125" = 1,25 % (najviac 8 desatinnych miest)
+    pub rate: Decimal,
+}
+
+/// Vysledok pripisania uroku
+#[derive(Debug, Serialize)]
+pub struct InterestRun {
+    /// Identifikator behu
+    pub run_id: Uuid,
+    /// Pouzita sadzba
+    pub rate: Decimal,
+    /// Suhrn podla meny
+    pub currencies: Vec<InterestSummary>,
+}
+
+/// Ocakavany zostatok uctu pri oprave zostatkov
+#[derive(Debug, Deserialize)]
+pub struct ReconcileEntry {
+    pub account_id: Uuid,
+    /// Zostatok podla autoritativneho zdroja
+    pub expected_balance: Decimal,
+}
+
+/// Parametre dotazu opravy zostatkov
+#[derive(Debug, Default, Deserialize)]
+pub struct ReconcileQuery {
+    /// Opravit aj zablokovane ucty (predvolene false)
+    pub force: Option<bool>,
+}
+
+/// Oprava jedneho uctu
+#[derive(Debug, Serialize)]
+pub struct ReconcileAdjustment {
+    pub account_id: Uuid,
+    pub previous_balance: Decimal,
+    pub expected_balance: Decimal,
+    /// Rozdiel pripisany na ucet (zaporny = odpis)
+    pub adjustment: Decimal,
+    /// Vyrovnavacia transakcia (None ak zostatok uz sedel)
+    pub transaction_id: Option<Uuid>,
+}
+
+/// Vysledok opravy zostatkov
+#[derive(Debug, Serialize)]
+pub struct Reconciliation {
+    pub id: Uuid,
+    /// Kto opravu vykonal (hlavicka X-Admin-Actor)
+    pub actor: Option<String>,
+    pub forced: bool,
+    pub adjustments: Vec<ReconcileAdjustment>,
+}
+
+/// Suhrn pripisaneho uroku v jednej mene
+#[derive(Debug, Serialize)]
+pub struct InterestSummary {
+    /// Mena (ISO 4217)
+    pub currency: String,
+    /// Pocet uctov, na ktore sa urok pripisal
+    pub accounts: i32,
+    /// Presny urok pred zaokruhlenim
+    pub total_interest: Decimal,
+    /// Urok pripisany na ucty (zaokruhleny na centy)
+    pub total_credited: Decimal,
+    /// Rozdiel presunuty na zaokruhlovaci ucet
+    pub rounding_remainder: Decimal,
+}
+
+/// Kontrola zaokruhlovacieho uctu jednej meny
+#[derive(Debug, Serialize)]
+pub struct RoundingAudit {
+    /// Mena (ISO 4217)
+    pub currency: String,
+    /// Sucet presneho uroku zo vsetkych behov
+    pub total_interest: Decimal,
+    /// Sucet uroku pripisaneho na ucty
+    pub total_credited: Decimal,
+    /// Zostatok zaokruhlovacieho uctu
+    pub rounding_balance: Decimal,
+    /// true ak total_interest == total_credited + rounding_balance
+    pub balanced: bool,
+}
+
+/// Upozornenie pre administratora - zostatok uctu klesol pod nulu
+#[derive(Debug, Serialize)]
+pub struct NegativeBalanceAlert {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// Odpis, ktory zostatok dostal pod nulu
+    pub transaction_id: Option<Uuid>,
+    /// Zostatok pred odpisom
+    pub previous_balance: Decimal,
+    /// Zostatok po odpise (zaporny)
+    pub balance: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parametre zoznamu upozorneni (`?account=...&limit=50`)
+#[derive(Debug, Default, Deserialize)]
+pub struct NegativeBalanceAlertQuery {
+    /// Len upozornenia daneho uctu
+    pub account: Option<Uuid>,
+    /// Pocet upozorneni (predvolene 100, najviac 500)
+    pub limit: Option<i64>,
+}
+
+/// Spravanie hromadneho importu pri duplicitnom mene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// Duplicitny riadok sa nahlasi a import pokracuje
+    #[default]
+    Skip,
+    /// Akakolvek chyba (duplicita alebo neplatny riadok) zrusi cely import
+    Abort,
+}
+
+/// Parametre dotazu pre import pouzivatelov
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportQuery {
+    /// skip (predvolene) | abort
+    #[serde(default)]
+    pub on_duplicate: DuplicatePolicy,
+}
+
+/// Vysledok spracovania jedneho riadku importu
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    /// Pouzivatel bol vytvoreny (ak committed = true)
+    Created,
+    /// Meno uz existuje v databaze alebo skor v tom istom subore
+    Duplicate,
+    /// Riadok nema tvar `username,password`
+    Invalid,
+}
+
+/// Vysledok jedneho riadku importu
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    /// Cislo riadku v CSV (od 1)
+    pub line: usize,
+    /// Pouzivatelske meno z riadku
+    pub username: String,
+    /// Vysledok spracovania
+    pub status: ImportRowStatus,
+    /// ID vytvoreneho pouzivatela
+    pub id: Option<Uuid>,
+}
+
+/// Suhrn hromadneho importu pouzivatelov
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    /// Ci boli zmeny ulozene (false = import zruseny pri on_duplicate=abort)
+    pub committed: bool,
+    /// Pocet vytvorenych pouzivatelov
+    pub created: usize,
+    /// Pocet neuspesnych riadkov
+    pub failed: usize,
+    /// Vysledok kazdeho riadku
+    pub rows: Vec<ImportRowResult>,
+}
+
+/// Nezhoda medzi ulozenym zostatkom a historiou transakcii
+#[derive(Debug, Serialize)]
+pub struct BalanceDiscrepancy {
+    /// Identifikator uctu
+    pub account_id: Uuid,
+    /// Zostatok ulozeny v tabulke accounts
+    pub stored_balance: Decimal,
+    /// Zostatok vypocitany z transakcii (prijmy - vydavky)
+    pub computed_balance: Decimal,
+    /// stored_balance - computed_balance
+    pub difference: Decimal,
+}
+
+/// Frekvencia trvaleho prikazu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum Frequency {
+    /// Kazdy den
+    Daily,
+    /// Kazdy tyzden
+    Weekly,
+    /// Kazdy mesiac
+    Monthly,
+}
+
+/// Poziadavka na vytvorenie trvaleho prikazu
+#[derive(Debug, Deserialize)]
+pub struct CreateRecurringTransferRequest {
+    /// Ucet odosielatela (musi patrit prihlasenemu pouzivatelovi)
+    pub from_account: Uuid,
+    /// Ucet prijemcu
+    pub to_account: Uuid,
+    /// Suma kazdeho prevodu
+    pub amount: Decimal,
+    /// Frekvencia (daily | weekly | monthly)
+    pub frequency: Frequency,
+    /// Cas prveho prevodu (predvolene hned)
+    pub start_at: Option<DateTime<Utc>>,
+    /// Volitelna poznamka odosielatela
+    pub sender_note: Option<String>,
+    /// Volitelna poznamka prijemcu
+    pub receiver_note: Option<String>,
+}
+
+/// Trvaly prikaz
+#[derive(Debug, Serialize)]
+pub struct RecurringTransfer {
+    /// Identifikator prikazu
+    pub id: Uuid,
+    /// Ucet odosielatela
+    pub from_account: Uuid,
+    /// Ucet prijemcu
+    pub to_account: Uuid,
+    /// Suma kazdeho prevodu
+    pub amount: Decimal,
+    /// Frekvencia
+    pub frequency: Frequency,
+    /// Cas nasledujuceho prevodu
+    pub next_run_at: DateTime<Utc>,
+    /// false = prikaz je zruseny
+    pub active: bool,
+    /// Cas posledneho behu
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Kod chyby posledneho behu (None = uspech alebo este nebezal)
+    pub last_error: Option<String>,
+}
+
+/// Udalost, o ktorej moze byt pouzivatel upovedomeny
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// Prichadzajuci prevod na ucet pouzivatela
+    IncomingTransfer,
+    /// Odchadzajuci prevod od LARGE_WITHDRAWAL_THRESHOLD
+    LargeWithdrawal,
+    /// Prihlasenie z noveho zariadenia
+    NewDeviceLogin,
+    /// Zostatok uctu klesol pod nastavenu hranicu
+    LowBalance,
+    /// Overovaci token e-mailovej adresy (v poli detail)
+    EmailVerification,
+}
+
+/// Nastavenia notifikacii pouzivatela
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct NotificationPrefs {
+    pub incoming_transfer: bool,
+    pub large_withdrawal: bool,
+    pub new_device_login: bool,
+    pub low_balance: bool,
+}
+
+impl Default for NotificationPrefs {
+    /// Bez nastaveni su vsetky notifikacie zapnute
+    fn default() -> Self {
+        Self {
+            incoming_transfer: true,
+            large_withdrawal: true,
+            new_device_login: true,
+            low_balance: true,
+        }
+    }
+}
+
+impl NotificationPrefs {
+    /// Ci pouzivatel chce notifikaciu o danej udalosti
+    pub fn allows(&self, event: NotificationEvent) -> bool {
+        match event {
+            NotificationEvent::IncomingTransfer => self.incoming_transfer,
+            NotificationEvent::LargeWithdrawal => self.large_withdrawal,
+            NotificationEvent::NewDeviceLogin => self.new_device_login,
+            NotificationEvent::LowBalance => self.low_balance,
+            // Token sa inak ako notifikaciou nedoruci, preto sa vypnut neda
+            NotificationEvent::EmailVerification => true,
+        }
+    }
+}
+
+/// Zmena nastaveni notifikacii (chybajuce pole = bez zmeny)
+#[derive(Debug, Default, Deserialize)]
+pub struct NotificationPrefsUpdate {
+    pub incoming_transfer: Option<bool>,
+    pub large_withdrawal: Option<bool>,
+    pub new_device_login: Option<bool>,
+    pub low_balance: Option<bool>,
+}
+
+/// Nastavenie hranice nizkeho zostatku uctu
+#[derive(Debug, Deserialize)]
+pub struct LowBalanceThresholdRequest {
+    /// Hranica (None = upozornenie vypnute)
+    pub threshold: Option<Decimal>,
+}
+
+/// Hranica nizkeho zostatku uctu
+#[derive(Debug, Serialize)]
+pub struct LowBalanceThreshold {
+    pub account_id: Uuid,
+    pub threshold: Option<Decimal>,
+}
+
+/// Nastavenie PIN-u uctu
+#[derive(Debug, Deserialize)]
+pub struct AccountPinRequest {
+    /// Novy PIN (4 az 8 cislic), None = PIN sa odstrani
+    pub pin: Option<String>,
+}
+
+/// Stav PIN-u uctu (samotny PIN sa nikdy nevracia)
+#[derive(Debug, Serialize)]
+pub struct AccountPin {
+    pub account_id: Uuid,
+    pub pin_set: bool,
+}
+
+/// Odoslana notifikacia
+#[derive(Debug, Serialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub event: NotificationEvent,
+    /// Transakcia, ktorej sa notifikacia tyka
+    pub transaction_id: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    /// Doplnujuce udaje (napr. zariadenie pri prihlaseni)
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Zaznam pokusu o prihlasenie
+#[derive(Debug, Serialize)]
+pub struct LoginAttempt {
+    pub id: Uuid,
+    pub success: bool,
+    /// IP adresa klienta (X-Forwarded-For alebo adresa spojenia)
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parametre dotazu historie prihlaseni (`?success=false&limit=20&offset=40`)
+#[derive(Debug, Default, Deserialize)]
+pub struct LoginHistoryQuery {
+    /// Len uspesne (true) alebo neuspesne (false) pokusy
+    pub success: Option<bool>,
+    /// Pocet pokusov na stranke (predvolene 100)
+    pub limit: Option<i64>,
+    /// Pocet preskocenych pokusov (predvolene 0)
+    pub offset: Option<i64>,
+}
+
+/// Stranka historie prihlaseni
+#[derive(Debug, Serialize)]
+pub struct LoginHistoryPage {
+    /// Pocet vsetkych pokusov vyhovujucich filtru (bez ohladu na strankovanie)
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// Pokusy zoradene od najnovsich
+    pub attempts: Vec<LoginAttempt>,
+}
+
+/// Povolene hodnoty vyctovych poli API (pre klientov, napr. vyber v formulari)
+#[derive(Debug, Serialize)]
+pub struct EnumValues {
+    pub account_type: &'static [AccountType],
+    pub currency: &'static [&'static str],
+    pub transaction_kind: &'static [TransactionKind],
+    pub account_status: &'static [AccountStatus],
+}
+
+/// Stav zdielaneho poolu spojeni servera
+#[derive(Debug, Serialize)]
+pub struct PoolStats {
+    /// Otvorene spojenia (pouzivane aj necinne)
+    pub size: u32,
+    /// Necinne spojenia pripravene na pouzitie
+    pub idle: u32,
+    /// Prave pouzivane spojenia
+    pub in_use: u32,
+    /// Najvyssi pocet spojeni poolu (DB_MAX_CONNECTIONS)
+    pub max_connections: u32,
+}
+
+/// Spojenia do databazy servera podla pg_stat_activity (zo vsetkych poolov)
+#[derive(Debug, Serialize)]
+pub struct DatabaseConnections {
+    pub total: i64,
+    /// Spojenia prave vykonavajuce dotaz
+    pub active: i64,
+    pub idle: i64,
+}
+
+/// Podrobny stav servera (GET /health/detailed)
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: &'static str,
+    /// Doba odozvy `SELECT 1` v milisekundach
+    pub database_latency_ms: f64,
+    pub pool: PoolStats,
+    pub connections: DatabaseConnections,
 }
@@ -0,0 +1,315 @@
+// notifications.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::models::{Notification, NotificationEvent, NotificationPrefs, NotificationPrefsUpdate};
+use crate::rounding::MONEY_SCALE;
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool, Postgres, query};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Notifikacia pred odoslanim
+#[derive(Debug, Clone, Copy)]
+pub struct NewNotification<'a> {
+    pub event: NotificationEvent,
+    pub transaction_id: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    pub detail: Option<&'a str>,
+}
+
+/// Zastupne symboly sablony notifikacie o prevode
+pub const TEMPLATE_PLACEHOLDERS: [&str; 5] =
+    ["amount", "currency", "counterparty", "balance", "reference"];
+
+/// Sablona textu notifikacie o prevode, napr. `Prisla platba {amount} {currency} od {counterparty}`
+///
+/// # Poznamka
+/// Text sa ulozi do pola `detail` notifikacie. Neznamy zastupny symbol je chyba
+/// uz pri nacitani sablony, takze sa pri odoslani nic nepreskakuje potichu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationTemplate(String);
+
+/// Udaje prevodu dosadzane do sablony
+#[derive(Debug, Clone, Copy)]
+pub struct TransferValues<'a> {
+    /// Suma prevodu
+    pub amount: Decimal,
+    /// Mena uctu prijemcu notifikacie
+    pub currency: &'a str,
+    /// Pouzivatelske meno druhej strany prevodu
+    pub counterparty: &'a str,
+    /// Zostatok uctu prijemcu notifikacie po prevode
+    pub balance: Decimal,
+    /// Referencne cislo prevodu
+    pub reference: &'a str,
+}
+
+impl NotificationTemplate {
+    /// Premenna prostredia so sablonou pre danu udalost (len udalosti prevodu)
+    ///
+    /// # Konfiguracia
+    /// - NOTIFICATION_TEMPLATE_INCOMING_TRANSFER: prichadzajuci prevod
+    /// - NOTIFICATION_TEMPLATE_LARGE_WITHDRAWAL: odchadzajuci prevod od LARGE_WITHDRAWAL_THRESHOLD
+    pub fn env_var(event: NotificationEvent) -> Option<&'static str> {
+        match event {
+            NotificationEvent::IncomingTransfer => Some("NOTIFICATION_TEMPLATE_INCOMING_TRANSFER"),
+            NotificationEvent::LargeWithdrawal => Some("NOTIFICATION_TEMPLATE_LARGE_WITHDRAWAL"),
+            NotificationEvent::NewDeviceLogin
+            | NotificationEvent::LowBalance
+            | NotificationEvent::EmailVerification => None,
+        }
+    }
+
+    /// Dosadi udaje prevodu za zastupne symboly (sumy s MONEY_SCALE desatinnymi miestami)
+    pub fn render(&self, values: &TransferValues<'_>) -> String {
+        let money = |mut value: Decimal| {
+            value.rescale(MONEY_SCALE);
+            value.to_string()
+        };
+        let mut rendered = String::with_capacity(self.0.len());
+        let mut rest = self.0.as_str();
+
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            match &rest[start + 1..start + len] {
+                "amount" => rendered.push_str(&money(values.amount)),
+                "currency" => rendered.push_str(values.currency),
+                "counterparty" => rendered.push_str(values.counterparty),
+                "balance" => rendered.push_str(&money(values.balance)),
+                "reference" => rendered.push_str(values.reference),
+                _ => rendered.push_str(&rest[start..=start + len]),
+            }
+            rest = &rest[start + len + 1..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+impl FromStr for NotificationTemplate {
+    type Err = String;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                return Err("unclosed '{' in template".to_string());
+            };
+            let name = &rest[start + 1..start + len];
+            if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "unknown placeholder '{{{name}}}', expected one of {}",
+                    TEMPLATE_PLACEHOLDERS.join(", ")
+                ));
+            }
+            rest = &rest[start + len + 1..];
+        }
+        Ok(Self(template.to_string()))
+    }
+}
+
+impl fmt::Display for NotificationTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Text notifikacie o prevode podla nastavenej sablony
+///
+/// # Parametre
+/// - conn: spojenie (transakcia prevodu, zostatky su uz zmenene)
+/// - config: konfiguracia so sablonami (NOTIFICATION_TEMPLATE_*)
+/// - event: udalost notifikacie
+/// - account_id: ucet prijemcu notifikacie (jeho mena a zostatok)
+/// - counterparty_account: ucet druhej strany prevodu
+/// - amount: suma prevodu
+/// - reference: referencne cislo prevodu
+///
+/// # Navratova hodnota
+/// Vracia None, ak pre udalost nie je nastavena sablona
+pub async fn transfer_detail(
+    conn: &mut PgConnection,
+    config: &Config,
+    event: NotificationEvent,
+    account_id: Uuid,
+    counterparty_account: Uuid,
+    amount: Decimal,
+    reference: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let Some(template) = config.notification_template(event) else {
+        return Ok(None);
+    };
+
+    let row = query!(
+        "SELECT a.balance, a.currency, u.username
+         FROM accounts a, accounts c
+         JOIN users u ON u.id = c.user_id
+         WHERE a.id = $1 AND c.id = $2",
+        account_id,
+        counterparty_account
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(Some(template.render(&TransferValues {
+        amount,
+        currency: &row.currency,
+        counterparty: &row.username,
+        balance: row.balance,
+        reference,
+    })))
+}
+
+/// Zisti, ci zostatok prave klesol pod hranicu nizkeho zostatku
+///
+/// # Poznamka
+/// Upozornenie sa posiela len pri prekroceni hranice smerom dole. Dalsie odpisy
+/// pod hranicou neupozornuju znovu, kym zostatok nevystupi na hranicu alebo nad nu.
+pub fn crosses_below(old_balance: Decimal, new_balance: Decimal, threshold: Decimal) -> bool {
+    old_balance >= threshold && new_balance < threshold
+}
+
+/// Nacita nastavenia notifikacii (bez ulozenych nastaveni vsetko zapnute)
+async fn load_prefs(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<NotificationPrefs, sqlx::Error> {
+    let prefs = query!(
+        "SELECT incoming_transfer, large_withdrawal, new_device_login, low_balance
+         FROM notification_prefs WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| NotificationPrefs {
+        incoming_transfer: row.incoming_transfer,
+        large_withdrawal: row.large_withdrawal,
+        new_device_login: row.new_device_login,
+        low_balance: row.low_balance,
+    })
+    .unwrap_or_default();
+
+    Ok(prefs)
+}
+
+/// Odosle notifikaciu, ak ju pouzivatel nema vypnutu
+///
+/// # Parametre
+/// - tx: databazova transakcia operacie, ktora notifikaciu vyvolala -
+///   pri jej stornovani sa notifikacia neodosle
+/// - user_id: prijemca notifikacie
+/// - notification: udalost a jej udaje
+///
+/// # Navratova hodnota
+/// Vracia true, ak bola notifikacia odoslana
+pub async fn notify(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+    notification: NewNotification<'_>,
+) -> Result<bool, sqlx::Error> {
+    if !load_prefs(tx, user_id).await?.allows(notification.event) {
+        return Ok(false);
+    }
+
+    query!(
+        "INSERT INTO notifications (id, user_id, event, transaction_id, amount, detail)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        Uuid::new_v4(),
+        user_id,
+        notification.event as NotificationEvent,
+        notification.transaction_id,
+        notification.amount,
+        notification.detail
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(true)
+}
+
+/// Ziska nastavenia notifikacii pouzivatela
+pub async fn get_notification_prefs(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<NotificationPrefs, BankError> {
+    let mut conn = pool.acquire().await?;
+    Ok(load_prefs(&mut conn, user_id).await?)
+}
+
+/// Zmeni nastavenia notifikacii pouzivatela
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID pouzivatela
+/// - prefs: zmenene polia (chybajuce zostanu bez zmeny)
+///
+/// # Navratova hodnota
+/// Vracia vysledne nastavenia
+///
+/// # Chyby
+/// - BankError::ReferenceNotFound("user"): pouzivatel neexistuje
+pub async fn update_notification_prefs(
+    pool: &PgPool,
+    user_id: Uuid,
+    prefs: &NotificationPrefsUpdate,
+) -> Result<NotificationPrefs, BankError> {
+    let row = query!(
+        "INSERT INTO notification_prefs
+             (user_id, incoming_transfer, large_withdrawal, new_device_login, low_balance)
+         VALUES ($1, COALESCE($2, TRUE), COALESCE($3, TRUE), COALESCE($4, TRUE), COALESCE($5, TRUE))
+         ON CONFLICT (user_id) DO UPDATE SET
+             incoming_transfer = COALESCE($2, notification_prefs.incoming_transfer),
+             large_withdrawal = COALESCE($3, notification_prefs.large_withdrawal),
+             new_device_login = COALESCE($4, notification_prefs.new_device_login),
+             low_balance = COALESCE($5, notification_prefs.low_balance),
+             updated_at = NOW()
+         RETURNING incoming_transfer, large_withdrawal, new_device_login, low_balance",
+        user_id,
+        prefs.incoming_transfer,
+        prefs.large_withdrawal,
+        prefs.new_device_login,
+        prefs.low_balance
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(NotificationPrefs {
+        incoming_transfer: row.incoming_transfer,
+        large_withdrawal: row.large_withdrawal,
+        new_device_login: row.new_device_login,
+        low_balance: row.low_balance,
+    })
+}
+
+/// Ziska notifikacie pouzivatela od najnovsej
+pub async fn list_notifications(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Notification>, BankError> {
+    let rows = query!(
+        r#"SELECT id, event AS "event: NotificationEvent", transaction_id, amount, detail, created_at
+           FROM notifications
+           WHERE user_id = $1
+           ORDER BY created_at DESC"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let notifications = rows
+        .into_iter()
+        .map(|row| Notification {
+            id: row.id,
+            event: row.event,
+            transaction_id: row.transaction_id,
+            amount: row.amount,
+            detail: row.detail,
+            created_at: row.created_at,
+        })
+        .collect();
+    Ok(notifications)
+}
@@ -0,0 +1,121 @@
+// ofx.rs
+use crate::models::{PubAccount, Transaction};
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// MIME typ OFX suboru
+pub const OFX_CONTENT_TYPE: &str = "application/x-ofx";
+
+/// Druh pohybu na ucte podla OFX (element TRNTYPE)
+fn transaction_type(account_id: uuid::Uuid, transaction: &Transaction) -> &'static str {
+    match transaction.from_account {
+        // Vklad alebo pripisany urok (bez odosielatela)
+        None => "DEP",
+        Some(from) if from == account_id => "DEBIT",
+        Some(_) => "CREDIT",
+    }
+}
+
+/// Datum a cas vo formate OFX (YYYYMMDDHHMMSS v UTC)
+fn ofx_datetime(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%d%H%M%S[0:GMT]").to_string()
+}
+
+/// Nahradi znaky so specialnym vyznamom v XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Vytvori vypis uctu vo formate OFX 2.2 (XML)
+///
+/// # Parametre
+/// - account: ucet, ktoreho vypis sa vytvara (mena, cislo, zostatok)
+/// - transactions: historia uctu (napr. z get_transaction_history)
+/// - generated_at: cas vytvorenia vypisu (DTSERVER, DTASOF)
+///
+/// # Navratova hodnota
+/// Vracia XML dokument s jednym STMTTRN elementom na transakciu
+///
+/// # Poznamka
+/// Odchadzajuce prevody maju zapornu sumu (DEBIT), prichadzajuce kladnu
+/// (CREDIT), vklady a urok kladnu (DEP). FITID je ID transakcie, takze
+/// opakovany import rovnakych transakcii nevytvori duplicity.
+pub fn render_ofx(
+    account: &PubAccount,
+    transactions: &[Transaction],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let dates = transactions.iter().filter_map(|t| t.created_at);
+    let start = dates.clone().min().unwrap_or(generated_at);
+    let end = dates.max().unwrap_or(generated_at);
+
+    let account_id = account
+        .account_number
+        .clone()
+        .unwrap_or_else(|| account.id.to_string());
+
+    let mut ofx = String::new();
+    ofx.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+    ofx.push_str(
+        "<?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n",
+    );
+    ofx.push_str("<OFX>\n");
+    ofx.push_str("<SIGNONMSGSRSV1><SONRS>\n");
+    ofx.push_str("<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>\n");
+    let _ = writeln!(ofx, "<DTSERVER>{}</DTSERVER>", ofx_datetime(generated_at));
+    ofx.push_str("<LANGUAGE>ENG</LANGUAGE>\n");
+    ofx.push_str("</SONRS></SIGNONMSGSRSV1>\n");
+    ofx.push_str("<BANKMSGSRSV1><STMTTRNRS>\n");
+    ofx.push_str("<TRNUID>0</TRNUID>\n");
+    ofx.push_str("<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>\n");
+    ofx.push_str("<STMTRS>\n");
+    let _ = writeln!(ofx, "<CURDEF>{}</CURDEF>", escape(account.currency.trim()));
+    ofx.push_str("<BANKACCTFROM>\n");
+    ofx.push_str("<BANKID>BANKBACKEND</BANKID>\n");
+    let _ = writeln!(ofx, "<ACCTID>{}</ACCTID>", escape(&account_id));
+    ofx.push_str("<ACCTTYPE>CHECKING</ACCTTYPE>\n");
+    ofx.push_str("</BANKACCTFROM>\n");
+    ofx.push_str("<BANKTRANLIST>\n");
+    let _ = writeln!(ofx, "<DTSTART>{}</DTSTART>", ofx_datetime(start));
+    let _ = writeln!(ofx, "<DTEND>{}</DTEND>", ofx_datetime(end));
+
+    for transaction in transactions {
+        let kind = transaction_type(account.id, transaction);
+        let amount = match kind {
+            "DEBIT" => -transaction.amount,
+            _ => transaction.amount,
+        };
+
+        ofx.push_str("<STMTTRN>\n");
+        let _ = writeln!(ofx, "<TRNTYPE>{kind}</TRNTYPE>");
+        let _ = writeln!(
+            ofx,
+            "<DTPOSTED>{}</DTPOSTED>",
+            ofx_datetime(transaction.created_at.unwrap_or(generated_at))
+        );
+        let _ = writeln!(ofx, "<TRNAMT>{:.2}</TRNAMT>", amount);
+        let _ = writeln!(ofx, "<FITID>{}</FITID>", transaction.id);
+        if let Some(reference) = &transaction.reference {
+            let _ = writeln!(ofx, "<NAME>{}</NAME>", escape(reference));
+        }
+        if let Some(note) = &transaction.note {
+            let _ = writeln!(ofx, "<MEMO>{}</MEMO>", escape(note));
+        }
+        ofx.push_str("</STMTTRN>\n");
+    }
+
+    ofx.push_str("</BANKTRANLIST>\n");
+    ofx.push_str("<LEDGERBAL>\n");
+    let _ = writeln!(ofx, "<BALAMT>{:.2}</BALAMT>", account.balance);
+    let _ = writeln!(ofx, "<DTASOF>{}</DTASOF>", ofx_datetime(generated_at));
+    ofx.push_str("</LEDGERBAL>\n");
+    ofx.push_str("</STMTRS>\n");
+    ofx.push_str("</STMTTRNRS></BANKMSGSRSV1>\n");
+    ofx.push_str("</OFX>\n");
+
+    ofx
+}
@@ -0,0 +1,474 @@
+// password.rs
+use crate::error::BankError;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use scrypt::Scrypt;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Algoritmus, ktorym sa hashuju hesla novych pouzivatelov
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PasswordBackend {
+    /// Argon2 (varianta a verzia podla ARGON2_ALGORITHM a ARGON2_VERSION)
+    #[default]
+    Argon2,
+    /// bcrypt s predvolenou cenou (bcrypt::DEFAULT_COST)
+    Bcrypt,
+    /// scrypt s odporucanymi parametrami (PHC retazec `$scrypt$...`)
+    Scrypt,
+}
+
+impl FromStr for PasswordBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "argon2" => Ok(PasswordBackend::Argon2),
+            "bcrypt" => Ok(PasswordBackend::Bcrypt),
+            "scrypt" => Ok(PasswordBackend::Scrypt),
+            _ => Err(format!("expected argon2, bcrypt or scrypt, got '{value}'")),
+        }
+    }
+}
+
+/// Chyba hashovania alebo overenia hesla
+#[derive(Debug)]
+pub enum PasswordError {
+    /// Ulozeny hash nepatri ziadnemu podporovanemu algoritmu (alebo inemu, nez sa cakalo)
+    UnknownFormat,
+    /// Chyba kniznice pri hashovani alebo pri citani hashu
+    Backend(String),
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::UnknownFormat => write!(f, "Unknown password hash format"),
+            PasswordError::Backend(e) => write!(f, "Password hashing failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+/// Algoritmus hashovania hesiel
+///
+/// Kazdy backend rozpozna svoje hashe podla prefixu, takze overenie
+/// (verify_password) funguje aj po zmene PASSWORD_HASHER.
+pub trait PasswordHasher: Send + Sync {
+    /// Ci ulozeny hash patri tomuto algoritmu (podla prefixu, napr. `$argon2id$`)
+    fn recognizes(&self, hash: &str) -> bool;
+
+    /// Zahashuje heslo s nahodnou solou
+    fn hash(&self, password: &str) -> Result<String, PasswordError>;
+
+    /// Overi heslo voci ulozenemu hashu
+    ///
+    /// # Navratova hodnota
+    /// Ok(true) ak heslo sedi, Ok(false) ak nie
+    ///
+    /// # Chyby
+    /// - PasswordError::UnknownFormat: hash patri inemu algoritmu
+    /// - PasswordError::Backend: hash sa neda precitat
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, PasswordError>;
+}
+
+/// Argon2 - predvoleny backend
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Argon2Hasher {
+    /// Varianta a verzia pre nove hashe (overenie sa riadi ulozenym hashom)
+    pub config: PasswordConfig,
+}
+
+impl PasswordHasher for Argon2Hasher {
+    fn recognizes(&self, hash: &str) -> bool {
+        hash.starts_with("$argon2")
+    }
+
+    fn hash(&self, password: &str) -> Result<String, PasswordError> {
+        let salt = SaltString::generate(&mut OsRng);
+        Ok(self
+            .config
+            .argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| PasswordError::Backend(e.to_string()))?
+            .to_string())
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, PasswordError> {
+        if !self.recognizes(hash) {
+            return Err(PasswordError::UnknownFormat);
+        }
+        verify_phc(&Argon2::default(), password, hash)
+    }
+}
+
+/// bcrypt (`$2a$`, `$2b$`, `$2x$`, `$2y$`)
+///
+/// # Poznamka
+/// bcrypt berie do uvahy len prvych 72 bajtov hesla.
+#[derive(Debug, Clone, Copy)]
+pub struct BcryptHasher {
+    /// Cena (log2 poctu iteracii)
+    pub cost: u32,
+}
+
+impl Default for BcryptHasher {
+    fn default() -> Self {
+        Self {
+            cost: bcrypt::DEFAULT_COST,
+        }
+    }
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn recognizes(&self, hash: &str) -> bool {
+        ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+    }
+
+    fn hash(&self, password: &str) -> Result<String, PasswordError> {
+        bcrypt::hash(password, self.cost).map_err(|e| PasswordError::Backend(e.to_string()))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, PasswordError> {
+        if !self.recognizes(hash) {
+            return Err(PasswordError::UnknownFormat);
+        }
+        bcrypt::verify(password, hash).map_err(|e| PasswordError::Backend(e.to_string()))
+    }
+}
+
+/// scrypt v PHC formate (`$scrypt$ln=17,r=8,p=1$...`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScryptHasher;
+
+impl PasswordHasher for ScryptHasher {
+    fn recognizes(&self, hash: &str) -> bool {
+        hash.starts_with("$scrypt$")
+    }
+
+    fn hash(&self, password: &str) -> Result<String, PasswordError> {
+        let salt = SaltString::generate(&mut OsRng);
+        Ok(Scrypt
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| PasswordError::Backend(e.to_string()))?
+            .to_string())
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, PasswordError> {
+        if !self.recognizes(hash) {
+            return Err(PasswordError::UnknownFormat);
+        }
+        verify_phc(&Scrypt, password, hash)
+    }
+}
+
+/// Overenie hesla voci PHC retazcu (Argon2, scrypt) - nezhoda hesla nie je chyba
+fn verify_phc(
+    verifier: &dyn PasswordVerifier,
+    password: &str,
+    hash: &str,
+) -> Result<bool, PasswordError> {
+    let parsed = PasswordHash::new(hash).map_err(|e| PasswordError::Backend(e.to_string()))?;
+    match verifier.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(PasswordError::Backend(e.to_string())),
+    }
+}
+
+/// Odporucane minimum pamate Argon2 v KiB (OWASP: 19 MiB pri 2 iteraciach)
+pub const ARGON2_MIN_MEMORY_KIB: u32 = 19 * 1024;
+
+/// Odporucane minimum iteracii Argon2
+pub const ARGON2_MIN_ITERATIONS: u32 = 2;
+
+/// Nastavenia hashovania hesiel pre novych pouzivatelov
+///
+/// Existujuce hashe sa overuju podla algoritmu, varianty a verzie ulozenej v hashi,
+/// takze zmena nastaveni neovplyvni prihlasenie starsich pouzivatelov.
+#[derive(Debug, Clone, Copy)]
+pub struct PasswordConfig {
+    /// Algoritmus novych hashov (predvolene Argon2)
+    pub backend: PasswordBackend,
+    /// Varianta Argon2 (predvolene Argon2id)
+    pub algorithm: Algorithm,
+    /// Verzia Argon2 (predvolene 0x13)
+    pub version: Version,
+    /// Pamat Argon2 v KiB (predvolene 19456)
+    pub memory_kib: u32,
+    /// Pocet iteracii Argon2 (predvolene 2)
+    pub iterations: u32,
+    /// Pocet paralelnych vlakien Argon2 (predvolene 1)
+    pub parallelism: u32,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            backend: PasswordBackend::Argon2,
+            algorithm: Algorithm::Argon2id,
+            version: Version::V0x13,
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// Backend pre nove hashe podla PASSWORD_HASHER
+    pub fn hasher(&self) -> Box<dyn PasswordHasher> {
+        match self.backend {
+            PasswordBackend::Argon2 => Box::new(Argon2Hasher { config: *self }),
+            PasswordBackend::Bcrypt => Box::new(BcryptHasher::default()),
+            PasswordBackend::Scrypt => Box::new(ScryptHasher),
+        }
+    }
+
+    /// Parametre Argon2 (chyba pri neplatnej kombinacii, napr. malo pamate na pocet vlakien)
+    pub fn argon2_params(&self) -> Result<Params, argon2::Error> {
+        Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+    }
+
+    /// Vytvori Argon2 hasher so zvolenou variantou, verziou a parametrami
+    /// (neplatna kombinacia parametrov pouzije predvolene - Config ju odmietne uz pri starte)
+    pub fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(
+            self.algorithm,
+            self.version,
+            self.argon2_params().unwrap_or_default(),
+        )
+    }
+
+    /// Parametre Argon2 pod odporucanym minimom (ARGON2_MIN_MEMORY_KIB, ARGON2_MIN_ITERATIONS)
+    ///
+    /// # Navratova hodnota
+    /// Vracia popis kazdeho slabeho parametra, prazdny zoznam ak su parametre v poriadku
+    /// alebo sa Argon2 pre nove hashe nepouziva
+    pub fn argon2_weaknesses(&self) -> Vec<String> {
+        if self.backend != PasswordBackend::Argon2 {
+            return Vec::new();
+        }
+
+        let mut weaknesses = Vec::new();
+        if self.memory_kib < ARGON2_MIN_MEMORY_KIB {
+            weaknesses.push(format!(
+                "ARGON2_MEMORY_KIB: {} KiB is below the recommended minimum of {ARGON2_MIN_MEMORY_KIB} KiB",
+                self.memory_kib
+            ));
+        }
+        if self.iterations < ARGON2_MIN_ITERATIONS {
+            weaknesses.push(format!(
+                "ARGON2_ITERATIONS: {} is below the recommended minimum of {ARGON2_MIN_ITERATIONS}",
+                self.iterations
+            ));
+        }
+        weaknesses
+    }
+}
+
+/// Predvolena najmensia dlzka hesla (len neprazdne heslo)
+pub const DEFAULT_PASSWORD_MIN_LENGTH: usize = 1;
+
+/// Poziadavky na heslo noveho pouzivatela (registracia aj import)
+///
+/// # Poznamka
+/// Predvolene sa vyzaduje len neprazdne heslo. Dlzka sa pocita v znakoch, nie v bajtoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PasswordPolicy {
+    /// Najmensi pocet znakov (PASSWORD_MIN_LENGTH)
+    pub min_length: usize,
+    /// Aspon jedno velke pismeno (PASSWORD_REQUIRE_UPPERCASE)
+    pub require_uppercase: bool,
+    /// Aspon jedno male pismeno (PASSWORD_REQUIRE_LOWERCASE)
+    pub require_lowercase: bool,
+    /// Aspon jedna cislica (PASSWORD_REQUIRE_DIGIT)
+    pub require_digit: bool,
+    /// Aspon jeden znak, ktory nie je pismeno ani cislica (PASSWORD_REQUIRE_SYMBOL)
+    pub require_symbol: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: DEFAULT_PASSWORD_MIN_LENGTH,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Poziadavky, ktore heslo nesplna
+    ///
+    /// # Navratova hodnota
+    /// Prazdny zoznam, ak heslo vyhovuje, inak jeden popis za kazdu nesplnenu poziadavku
+    pub fn violations(&self, password: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        if password.chars().count() < self.min_length {
+            violations.push(format!(
+                "must be at least {} characters long",
+                self.min_length
+            ));
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            violations.push("must contain an uppercase letter".to_string());
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            violations.push("must contain a lowercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push("must contain a digit".to_string());
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            violations.push("must contain a symbol".to_string());
+        }
+        violations
+    }
+
+    /// Overi heslo voci poziadavkam
+    ///
+    /// # Chyby
+    /// - BankError::InvalidBody: heslo nesplna niektoru poziadavku (vsetky v jednej sprave)
+    pub fn check(&self, password: &str) -> Result<(), BankError> {
+        let violations = self.violations(password);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(BankError::InvalidBody(format!(
+            "password {}",
+            violations.join(", ")
+        )))
+    }
+}
+
+/// Prevedie textovu verziu Argon2 ("19", "0x13", "16", "0x10") na Version
+pub(crate) fn parse_version(value: &str) -> Option<Version> {
+    match value.trim().to_lowercase().as_str() {
+        "19" | "0x13" => Some(Version::V0x13),
+        "16" | "0x10" => Some(Version::V0x10),
+        _ => None,
+    }
+}
+
+/// Zahashuje heslo podla nastaveni s nahodnou solou
+///
+/// # Navratova hodnota
+/// Vracia hash s prefixom algoritmu - PHC retazec pre Argon2 a scrypt
+/// (obsahuje variantu, verziu, parametre a sol), `$2b$...` pre bcrypt
+pub fn hash_password(config: &PasswordConfig, password: &str) -> Result<String, PasswordError> {
+    config.hasher().hash(password)
+}
+
+/// Overi heslo voci ulozenemu hashu - algoritmus sa urci podla prefixu hashu
+///
+/// # Navratova hodnota
+/// Ok(true) ak heslo sedi, Ok(false) ak nie
+///
+/// # Chyby
+/// - PasswordError::UnknownFormat: hash nepatri Argon2, bcrypt ani scrypt
+/// - PasswordError::Backend: hash ma spravny prefix, ale neda sa precitat
+pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError> {
+    let hashers: [&dyn PasswordHasher; 3] = [
+        &Argon2Hasher::default(),
+        &BcryptHasher::default(),
+        &ScryptHasher,
+    ];
+    hashers
+        .into_iter()
+        .find(|hasher| hasher.recognizes(hash))
+        .ok_or(PasswordError::UnknownFormat)?
+        .verify(password, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BankError;
+    use axum::http::StatusCode;
+
+    fn backends() -> Vec<Box<dyn PasswordHasher>> {
+        vec![
+            Box::new(Argon2Hasher::default()),
+            // Najnizsia cena, nech test netrva dlho
+            Box::new(BcryptHasher { cost: 4 }),
+            Box::new(ScryptHasher),
+        ]
+    }
+
+    #[test]
+    fn each_backend_verifies_its_own_hashes() {
+        for hasher in backends() {
+            let hash = hasher.hash("correct horse").unwrap();
+            assert!(hasher.recognizes(&hash));
+            assert!(hasher.verify("correct horse", &hash).unwrap());
+            assert!(!hasher.verify("wrong horse", &hash).unwrap());
+            assert!(verify_password("correct horse", &hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn foreign_hash_is_unknown_format_not_a_panic() {
+        let hashers = backends();
+        let hashes: Vec<String> = hashers
+            .iter()
+            .map(|hasher| hasher.hash("correct horse").unwrap())
+            .collect();
+
+        for (i, hasher) in hashers.iter().enumerate() {
+            for (j, hash) in hashes.iter().enumerate().filter(|(j, _)| *j != i) {
+                assert!(
+                    matches!(
+                        hasher.verify("correct horse", hash),
+                        Err(PasswordError::UnknownFormat)
+                    ),
+                    "backend {i} accepted hash of backend {j}"
+                );
+            }
+        }
+        assert!(matches!(
+            verify_password("correct horse", "plaintext"),
+            Err(PasswordError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn hashing_failure_is_internal_bank_error() {
+        // Cena bcrypt mimo rozsahu 4..=31 - kniznica hash odmietne
+        let error = BcryptHasher { cost: 3 }.hash("correct horse").unwrap_err();
+
+        let error = BankError::from(error);
+        assert!(matches!(error, BankError::PasswordHashing(_)));
+        assert_eq!(error.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.code(), "PASSWORD_HASHING_ERROR");
+    }
+
+    #[test]
+    fn weak_argon2_parameters_are_reported() {
+        assert!(PasswordConfig::default().argon2_weaknesses().is_empty());
+
+        let weak = PasswordConfig {
+            memory_kib: 1024,
+            iterations: 1,
+            ..PasswordConfig::default()
+        };
+        let weaknesses = weak.argon2_weaknesses();
+        assert_eq!(weaknesses.len(), 2, "{weaknesses:?}");
+        assert!(weaknesses[0].starts_with("ARGON2_MEMORY_KIB: 1024 KiB"));
+        assert!(weaknesses[1].starts_with("ARGON2_ITERATIONS: 1 "));
+
+        // Slabe Argon2 nevadi, ak sa nove hashe vytvaraju inym algoritmom
+        let bcrypt = PasswordConfig {
+            backend: PasswordBackend::Bcrypt,
+            ..weak
+        };
+        assert!(bcrypt.argon2_weaknesses().is_empty());
+    }
+}
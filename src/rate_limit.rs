@@ -0,0 +1,237 @@
+// rate_limit.rs
+use axum::http::Method;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Najvyssi pocet sledovanych vedierok, potom sa zahodia plne a najdlhsie nepouzite
+const MAX_BUCKETS: usize = 10_000;
+
+/// Limit poziadaviek: `requests` za `period`, zapis `10/60` (10 poziadaviek za 60 s)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Najvyssi pocet poziadaviek naraz (kapacita vedierka)
+    pub requests: u32,
+    /// Za aky cas sa vedierko uplne doplni
+    pub period: Duration,
+}
+
+impl RateLimit {
+    /// Pocet tokenov doplnenych za sekundu
+    fn refill_per_sec(&self) -> f64 {
+        f64::from(self.requests) / self.period.as_secs_f64()
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected <requests>/<seconds>, got '{value}'");
+        let (requests, seconds) = value.trim().split_once('/').ok_or_else(invalid)?;
+        let requests: u32 = requests.trim().parse().map_err(|_| invalid())?;
+        let seconds: u64 = seconds.trim().parse().map_err(|_| invalid())?;
+        if requests == 0 || seconds == 0 {
+            return Err(format!(
+                "requests and seconds must be greater than zero, got '{value}'"
+            ));
+        }
+        Ok(Self {
+            requests,
+            period: Duration::from_secs(seconds),
+        })
+    }
+}
+
+/// Limit jednej trasy, zapis `POST /transactions=10/60`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteRateLimit {
+    pub method: Method,
+    /// Sablona trasy tak, ako je v routeri (napr. `/accounts/:id/close`)
+    pub path: String,
+    pub limit: RateLimit,
+}
+
+impl FromStr for RouteRateLimit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected <METHOD> <path>=<requests>/<seconds>, got '{value}'");
+        let (route, limit) = value.trim().split_once('=').ok_or_else(invalid)?;
+        let (method, path) = route.trim().split_once(' ').ok_or_else(invalid)?;
+        let method = Method::from_str(&method.trim().to_uppercase()).map_err(|_| invalid())?;
+        let path = path.trim();
+        if !path.starts_with('/') {
+            return Err(invalid());
+        }
+        Ok(Self {
+            method,
+            path: path.to_string(),
+            limit: limit.parse()?,
+        })
+    }
+}
+
+/// Limity poziadaviek podla trasy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimits {
+    /// Limit pre zapisove poziadavky (POST, PUT, PATCH, DELETE) bez vlastneho limitu
+    pub default: Option<RateLimit>,
+    /// Limity konkretnych tras (maju prednost pred default, platia aj pre GET)
+    pub routes: Vec<RouteRateLimit>,
+}
+
+impl RateLimits {
+    /// Limit pre danu trasu, None = bez obmedzenia
+    pub fn limit_for(&self, method: &Method, path: &str) -> Option<RateLimit> {
+        self.routes
+            .iter()
+            .find(|route| route.method == *method && route.path == path)
+            .map(|route| route.limit)
+            .or_else(|| {
+                let is_write = !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+                self.default.filter(|_| is_write)
+            })
+    }
+}
+
+/// Koho sa limit tyka
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitSubject {
+    /// Adresa klienta
+    Ip(IpAddr),
+    /// Pouzivatel prihlaseny API klucom (vsetky jeho kluce spolu)
+    User(Uuid),
+}
+
+/// Stav vedierka tokenov jednej trasy a jedneho subjektu
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Kedy bude vedierko opat plne (potom je rovnake ako nove)
+    full_at: Instant,
+}
+
+/// Obmedzovac poziadaviek metodou vedierka tokenov (token bucket)
+///
+/// Kazda trasa ma pre kazdu IP adresu a kazdeho pouzivatela vlastne vedierko
+/// s kapacitou `requests`, ktore sa plynulo dopina za `period`. Stav je len
+/// v pamati procesu - pri viacerych instanciach plati limit pre kazdu zvlast.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limits: RateLimits,
+    buckets: Mutex<HashMap<(Method, String, RateLimitSubject), Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Limit pre danu trasu (pozri RateLimits::limit_for)
+    pub fn limit_for(&self, method: &Method, path: &str) -> Option<RateLimit> {
+        self.limits.limit_for(method, path)
+    }
+
+    /// Odoberie token z vedierka subjektu pre danu trasu
+    ///
+    /// # Navratova hodnota
+    /// Ok ak poziadavka limit neprekrocila, inak Err s poctom sekund,
+    /// po ktorych bude k dispozicii dalsi token (aspon 1)
+    pub fn check(
+        &self,
+        method: &Method,
+        path: &str,
+        limit: RateLimit,
+        subject: RateLimitSubject,
+    ) -> Result<(), u64> {
+        let now = Instant::now();
+        let rate = limit.refill_per_sec();
+        let capacity = f64::from(limit.requests);
+        let key = (method.clone(), path.to_string(), subject);
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(&key) {
+            evict_buckets(&mut buckets, now);
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+            full_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens < 1.0 {
+            let wait = (1.0 - bucket.tokens) / rate;
+            return Err((wait.ceil() as u64).max(1));
+        }
+
+        bucket.tokens -= 1.0;
+        bucket.full_at = now + Duration::from_secs_f64((capacity - bucket.tokens) / rate);
+        Ok(())
+    }
+}
+
+/// Uvolni miesto v mape vedierok
+///
+/// # Poznamka
+/// Plne vedierko je rovnake ako nove, zahodi sa vzdy. Ak ich aj potom zostane
+/// viac ako polovica MAX_BUCKETS, ponechaju sa len najnovsie pouzite (podla `updated`).
+/// Po uvolneni polovice mapy sa dalsie cistenie spusti az po MAX_BUCKETS / 2 novych
+/// vedierkach, takze cena O(n) sa rozlozi medzi ne.
+fn evict_buckets<K>(buckets: &mut HashMap<K, Bucket>, now: Instant) {
+    buckets.retain(|_, bucket| bucket.full_at > now);
+
+    let keep = MAX_BUCKETS / 2;
+    if buckets.len() > keep {
+        let mut last_seen: Vec<Instant> = buckets.values().map(|bucket| bucket.updated).collect();
+        let drop = last_seen.len() - keep;
+        let (_, cutoff, _) = last_seen.select_nth_unstable(drop);
+        let cutoff = *cutoff;
+        buckets.retain(|_, bucket| bucket.updated >= cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn bucket_map_stays_bounded_and_keeps_recent_subjects() {
+        let limiter = RateLimiter::new(RateLimits::default());
+        let limit = RateLimit {
+            requests: 1,
+            period: Duration::from_secs(3600),
+        };
+        let subject = |n: u32| RateLimitSubject::Ip(IpAddr::V4(Ipv4Addr::from(n)));
+
+        for n in 0..(MAX_BUCKETS as u32 * 3) {
+            assert!(
+                limiter
+                    .check(&Method::POST, "/x", limit, subject(n))
+                    .is_ok()
+            );
+        }
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert!(buckets.len() <= MAX_BUCKETS);
+        let newest = (
+            Method::POST,
+            "/x".to_string(),
+            subject(MAX_BUCKETS as u32 * 3 - 1),
+        );
+        assert!(buckets.contains_key(&newest));
+    }
+}
@@ -0,0 +1,239 @@
+// rates.rs
+use crate::error::BankError;
+use crate::models::{DisplayBalance, PubAccount};
+use crate::rounding::{MONEY_SCALE, RoundingMode};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Zdroj menovych kurzov
+///
+/// Implementacia moze citat kurzy zo statickej tabulky alebo z externej sluzby.
+/// Vracia kolko jednotiek meny `to` zodpoveda jednej jednotke meny `from`.
+#[async_trait]
+pub trait RateProvider: Send + Sync {
+    async fn rate(&self, from: &str, to: &str) -> Result<Decimal, BankError>;
+}
+
+/// Poskytovatel kurzov s pevnou tabulkou kurzov
+///
+/// Ak kurz pre dvojicu chyba, skusi sa obratena dvojica (1 / kurz).
+/// Kurz medzi rovnakymi menami je vzdy 1.
+#[derive(Debug, Default, Clone)]
+pub struct StaticRateProvider {
+    rates: HashMap<(String, String), Decimal>,
+}
+
+impl StaticRateProvider {
+    /// Vytvori prazdnu tabulku kurzov
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prida kurz `from -> to` do tabulky
+    pub fn with_rate(mut self, from: &str, to: &str, rate: Decimal) -> Self {
+        self.rates
+            .insert((from.to_uppercase(), to.to_uppercase()), rate);
+        self
+    }
+}
+
+impl FromStr for StaticRateProvider {
+    type Err = String;
+
+    /// Tabulka kurzov z hodnoty EXCHANGE_RATES
+    ///
+    /// # Format
+    /// ```text
+    /// EXCHANGE_RATES=EUR:USD=1.08,EUR:CZK=25.20
+    /// ```
+    /// Kurz musi byt kladne cislo, prazdne polozky sa preskocia.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut provider = Self::new();
+
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry.split_once('=').and_then(|(pair, rate)| {
+                let (from, to) = pair.split_once(':')?;
+                let rate = Decimal::from_str(rate.trim()).ok()?;
+                (rate > Decimal::ZERO).then_some((from.trim(), to.trim(), rate))
+            });
+            let Some((from, to, rate)) = parsed else {
+                return Err(format!(
+                    "expected FROM:TO=rate with a positive rate, got '{entry}'"
+                ));
+            };
+            provider = provider.with_rate(from, to, rate);
+        }
+
+        Ok(provider)
+    }
+}
+
+#[async_trait]
+impl RateProvider for StaticRateProvider {
+    async fn rate(&self, from: &str, to: &str) -> Result<Decimal, BankError> {
+        let from = from.to_uppercase();
+        let to = to.to_uppercase();
+
+        if from == to {
+            return Ok(Decimal::ONE);
+        }
+
+        if let Some(rate) = self.rates.get(&(from.clone(), to.clone())) {
+            return Ok(*rate);
+        }
+
+        // Skusime obrateny kurz
+        match self.rates.get(&(to.clone(), from.clone())) {
+            Some(rate) if !rate.is_zero() => Ok(Decimal::ONE / *rate),
+            _ => Err(BankError::RateUnavailable { from, to }),
+        }
+    }
+}
+
+/// Dekorator, ktory si pamata kurzy vnutorneho poskytovatela po dobu TTL
+///
+/// Zabranuje opakovanym dotazom na externu sluzbu. Po uplynuti TTL
+/// sa kurz pri dalsom dotaze nacita znovu. Chyby sa neukladaju.
+pub struct CachingRateProvider<P> {
+    inner: P,
+    ttl: Duration,
+    cache: Mutex<HashMap<(String, String), (Decimal, Instant)>>,
+}
+
+impl<P: RateProvider> CachingRateProvider<P> {
+    /// Obali poskytovatela kurzov cache s danou platnostou
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<P: RateProvider> RateProvider for CachingRateProvider<P> {
+    async fn rate(&self, from: &str, to: &str) -> Result<Decimal, BankError> {
+        let key = (from.to_uppercase(), to.to_uppercase());
+
+        // Platny zaznam v cache - vratime ho bez dotazu na zdroj
+        if let Some((rate, fetched_at)) = self.cache.lock().unwrap().get(&key)
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(*rate);
+        }
+
+        let rate = self.inner.rate(&key.0, &key.1).await?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(key, (rate, Instant::now()));
+
+        Ok(rate)
+    }
+}
+
+/// Doplni k uctom zostatok prepocitany do meny zobrazenia
+///
+/// # Parametre
+/// - rates: zdroj kurzov
+/// - accounts: ucty, ulozeny zostatok a mena uctu sa nemenia
+/// - currency: mena zobrazenia (uz normalizovana, napr. USD)
+/// - rounding: sposob zaokruhlenia (Config::rounding_mode, ROUNDING_MODE)
+///
+/// # Poznamka
+/// Prepocitana suma je kurz * zostatok zaokruhleny na centy.
+///
+/// # Chyby
+/// - BankError::RateUnavailable: kurz pre menu niektoreho uctu nie je k dispozicii
+pub async fn convert_for_display(
+    rates: &dyn RateProvider,
+    accounts: &mut [PubAccount],
+    currency: &str,
+    rounding: RoundingMode,
+) -> Result<(), BankError> {
+    for account in accounts.iter_mut() {
+        let rate = rates.rate(&account.currency, currency).await?;
+        account.display_balance = Some(DisplayBalance {
+            currency: currency.to_string(),
+            amount: rounding.round(account.balance * rate, MONEY_SCALE),
+            rate,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Zdroj s pevnym kurzom, ktory pocita dotazy (EUR -> USD, ine dvojice chybaju)
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RateProvider for CountingProvider {
+        async fn rate(&self, from: &str, to: &str) -> Result<Decimal, BankError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match (from, to) {
+                ("EUR", "USD") => Ok(Decimal::new(108, 2)),
+                _ => Err(BankError::RateUnavailable {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                }),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn cached_rate_within_ttl_skips_inner_provider() {
+        let rates =
+            CachingRateProvider::new(CountingProvider::default(), Duration::from_secs(3600));
+
+        assert_eq!(
+            rates.rate("EUR", "USD").await.unwrap(),
+            Decimal::new(108, 2)
+        );
+        // Kluc cache nezavisi od velkosti pismen
+        assert_eq!(
+            rates.rate("eur", "usd").await.unwrap(),
+            Decimal::new(108, 2)
+        );
+
+        assert_eq!(rates.inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_rate_is_fetched_again() {
+        let rates =
+            CachingRateProvider::new(CountingProvider::default(), Duration::from_millis(20));
+
+        rates.rate("EUR", "USD").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        rates.rate("EUR", "USD").await.unwrap();
+
+        assert_eq!(rates.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let rates =
+            CachingRateProvider::new(CountingProvider::default(), Duration::from_secs(3600));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                rates.rate("EUR", "GBP").await,
+                Err(BankError::RateUnavailable { .. })
+            ));
+        }
+
+        assert_eq!(rates.inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
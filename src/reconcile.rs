@@ -0,0 +1,173 @@
+// reconcile.rs
+use crate::auth::ClientInfo;
+use crate::config::Config;
+use crate::crud::ensure_account_active;
+use crate::error::BankError;
+use crate::ids::new_transaction_id;
+use crate::models::{AccountStatus, ReconcileAdjustment, ReconcileEntry, Reconciliation};
+use rust_decimal::Decimal;
+use sqlx::{PgPool, query};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Pocet desatinnych miest zostatku uctu
+const BALANCE_SCALE: u32 = 2;
+
+/// Opravi zostatky uctov podla autoritativneho zdroja (administrator)
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - config: konfiguracia aplikacie (strategia ID transakcii)
+/// - entries: ucty s ocakavanym zostatkom
+/// - force: opravit aj zablokovane ucty
+/// - actor: kto opravu vykonal (zapise sa do auditu)
+/// - client: IP adresa a user agent volajuceho (zapisu sa do auditu)
+///
+/// # Navratova hodnota
+/// Vracia Reconciliation s opravou kazdeho uctu v poradi poziadavky
+///
+/// # Poznamka
+/// Kazdy rozdiel sa zapise ako vyrovnavacia transakcia s reconciliation_id:
+/// kladny ako vklad (from_account = NULL), zaporny ako odpis (to_account = NULL).
+/// Audit (reconciliations, reconciliation_items) aj zmeny zostatkov prebiehaju
+/// v jednej databazovej transakcii - pri akejkolvek chybe sa neopravi ziadny ucet.
+///
+/// # Chyby
+/// - BankError::InvalidBody: prazdny zoznam alebo opakovany ucet
+/// - BankError::InvalidAmount: ocakavany zostatok je zaporny alebo ma viac ako 2 desatinne miesta
+/// - BankError::AccountNotFound: niektory ucet neexistuje
+/// - BankError::AccountFrozen: ucet je zablokovany a force nie je zapnute
+/// - BankError::AccountClosed / BankError::AccountPending: ucet nie je mozne opravit
+pub async fn reconcile_balances(
+    pool: &PgPool,
+    config: &Config,
+    entries: &[ReconcileEntry],
+    force: bool,
+    actor: Option<&str>,
+    client: &ClientInfo,
+) -> Result<Reconciliation, BankError> {
+    if entries.is_empty() {
+        return Err(BankError::InvalidBody(
+            "at least one account is required".to_string(),
+        ));
+    }
+    let ids: Vec<Uuid> = entries.iter().map(|e| e.account_id).collect();
+    if ids.iter().collect::<HashSet<_>>().len() != ids.len() {
+        return Err(BankError::InvalidBody(
+            "accounts must not repeat".to_string(),
+        ));
+    }
+    if entries
+        .iter()
+        .any(|e| e.expected_balance < Decimal::ZERO || e.expected_balance.scale() > BALANCE_SCALE)
+    {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Zamknutie uctov v stalom poradi
+    let accounts: HashMap<Uuid, (Decimal, AccountStatus)> = query!(
+        r#"SELECT id, balance, status AS "status: AccountStatus"
+           FROM accounts WHERE id = ANY($1)
+           ORDER BY id
+           FOR UPDATE"#,
+        &ids
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, (row.balance, row.status)))
+    .collect();
+
+    let reconciliation_id = Uuid::new_v4();
+    query!(
+        "INSERT INTO reconciliations (id, actor, ip, user_agent, forced)
+         VALUES ($1, $2, $3, $4, $5)",
+        reconciliation_id,
+        actor,
+        client.ip,
+        client.user_agent,
+        force
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut adjustments = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let (previous, status) = *accounts
+            .get(&entry.account_id)
+            .ok_or(BankError::AccountNotFound)?;
+
+        if !(force && status == AccountStatus::Frozen) {
+            ensure_account_active(status)?;
+        }
+
+        let adjustment = entry.expected_balance - previous;
+        let transaction_id = if adjustment.is_zero() {
+            None
+        } else {
+            let (from_account, to_account) = if adjustment > Decimal::ZERO {
+                (None, Some(entry.account_id))
+            } else {
+                (Some(entry.account_id), None)
+            };
+
+            let id = query!(
+                "INSERT INTO transactions (id, from_account, to_account, amount, currency, reconciliation_id)
+                 SELECT $1, $2, $3, $4, currency, $5 FROM accounts WHERE id = $6
+                 RETURNING id",
+                new_transaction_id(config),
+                from_account,
+                to_account,
+                adjustment.abs(),
+                reconciliation_id,
+                entry.account_id
+            )
+            .fetch_one(&mut *tx)
+            .await?
+            .id;
+
+            query!(
+                "UPDATE accounts SET balance = $1 WHERE id = $2",
+                entry.expected_balance,
+                entry.account_id
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            Some(id)
+        };
+
+        query!(
+            "INSERT INTO reconciliation_items
+                 (reconciliation_id, account_id, previous_balance, expected_balance, transaction_id)
+             VALUES ($1, $2, $3, $4, $5)",
+            reconciliation_id,
+            entry.account_id,
+            previous,
+            entry.expected_balance,
+            transaction_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        adjustments.push(ReconcileAdjustment {
+            account_id: entry.account_id,
+            previous_balance: previous,
+            expected_balance: entry.expected_balance,
+            adjustment,
+            transaction_id,
+        });
+    }
+
+    tx.commit().await?;
+
+    Ok(Reconciliation {
+        id: reconciliation_id,
+        actor: actor.map(str::to_string),
+        forced: force,
+        adjustments,
+    })
+}
@@ -0,0 +1,262 @@
+// recurring.rs
+use crate::config::Config;
+use crate::crud::{TransferNotes, check_amount_bounds, execute_transfer};
+use crate::error::BankError;
+use crate::models::{CreateRecurringTransferRequest, Frequency, RecurringTransfer};
+use crate::rates::RateProvider;
+use crate::sanitize::sanitize_optional_note;
+use chrono::{DateTime, Duration as ChronoDuration, Months, Utc};
+use rust_decimal::Decimal;
+use sqlx::{Acquire, PgPool, query};
+use std::sync::Arc;
+use uuid::Uuid;
+
+impl Frequency {
+    /// Cas nasledujuceho behu po `from`
+    pub fn advance(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Frequency::Daily => from + ChronoDuration::days(1),
+            Frequency::Weekly => from + ChronoDuration::weeks(1),
+            Frequency::Monthly => from
+                .checked_add_months(Months::new(1))
+                .unwrap_or(from + ChronoDuration::days(30)),
+        }
+    }
+
+    /// Prvy termin po `now`, ktory nasleduje za naplanovanym behom `scheduled`
+    ///
+    /// Zmeskane terminy (napr. server nebezal) sa preskocia - prikaz sa
+    /// vykona raz a pokracuje dalsim buducim terminom.
+    pub fn next_after(&self, scheduled: DateTime<Utc>, now: DateTime<Utc>) -> DateTime<Utc> {
+        let mut next = self.advance(scheduled);
+        while next <= now {
+            next = self.advance(next);
+        }
+        next
+    }
+}
+
+/// Vytvori trvaly prikaz
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - user_id: UUID prihlaseneho pouzivatela
+/// - request: parametre prikazu
+///
+/// # Navratova hodnota
+/// Vracia vytvoreny RecurringTransfer
+///
+/// # Chyby
+/// - BankError::InvalidAmount: suma nie je kladna
+/// - BankError::InvalidNote: neplatna poznamka
+/// - BankError::AccountNotFound: ucet odosielatela neexistuje
+/// - BankError::Forbidden: ucet odosielatela patri inemu pouzivatelovi
+/// - BankError::ReferenceNotFound("account"): ucet prijemcu neexistuje
+/// - BankError::AmountOutOfBounds: suma je mimo rozsahu meny uctu (AMOUNT_BOUNDS)
+pub async fn create_recurring_transfer(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    request: &CreateRecurringTransferRequest,
+) -> Result<RecurringTransfer, BankError> {
+    if request.amount <= Decimal::ZERO {
+        return Err(BankError::InvalidAmount);
+    }
+
+    let sender_note = sanitize_optional_note(
+        request.sender_note.as_deref(),
+        config.transaction_note_max_length,
+    )?;
+    let receiver_note = sanitize_optional_note(
+        request.receiver_note.as_deref(),
+        config.transaction_note_max_length,
+    )?;
+
+    let owner = query!(
+        "SELECT user_id, currency FROM accounts WHERE id = $1",
+        request.from_account
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(BankError::AccountNotFound)?;
+
+    if owner.user_id != user_id {
+        return Err(BankError::Forbidden);
+    }
+    check_amount_bounds(config, &owner.currency, request.amount)?;
+
+    let row = query!(
+        r#"INSERT INTO recurring_transfers
+               (id, user_id, from_account, to_account, amount, sender_note, receiver_note,
+                frequency, next_run_at)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           RETURNING id, from_account, to_account, amount, frequency AS "frequency: Frequency",
+                     next_run_at, active, last_run_at, last_error"#,
+        Uuid::new_v4(),
+        user_id,
+        request.from_account,
+        request.to_account,
+        request.amount,
+        sender_note,
+        receiver_note,
+        request.frequency as Frequency,
+        request.start_at.unwrap_or_else(Utc::now)
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(RecurringTransfer {
+        id: row.id,
+        from_account: row.from_account,
+        to_account: row.to_account,
+        amount: row.amount,
+        frequency: row.frequency,
+        next_run_at: row.next_run_at,
+        active: row.active,
+        last_run_at: row.last_run_at,
+        last_error: row.last_error,
+    })
+}
+
+/// Ziska trvale prikazy pouzivatela
+///
+/// # Navratova hodnota
+/// Vracia aktivne aj zrusene prikazy zoradene podla vytvorenia
+pub async fn list_recurring_transfers(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<RecurringTransfer>, BankError> {
+    let rows = query!(
+        r#"SELECT id, from_account, to_account, amount, frequency AS "frequency: Frequency",
+                  next_run_at, active, last_run_at, last_error
+           FROM recurring_transfers
+           WHERE user_id = $1
+           ORDER BY created_at"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let transfers = rows
+        .into_iter()
+        .map(|row| RecurringTransfer {
+            id: row.id,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            frequency: row.frequency,
+            next_run_at: row.next_run_at,
+            active: row.active,
+            last_run_at: row.last_run_at,
+            last_error: row.last_error,
+        })
+        .collect();
+    Ok(transfers)
+}
+
+/// Vykona vsetky splatne trvale prikazy
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - config: konfiguracia (pravidla prevodov rovnake ako pri make_transaction)
+/// - rates: zdroj kurzov pre prikazy na ucet v inej mene
+///
+/// # Navratova hodnota
+/// Vracia pocet spracovanych prikazov (uspesnych aj neuspesnych)
+///
+/// # Poznamka
+/// Kazdy prikaz sa spracuje vo vlastnej databazovej transakcii so zamknutym
+/// riadkom (FOR UPDATE SKIP LOCKED), takze ho subezny beh nevykona dvakrat.
+/// Prevod bezi v savepointe - ak zlyha (napr. nedostatok penazi), stornuje sa
+/// len prevod, chyba sa zapise do last_error a prikaz sa posunie na dalsi termin.
+/// Zmeskane terminy sa dobiehaju len raz (Frequency::next_after).
+pub async fn run_due_recurring_transfers(
+    pool: &PgPool,
+    config: &Config,
+    rates: &dyn RateProvider,
+) -> Result<u64, sqlx::Error> {
+    let mut processed = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+
+        let Some(rule) = query!(
+            r#"SELECT id, from_account, to_account, amount, sender_note, receiver_note,
+                      frequency AS "frequency: Frequency", next_run_at
+               FROM recurring_transfers
+               WHERE active AND next_run_at <= NOW()
+               ORDER BY next_run_at
+               LIMIT 1
+               FOR UPDATE SKIP LOCKED"#
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            break;
+        };
+
+        let mut savepoint = tx.begin().await?;
+        let result = execute_transfer(
+            &mut savepoint,
+            config,
+            rates,
+            rule.from_account,
+            rule.to_account,
+            rule.amount,
+            TransferNotes {
+                sender: rule.sender_note.as_deref(),
+                receiver: rule.receiver_note.as_deref(),
+            },
+        )
+        .await;
+
+        let (transaction_id, error) = match result {
+            Ok(transaction) => {
+                savepoint.commit().await?;
+                (Some(transaction.id), None)
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                (None, Some(e.code()))
+            }
+        };
+
+        query!(
+            "UPDATE recurring_transfers
+             SET next_run_at = $1, last_run_at = NOW(),
+                 last_transaction_id = COALESCE($2, last_transaction_id), last_error = $3
+             WHERE id = $4",
+            rule.frequency.next_after(rule.next_run_at, Utc::now()),
+            transaction_id,
+            error,
+            rule.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        processed += 1;
+    }
+
+    Ok(processed)
+}
+
+/// Spusti spracovanie trvalych prikazov na pozadi
+///
+/// # Konfiguracia
+/// - RECURRING_POLL_SECS: interval kontroly splatnych prikazov (predvolene 60 s)
+pub fn spawn_recurring_job(config: Arc<Config>, rates: Arc<dyn RateProvider>, pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.recurring_poll_interval);
+
+        loop {
+            interval.tick().await;
+
+            match run_due_recurring_transfers(&pool, &config, rates.as_ref()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Processed {count} recurring transfers"),
+                Err(e) => tracing::error!("Recurring transfers failed: {e}"),
+            }
+        }
+    });
+}
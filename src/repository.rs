@@ -0,0 +1,870 @@
+// repository.rs
+use crate::auth::ClientInfo;
+use crate::config::Config;
+use crate::crud::TransferNotes;
+use crate::error::BankError;
+use crate::export::ExportStream;
+use crate::models::{
+    AccountBalance, AccountBatch, AccountFilter, AccountList, AccountMerge, AccountPin,
+    AccountStatement, AccountStats, AccountType, ApiKeyInfo, BalanceDiscrepancy, BatchReversal,
+    BatchTransferItem, CancelledTransfer, Counterparty, CreateRecurringTransferRequest,
+    CreatedApiKey, DepositBatch, DuplicatePolicy, EmailStatus, EmailVerificationSent,
+    FinalStatement, ImportReport, InterestRun, LoginHistoryPage, LoginHistoryQuery,
+    LowBalanceThreshold, MultiSourceTransfer, MultiSourceTransferRequest, NegativeBalanceAlert,
+    NegativeBalanceAlertQuery, Notification, NotificationPrefs, NotificationPrefsUpdate,
+    PubAccount, PublicUser, ReconcileEntry, Reconciliation, RecurringTransfer, RoundingAudit,
+    SortOrder, SystemStats, Transaction, TransactionPage, TransactionRequest, TransactionSearch,
+    TransactionSplit, TransactionTags, TransferBatch, TransferInitiation, TransferPreview,
+};
+use crate::rates::RateProvider;
+use crate::{
+    archive, balance_alerts, crud, export, interest, notifications, reconcile, recurring, statement,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Abstrakcia nad databazovymi operaciami pouzivanymi handlermi
+///
+/// Handlery pristupuju k datam cez `AppState::repo` (`Arc<dyn Repository>`),
+/// takze databazu je mozne nahradit inou implementaciou (napr. v testoch).
+/// Predvolena implementacia je PgRepository.
+///
+/// Metody zodpovedaju rovnomennym funkciam v moduloch crud, balance_alerts, interest,
+/// notifications, reconcile, recurring a statement - popis
+/// parametrov a chyb je pri nich.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn create_user(&self, username: &str, password: &str) -> Result<PublicUser, BankError>;
+
+    async fn import_users(
+        &self,
+        csv: &str,
+        policy: DuplicatePolicy,
+    ) -> Result<ImportReport, BankError>;
+
+    async fn get_user(&self, user_id: Uuid) -> Result<PublicUser, sqlx::Error>;
+
+    async fn get_user_by_username(&self, username: &str) -> Result<PublicUser, BankError>;
+
+    async fn delete_user(&self, user_id: Uuid, cancel_scheduled: bool) -> Result<u64, BankError>;
+
+    async fn login_user(&self, username: &str, password: &str) -> Result<PublicUser, String>;
+
+    async fn record_login_attempt(
+        &self,
+        username: &str,
+        success: bool,
+        client: &ClientInfo,
+    ) -> Result<(), BankError>;
+
+    async fn list_login_attempts(
+        &self,
+        user_id: Uuid,
+        query: &LoginHistoryQuery,
+    ) -> Result<LoginHistoryPage, BankError>;
+
+    async fn export_user_data(&self, user_id: Uuid) -> Result<ExportStream, BankError>;
+
+    async fn create_account(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        account_type: AccountType,
+        initial_deposit: Decimal,
+    ) -> Result<PubAccount, BankError>;
+
+    async fn decide_account_approval(
+        &self,
+        account_id: Uuid,
+        approve: bool,
+    ) -> Result<PubAccount, BankError>;
+
+    async fn get_account(
+        &self,
+        user_id: Uuid,
+        filter: &AccountFilter,
+    ) -> Result<AccountList, sqlx::Error>;
+
+    async fn get_accounts_batch(
+        &self,
+        user_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<AccountBatch, BankError>;
+
+    async fn get_account_by_id(&self, account_id: Uuid) -> Result<PubAccount, BankError>;
+
+    async fn get_account_by_number(&self, number: &str) -> Result<PubAccount, BankError>;
+
+    async fn close_account(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        sweep_to: Option<Uuid>,
+        cancel_scheduled: bool,
+    ) -> Result<FinalStatement, BankError>;
+
+    async fn merge_accounts(
+        &self,
+        user_id: Uuid,
+        source: Uuid,
+        target: Uuid,
+        cancel_scheduled: bool,
+    ) -> Result<AccountMerge, BankError>;
+
+    async fn set_low_balance_threshold(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        threshold: Option<Decimal>,
+    ) -> Result<LowBalanceThreshold, BankError>;
+
+    async fn get_account_balance(&self, account_id: Uuid) -> Result<AccountBalance, BankError>;
+
+    async fn get_balances_by_currency(
+        &self,
+        user_id: Uuid,
+    ) -> Result<BTreeMap<String, Decimal>, BankError>;
+
+    async fn total_balance(&self, user_id: Uuid) -> Result<Decimal, BankError>;
+
+    async fn add_money(
+        &self,
+        account_id: Uuid,
+        money: Decimal,
+        idempotency_key: Option<&str>,
+    ) -> Result<PubAccount, BankError>;
+
+    async fn add_money_batch(
+        &self,
+        credits: Vec<(Uuid, Decimal)>,
+    ) -> Result<DepositBatch, BankError>;
+
+    async fn make_transaction(
+        &self,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+        notes: TransferNotes<'_>,
+        idempotency_key: Option<&str>,
+        splits: &[TransactionSplit],
+    ) -> Result<Transaction, BankError>;
+
+    async fn set_account_pin(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        pin: Option<&str>,
+    ) -> Result<AccountPin, BankError>;
+
+    async fn verify_transfer_pin(
+        &self,
+        from_account: Uuid,
+        amount: Decimal,
+        pin: Option<&str>,
+    ) -> Result<(), BankError>;
+
+    async fn make_transaction_from_multiple(
+        &self,
+        user_id: Uuid,
+        sources: &[Uuid],
+        to_account: Uuid,
+        amount: Decimal,
+        sender_note: Option<&str>,
+        receiver_note: Option<&str>,
+    ) -> Result<MultiSourceTransfer, BankError>;
+
+    async fn make_batch_transfer(
+        &self,
+        user_id: Uuid,
+        from_account: Uuid,
+        transfers: &[BatchTransferItem],
+    ) -> Result<TransferBatch, BankError>;
+
+    async fn reverse_batch(&self, batch_id: Uuid) -> Result<BatchReversal, BankError>;
+
+    async fn preview_transfer(
+        &self,
+        user_id: Uuid,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+    ) -> Result<TransferPreview, BankError>;
+
+    async fn initiate_transfer(
+        &self,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+        sender_note: Option<&str>,
+        receiver_note: Option<&str>,
+    ) -> Result<TransferInitiation, BankError>;
+
+    async fn confirm_transfer(&self, token: &str) -> Result<Transaction, BankError>;
+
+    async fn cancel_transfer(
+        &self,
+        user_id: Uuid,
+        transfer_id: Uuid,
+    ) -> Result<CancelledTransfer, BankError>;
+
+    async fn request_email_verification(
+        &self,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<EmailVerificationSent, BankError>;
+
+    async fn verify_email(&self, token: &str) -> Result<EmailStatus, BankError>;
+
+    async fn get_transaction(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Transaction, BankError>;
+
+    async fn add_transaction_tag(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+        tag: &str,
+    ) -> Result<TransactionTags, BankError>;
+
+    async fn remove_transaction_tag(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+        tag: &str,
+    ) -> Result<TransactionTags, BankError>;
+
+    async fn filter_transactions_by_tag(
+        &self,
+        account_id: Uuid,
+        tag: &str,
+        transactions: &mut Vec<Transaction>,
+    ) -> Result<(), BankError>;
+
+    async fn create_recurring_transfer(
+        &self,
+        user_id: Uuid,
+        request: &CreateRecurringTransferRequest,
+    ) -> Result<RecurringTransfer, BankError>;
+
+    async fn get_notification_prefs(&self, user_id: Uuid) -> Result<NotificationPrefs, BankError>;
+
+    async fn update_notification_prefs(
+        &self,
+        user_id: Uuid,
+        prefs: &NotificationPrefsUpdate,
+    ) -> Result<NotificationPrefs, BankError>;
+
+    async fn list_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>, BankError>;
+
+    async fn list_recurring_transfers(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RecurringTransfer>, BankError>;
+
+    /// Historia transakcii uctu, pri `include_archived` aj z archivu
+    async fn get_transaction_history(
+        &self,
+        account_id: Uuid,
+        include_archived: bool,
+        order: SortOrder,
+    ) -> Result<Vec<Transaction>, BankError>;
+
+    async fn expand_transaction_parties(
+        &self,
+        transactions: &mut [Transaction],
+    ) -> Result<(), BankError>;
+
+    async fn account_stats(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStats, BankError>;
+
+    async fn system_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<SystemStats, BankError>;
+
+    async fn list_counterparties(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Counterparty>, BankError>;
+
+    async fn account_statement(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStatement, BankError>;
+
+    async fn check_balance_consistency(&self) -> Result<Vec<BalanceDiscrepancy>, BankError>;
+
+    async fn create_api_key(&self, user_id: Uuid) -> Result<CreatedApiKey, BankError>;
+
+    async fn revoke_api_key(&self, user_id: Uuid, key_id: Uuid) -> Result<(), BankError>;
+
+    async fn list_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKeyInfo>, BankError>;
+
+    async fn authenticate_api_key(&self, key: &str) -> Result<Uuid, BankError>;
+
+    async fn apply_interest(&self, rate: Decimal) -> Result<InterestRun, BankError>;
+
+    async fn rounding_audit(&self) -> Result<Vec<RoundingAudit>, BankError>;
+
+    async fn list_negative_balance_alerts(
+        &self,
+        query: &NegativeBalanceAlertQuery,
+    ) -> Result<Vec<NegativeBalanceAlert>, BankError>;
+
+    async fn search_transactions(
+        &self,
+        search: &TransactionSearch,
+    ) -> Result<TransactionPage, BankError>;
+
+    async fn reconcile_balances(
+        &self,
+        entries: &[ReconcileEntry],
+        force: bool,
+        actor: Option<&str>,
+        client: &ClientInfo,
+    ) -> Result<Reconciliation, BankError>;
+}
+
+/// Implementacia Repository nad PostgreSQL (funkcie z modulov crud, archive, export,
+/// interest, notifications, reconcile a recurring)
+///
+/// Vsetky funkcie pracuju nad zdielanym poolom servera (AppState::pool).
+/// Nastavenia (limity, pravidla prevodov, hashovanie hesiel...) beru funkcie
+/// z konfiguracie nacitanej pri starte, nie priamo z premennych prostredia.
+/// Prevody na ucty v inej mene sa prepocitavaju kurzami zo zdielaneho RateProvider.
+#[derive(Clone)]
+pub struct PgRepository {
+    pool: PgPool,
+    config: Arc<Config>,
+    rates: Arc<dyn RateProvider>,
+}
+
+impl PgRepository {
+    /// Vytvori repozitar nad zdielanym poolom, konfiguraciou a zdrojom kurzov
+    pub fn new(pool: PgPool, config: Arc<Config>, rates: Arc<dyn RateProvider>) -> Self {
+        Self {
+            pool,
+            config,
+            rates,
+        }
+    }
+}
+
+#[async_trait]
+impl Repository for PgRepository {
+    async fn create_user(&self, username: &str, password: &str) -> Result<PublicUser, BankError> {
+        crud::create_user(&self.pool, &self.config, username, password).await
+    }
+
+    async fn import_users(
+        &self,
+        csv: &str,
+        policy: DuplicatePolicy,
+    ) -> Result<ImportReport, BankError> {
+        crud::import_users(&self.pool, &self.config, csv, policy).await
+    }
+
+    async fn get_user(&self, user_id: Uuid) -> Result<PublicUser, sqlx::Error> {
+        crud::get_user(&self.pool, user_id).await
+    }
+
+    async fn get_user_by_username(&self, username: &str) -> Result<PublicUser, BankError> {
+        crud::get_user_by_username(&self.pool, username).await
+    }
+
+    async fn delete_user(&self, user_id: Uuid, cancel_scheduled: bool) -> Result<u64, BankError> {
+        crud::delete_user(&self.pool, user_id, cancel_scheduled).await
+    }
+
+    async fn login_user(&self, username: &str, password: &str) -> Result<PublicUser, String> {
+        crud::login_user(&self.pool, username, password).await
+    }
+
+    async fn record_login_attempt(
+        &self,
+        username: &str,
+        success: bool,
+        client: &ClientInfo,
+    ) -> Result<(), BankError> {
+        crud::record_login_attempt(&self.pool, username, success, client).await
+    }
+
+    async fn list_login_attempts(
+        &self,
+        user_id: Uuid,
+        query: &LoginHistoryQuery,
+    ) -> Result<LoginHistoryPage, BankError> {
+        crud::list_login_attempts(&self.pool, user_id, query).await
+    }
+
+    async fn export_user_data(&self, user_id: Uuid) -> Result<ExportStream, BankError> {
+        export::export_user_data(&self.pool, &self.config, user_id).await
+    }
+
+    async fn create_account(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        account_type: AccountType,
+        initial_deposit: Decimal,
+    ) -> Result<PubAccount, BankError> {
+        crud::create_account(
+            &self.pool,
+            &self.config,
+            user_id,
+            currency,
+            account_type,
+            initial_deposit,
+        )
+        .await
+    }
+
+    async fn decide_account_approval(
+        &self,
+        account_id: Uuid,
+        approve: bool,
+    ) -> Result<PubAccount, BankError> {
+        crud::decide_account_approval(&self.pool, account_id, approve).await
+    }
+
+    async fn get_account(
+        &self,
+        user_id: Uuid,
+        filter: &AccountFilter,
+    ) -> Result<AccountList, sqlx::Error> {
+        crud::get_account(&self.pool, &self.config, user_id, filter).await
+    }
+
+    async fn get_accounts_batch(
+        &self,
+        user_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<AccountBatch, BankError> {
+        crud::get_accounts_batch(&self.pool, &self.config, user_id, ids).await
+    }
+
+    async fn get_account_by_id(&self, account_id: Uuid) -> Result<PubAccount, BankError> {
+        crud::get_account_by_id(&self.pool, account_id).await
+    }
+
+    async fn get_account_by_number(&self, number: &str) -> Result<PubAccount, BankError> {
+        crud::get_account_by_number(&self.pool, &self.config, number).await
+    }
+
+    async fn close_account(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        sweep_to: Option<Uuid>,
+        cancel_scheduled: bool,
+    ) -> Result<FinalStatement, BankError> {
+        crud::close_account(
+            &self.pool,
+            &self.config,
+            account_id,
+            user_id,
+            sweep_to,
+            cancel_scheduled,
+        )
+        .await
+    }
+
+    async fn merge_accounts(
+        &self,
+        user_id: Uuid,
+        source: Uuid,
+        target: Uuid,
+        cancel_scheduled: bool,
+    ) -> Result<AccountMerge, BankError> {
+        crud::merge_accounts(
+            &self.pool,
+            &self.config,
+            user_id,
+            source,
+            target,
+            cancel_scheduled,
+        )
+        .await
+    }
+
+    async fn set_low_balance_threshold(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        threshold: Option<Decimal>,
+    ) -> Result<LowBalanceThreshold, BankError> {
+        crud::set_low_balance_threshold(&self.pool, account_id, user_id, threshold).await
+    }
+
+    async fn get_account_balance(&self, account_id: Uuid) -> Result<AccountBalance, BankError> {
+        crud::get_account_balance(&self.pool, account_id).await
+    }
+
+    async fn get_balances_by_currency(
+        &self,
+        user_id: Uuid,
+    ) -> Result<BTreeMap<String, Decimal>, BankError> {
+        crud::get_balances_by_currency(&self.pool, user_id).await
+    }
+
+    async fn total_balance(&self, user_id: Uuid) -> Result<Decimal, BankError> {
+        crud::total_balance(&self.pool, user_id).await
+    }
+
+    async fn add_money(
+        &self,
+        account_id: Uuid,
+        money: Decimal,
+        idempotency_key: Option<&str>,
+    ) -> Result<PubAccount, BankError> {
+        crud::add_money(&self.pool, &self.config, account_id, money, idempotency_key).await
+    }
+
+    async fn add_money_batch(
+        &self,
+        credits: Vec<(Uuid, Decimal)>,
+    ) -> Result<DepositBatch, BankError> {
+        crud::add_money_batch(&self.pool, &self.config, credits).await
+    }
+
+    async fn make_transaction(
+        &self,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+        notes: TransferNotes<'_>,
+        idempotency_key: Option<&str>,
+        splits: &[TransactionSplit],
+    ) -> Result<Transaction, BankError> {
+        let request = TransactionRequest {
+            from_account,
+            to_account,
+            amount,
+            sender_note: notes.sender.map(str::to_string),
+            receiver_note: notes.receiver.map(str::to_string),
+            idempotency_key: idempotency_key.map(str::to_string),
+            splits: splits.to_vec(),
+            pin: None,
+        };
+        crud::make_transaction(&self.pool, &self.config, self.rates.as_ref(), &request).await
+    }
+
+    async fn set_account_pin(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+        pin: Option<&str>,
+    ) -> Result<AccountPin, BankError> {
+        crud::set_account_pin(&self.pool, &self.config, account_id, user_id, pin).await
+    }
+
+    async fn verify_transfer_pin(
+        &self,
+        from_account: Uuid,
+        amount: Decimal,
+        pin: Option<&str>,
+    ) -> Result<(), BankError> {
+        crud::verify_transfer_pin(&self.pool, &self.config, from_account, amount, pin).await
+    }
+
+    async fn make_transaction_from_multiple(
+        &self,
+        user_id: Uuid,
+        sources: &[Uuid],
+        to_account: Uuid,
+        amount: Decimal,
+        sender_note: Option<&str>,
+        receiver_note: Option<&str>,
+    ) -> Result<MultiSourceTransfer, BankError> {
+        let request = MultiSourceTransferRequest {
+            sources: sources.to_vec(),
+            to_account,
+            amount,
+            sender_note: sender_note.map(str::to_string),
+            receiver_note: receiver_note.map(str::to_string),
+            pin: None,
+        };
+        crud::make_transaction_from_multiple(
+            &self.pool,
+            &self.config,
+            self.rates.as_ref(),
+            user_id,
+            &request,
+        )
+        .await
+    }
+
+    async fn make_batch_transfer(
+        &self,
+        user_id: Uuid,
+        from_account: Uuid,
+        transfers: &[BatchTransferItem],
+    ) -> Result<TransferBatch, BankError> {
+        crud::make_batch_transfer(
+            &self.pool,
+            &self.config,
+            self.rates.as_ref(),
+            user_id,
+            from_account,
+            transfers,
+        )
+        .await
+    }
+
+    async fn reverse_batch(&self, batch_id: Uuid) -> Result<BatchReversal, BankError> {
+        crud::reverse_batch(&self.pool, &self.config, batch_id).await
+    }
+
+    async fn preview_transfer(
+        &self,
+        user_id: Uuid,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+    ) -> Result<TransferPreview, BankError> {
+        crud::preview_transfer(
+            &self.pool,
+            &self.config,
+            self.rates.as_ref(),
+            user_id,
+            from_account,
+            to_account,
+            amount,
+        )
+        .await
+    }
+
+    async fn initiate_transfer(
+        &self,
+        from_account: Uuid,
+        to_account: Uuid,
+        amount: Decimal,
+        sender_note: Option<&str>,
+        receiver_note: Option<&str>,
+    ) -> Result<TransferInitiation, BankError> {
+        crud::initiate_transfer(
+            &self.pool,
+            &self.config,
+            self.rates.as_ref(),
+            from_account,
+            to_account,
+            amount,
+            TransferNotes {
+                sender: sender_note,
+                receiver: receiver_note,
+            },
+        )
+        .await
+    }
+
+    async fn confirm_transfer(&self, token: &str) -> Result<Transaction, BankError> {
+        crud::confirm_transfer(&self.pool, &self.config, self.rates.as_ref(), token).await
+    }
+
+    async fn cancel_transfer(
+        &self,
+        user_id: Uuid,
+        transfer_id: Uuid,
+    ) -> Result<CancelledTransfer, BankError> {
+        crud::cancel_transfer(&self.pool, user_id, transfer_id).await
+    }
+
+    async fn request_email_verification(
+        &self,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<EmailVerificationSent, BankError> {
+        crud::request_email_verification(&self.pool, &self.config, user_id, email).await
+    }
+
+    async fn verify_email(&self, token: &str) -> Result<EmailStatus, BankError> {
+        crud::verify_email(&self.pool, token).await
+    }
+
+    async fn get_transaction(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Transaction, BankError> {
+        crud::get_transaction(&self.pool, transaction_id, user_id).await
+    }
+
+    async fn add_transaction_tag(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+        tag: &str,
+    ) -> Result<TransactionTags, BankError> {
+        crud::add_transaction_tag(&self.pool, transaction_id, user_id, tag).await
+    }
+
+    async fn remove_transaction_tag(
+        &self,
+        transaction_id: Uuid,
+        user_id: Uuid,
+        tag: &str,
+    ) -> Result<TransactionTags, BankError> {
+        crud::remove_transaction_tag(&self.pool, transaction_id, user_id, tag).await
+    }
+
+    async fn filter_transactions_by_tag(
+        &self,
+        account_id: Uuid,
+        tag: &str,
+        transactions: &mut Vec<Transaction>,
+    ) -> Result<(), BankError> {
+        crud::filter_transactions_by_tag(&self.pool, account_id, tag, transactions).await
+    }
+
+    async fn create_recurring_transfer(
+        &self,
+        user_id: Uuid,
+        request: &CreateRecurringTransferRequest,
+    ) -> Result<RecurringTransfer, BankError> {
+        recurring::create_recurring_transfer(&self.pool, &self.config, user_id, request).await
+    }
+
+    async fn get_notification_prefs(&self, user_id: Uuid) -> Result<NotificationPrefs, BankError> {
+        notifications::get_notification_prefs(&self.pool, user_id).await
+    }
+
+    async fn update_notification_prefs(
+        &self,
+        user_id: Uuid,
+        prefs: &NotificationPrefsUpdate,
+    ) -> Result<NotificationPrefs, BankError> {
+        notifications::update_notification_prefs(&self.pool, user_id, prefs).await
+    }
+
+    async fn list_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>, BankError> {
+        notifications::list_notifications(&self.pool, user_id).await
+    }
+
+    async fn list_recurring_transfers(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RecurringTransfer>, BankError> {
+        recurring::list_recurring_transfers(&self.pool, user_id).await
+    }
+
+    async fn get_transaction_history(
+        &self,
+        account_id: Uuid,
+        include_archived: bool,
+        order: SortOrder,
+    ) -> Result<Vec<Transaction>, BankError> {
+        if include_archived {
+            archive::get_transaction_history_with_archive(&self.pool, account_id, order).await
+        } else {
+            crud::get_transaction_history(&self.pool, account_id, order).await
+        }
+    }
+
+    async fn expand_transaction_parties(
+        &self,
+        transactions: &mut [Transaction],
+    ) -> Result<(), BankError> {
+        crud::expand_transaction_parties(&self.pool, transactions).await
+    }
+
+    async fn account_stats(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStats, BankError> {
+        crud::account_stats(&self.pool, account_id, from, to).await
+    }
+
+    async fn system_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<SystemStats, BankError> {
+        crud::system_stats(&self.pool, from, to).await
+    }
+
+    async fn list_counterparties(
+        &self,
+        account_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Counterparty>, BankError> {
+        crud::list_counterparties(&self.pool, account_id, user_id).await
+    }
+
+    async fn account_statement(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<AccountStatement, BankError> {
+        statement::account_statement(&self.pool, &self.config, account_id, from, to).await
+    }
+
+    async fn check_balance_consistency(&self) -> Result<Vec<BalanceDiscrepancy>, BankError> {
+        crud::check_balance_consistency(&self.pool).await
+    }
+
+    async fn create_api_key(&self, user_id: Uuid) -> Result<CreatedApiKey, BankError> {
+        crud::create_api_key(&self.pool, user_id).await
+    }
+
+    async fn revoke_api_key(&self, user_id: Uuid, key_id: Uuid) -> Result<(), BankError> {
+        crud::revoke_api_key(&self.pool, user_id, key_id).await
+    }
+
+    async fn list_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKeyInfo>, BankError> {
+        crud::list_api_keys(&self.pool, user_id).await
+    }
+
+    async fn authenticate_api_key(&self, key: &str) -> Result<Uuid, BankError> {
+        crud::authenticate_api_key(&self.pool, key).await
+    }
+
+    async fn apply_interest(&self, rate: Decimal) -> Result<InterestRun, BankError> {
+        interest::apply_interest(&self.pool, &self.config, rate).await
+    }
+
+    async fn rounding_audit(&self) -> Result<Vec<RoundingAudit>, BankError> {
+        interest::rounding_audit(&self.pool).await
+    }
+
+    async fn list_negative_balance_alerts(
+        &self,
+        query: &NegativeBalanceAlertQuery,
+    ) -> Result<Vec<NegativeBalanceAlert>, BankError> {
+        balance_alerts::list_negative_balance_alerts(&self.pool, query).await
+    }
+
+    async fn search_transactions(
+        &self,
+        search: &TransactionSearch,
+    ) -> Result<TransactionPage, BankError> {
+        archive::search_transactions(&self.pool, search).await
+    }
+
+    async fn reconcile_balances(
+        &self,
+        entries: &[ReconcileEntry],
+        force: bool,
+        actor: Option<&str>,
+        client: &ClientInfo,
+    ) -> Result<Reconciliation, BankError> {
+        reconcile::reconcile_balances(&self.pool, &self.config, entries, force, actor, client).await
+    }
+}
@@ -0,0 +1,53 @@
+// request_id.rs
+use std::future::Future;
+use uuid::Uuid;
+
+/// Predvolena hlavicka s ID poziadavky (REQUEST_ID_HEADER)
+pub const DEFAULT_REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Najdlhsie prijate ID od klienta, dlhsie sa nahradi novym
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    /// ID prave spracuvanej poziadavky (nastavuje middleware request_id)
+    static REQUEST_ID: String;
+}
+
+/// ID poziadavky ulozene v rozsireniach poziadavky (`Extension<RequestId>`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Ci mozno ID od klienta prevziat - najviac 128 znakov, len pismena, cislice a `-_.:`
+///
+/// # Poznamka
+/// ID sa zapisuje do logov a vracia v hlavicke, preto sa iny obsah (medzery,
+/// riadiace znaky) neprebera a poziadavka dostane nove ID.
+pub fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// Nove ID poziadavky (casovo zoradene UUID v7)
+pub fn new_request_id() -> String {
+    Uuid::now_v7().to_string()
+}
+
+/// Spusti future s nastavenym ID poziadavky (pre current_request_id a log_prefix)
+pub async fn with_request_id<F: Future>(id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(id, future).await
+}
+
+/// ID prave spracuvanej poziadavky (None mimo poziadavky, napr. v ulohach na pozadi)
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Predpona riadku logu s ID poziadavky, napr. `[0190a5...] ` (mimo poziadavky prazdna)
+pub fn log_prefix() -> String {
+    current_request_id()
+        .map(|id| format!("[{id}] "))
+        .unwrap_or_default()
+}
@@ -0,0 +1,124 @@
+// rounding.rs
+use rust_decimal::{Decimal, RoundingStrategy};
+use sqlx::{Postgres, query};
+use std::str::FromStr;
+
+/// Pocet desatinnych miest penaznych stlpcov (NUMERIC(15, 2))
+pub const MONEY_SCALE: u32 = 2;
+
+/// Sposob zaokruhlenia vypocitanych sum (urok, prepocet meny, poplatky)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Polovica smerom od nuly (0.125 -> 0.13, -0.125 -> -0.13)
+    HalfUp,
+    /// Bankarske zaokruhlenie - polovica k parnej cifre (0.125 -> 0.12, 0.135 -> 0.14)
+    #[default]
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Zaokruhli sumu na dany pocet desatinnych miest tymto sposobom
+    pub fn round(self, amount: Decimal, scale: u32) -> Decimal {
+        let strategy = match self {
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+        };
+        amount.round_dp_with_strategy(scale, strategy)
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "half-up" => Ok(RoundingMode::HalfUp),
+            "half-even" | "bankers" => Ok(RoundingMode::HalfEven),
+            _ => Err(format!("expected half-up or half-even, got '{value}'")),
+        }
+    }
+}
+
+/// Zaokruhli vypocitanu sumu na centy a vrati aj zvysok
+///
+/// # Parametre
+/// - mode: sposob zaokruhlenia (Config::rounding_mode, ROUNDING_MODE)
+/// - exact: presna suma (napr. zostatok * sadzba)
+///
+/// # Navratova hodnota
+/// Dvojica (zaokruhlena suma, zvysok), pricom `zaokruhlena + zvysok == exact`.
+/// Zvysok patri na zaokruhlovaci ucet (record_remainder), aby knihy sedeli.
+///
+/// # Poznamka
+/// Zaokruhluje sa na najblizsi cent, zvysok preto moze byt aj zaporny -
+/// vtedy klientovi pripisany cent zaplati zaokruhlovaci ucet.
+pub fn split_remainder(mode: RoundingMode, exact: Decimal) -> (Decimal, Decimal) {
+    let rounded = mode.round(exact, MONEY_SCALE);
+    (rounded, exact - rounded)
+}
+
+/// Pripocita zvysok po zaokruhleni na zaokruhlovaci ucet danej meny
+///
+/// # Parametre
+/// - tx: databazova transakcia, v ktorej prebieha pripisanie (urok, poplatok)
+/// - currency: mena zvysku
+/// - remainder: zvysok zo split_remainder (moze byt aj zaporny)
+pub async fn record_remainder(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    currency: &str,
+    remainder: Decimal,
+) -> Result<(), sqlx::Error> {
+    query!(
+        "INSERT INTO rounding_account (currency, balance) VALUES ($1, $2)
+         ON CONFLICT (currency)
+         DO UPDATE SET balance = rounding_account.balance + EXCLUDED.balance, updated_at = NOW()",
+        currency,
+        remainder
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn half_up_rounds_midpoint_away_from_zero() {
+        let mode = RoundingMode::HalfUp;
+        assert_eq!(mode.round(dec("0.125"), MONEY_SCALE), dec("0.13"));
+        assert_eq!(mode.round(dec("0.135"), MONEY_SCALE), dec("0.14"));
+        assert_eq!(mode.round(dec("-0.125"), MONEY_SCALE), dec("-0.13"));
+    }
+
+    #[test]
+    fn half_even_rounds_midpoint_to_even_digit() {
+        let mode = RoundingMode::HalfEven;
+        assert_eq!(mode.round(dec("0.125"), MONEY_SCALE), dec("0.12"));
+        assert_eq!(mode.round(dec("0.135"), MONEY_SCALE), dec("0.14"));
+        assert_eq!(mode.round(dec("-0.125"), MONEY_SCALE), dec("-0.12"));
+    }
+
+    #[test]
+    fn remainder_completes_the_exact_amount() {
+        for mode in [RoundingMode::HalfUp, RoundingMode::HalfEven] {
+            let exact = dec("10.125");
+            let (rounded, remainder) = split_remainder(mode, exact);
+            assert_eq!(rounded + remainder, exact);
+        }
+    }
+
+    #[test]
+    fn parses_mode_names() {
+        assert_eq!("half-up".parse(), Ok(RoundingMode::HalfUp));
+        assert_eq!("HALF_EVEN".parse(), Ok(RoundingMode::HalfEven));
+        assert_eq!("bankers".parse(), Ok(RoundingMode::HalfEven));
+        assert!("down".parse::<RoundingMode>().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+// sanitize.rs
+use crate::error::BankError;
+
+/// Predvolena maximalna dlzka poznamky k transakcii (v znakoch)
+pub const DEFAULT_NOTE_MAX_LENGTH: usize = 140;
+
+/// Vycisti poznamku k transakcii pred ulozenim
+///
+/// # Parametre
+/// - note: poznamka od pouzivatela
+/// - max_length: najvacsia dlzka po vycisteni v znakoch
+///   (Config::transaction_note_max_length, TRANSACTION_NOTE_MAX_LENGTH)
+///
+/// # Navratova hodnota
+/// Vracia vycistenu poznamku, alebo None ak po vycisteni nic nezostalo
+///
+/// # Poznamka
+/// Riadiace znaky (vratane novych riadkov a tabulatorov) sa nahradia medzerou,
+/// viacnasobne medzery sa zluca do jednej a okraje sa orezu - poznamka
+/// tak nemoze podvrhnut riadok v logu.
+///
+/// # Chyby
+/// - BankError::InvalidNote: poznamka obsahuje nulovy bajt alebo je prilis dlha
+pub fn sanitize_note(note: &str, max_length: usize) -> Result<Option<String>, BankError> {
+    if note.contains('\0') {
+        return Err(BankError::InvalidNote);
+    }
+
+    let cleaned = note
+        .split(|c: char| c.is_control() || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if cleaned.chars().count() > max_length {
+        return Err(BankError::InvalidNote);
+    }
+
+    Ok((!cleaned.is_empty()).then_some(cleaned))
+}
+
+/// Vycisti volitelnu poznamku (pozri sanitize_note)
+pub fn sanitize_optional_note(
+    note: Option<&str>,
+    max_length: usize,
+) -> Result<Option<String>, BankError> {
+    note.map(|note| sanitize_note(note, max_length))
+        .transpose()
+        .map(Option::flatten)
+}
+
+/// Maximalna dlzka stitku transakcie (v znakoch)
+pub const TAG_MAX_LENGTH: usize = 32;
+
+/// Normalizuje stitok transakcie (napr. "Rent " -> "rent")
+///
+/// # Navratova hodnota
+/// Vracia stitok orezany a malymi pismenami
+///
+/// # Chyby
+/// - BankError::InvalidTag: stitok je prazdny, dlhsi ako TAG_MAX_LENGTH
+///   alebo obsahuje ine znaky nez pismena, cislice, '-' a '_'
+pub fn normalize_tag(tag: &str) -> Result<String, BankError> {
+    let tag = tag.trim().to_lowercase();
+
+    let valid_chars = tag
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+
+    if tag.is_empty() || tag.chars().count() > TAG_MAX_LENGTH || !valid_chars {
+        return Err(BankError::InvalidTag);
+    }
+
+    Ok(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_characters_and_repeated_spaces_are_collapsed() {
+        let note = sanitize_note("  rent\n\tfor\r\n  May\u{7}  ", DEFAULT_NOTE_MAX_LENGTH);
+        assert_eq!(note.unwrap().as_deref(), Some("rent for May"));
+        assert_eq!(
+            sanitize_note(" \n\t ", DEFAULT_NOTE_MAX_LENGTH).unwrap(),
+            None
+        );
+        assert_eq!(
+            sanitize_optional_note(None, DEFAULT_NOTE_MAX_LENGTH).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn null_bytes_and_long_notes_are_rejected() {
+        assert!(matches!(
+            sanitize_note("a\0b", DEFAULT_NOTE_MAX_LENGTH),
+            Err(BankError::InvalidNote)
+        ));
+
+        // Dlzka sa pocita v znakoch po vycisteni
+        assert!(sanitize_note("žltý  kôň", 9).is_ok());
+        assert!(matches!(
+            sanitize_note("žltý kôň!!", 9),
+            Err(BankError::InvalidNote)
+        ));
+    }
+
+    #[test]
+    fn tags_are_normalized() {
+        assert_eq!(normalize_tag(" Rent ").unwrap(), "rent");
+        for invalid in ["", "two words", &"x".repeat(TAG_MAX_LENGTH + 1)] {
+            assert!(matches!(normalize_tag(invalid), Err(BankError::InvalidTag)));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// state.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::rate_limit::RateLimiter;
+use crate::rates::{CachingRateProvider, RateProvider};
+use crate::repository::{PgRepository, Repository};
+use crate::transfer_limit::{TransferLimiter, TransferPermit};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Zdielany stav aplikacie dostupny vo vsetkych handleroch
+#[derive(Clone)]
+pub struct AppState {
+    /// Pristup k datam (predvolene PostgreSQL)
+    pub repo: Arc<dyn Repository>,
+    /// Zdroj menovych kurzov
+    pub rates: Arc<dyn RateProvider>,
+    /// Rezim udrzby - ak je zapnuty, zapisove operacie su odmietnute
+    pub read_only: Arc<AtomicBool>,
+    /// Maximalna doba spracovania poziadavky, potom sa vrati 504
+    pub request_timeout: Duration,
+    /// Maskovat cisla cudzich uctov v odpovediach (okrem administratora)
+    pub redact_account_numbers: bool,
+    /// Limity poziadaviek podla trasy, IP adresy a pouzivatela
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Sucasne beziace prevody podla pouzivatela
+    pub transfer_limiter: Arc<TransferLimiter>,
+    /// Konfiguracia nacitana pri starte
+    pub config: Arc<Config>,
+    /// Zdielany pool spojeni servera (operacie nad datami, ulohy na pozadi, kontrola stavu);
+    /// None pri stave zostavenom nad inym Repository (from_repository)
+    pub pool: Option<PgPool>,
+}
+
+impl AppState {
+    /// Zostavi stav aplikacie z overenej konfiguracie a zdielaneho poolu
+    ///
+    /// # Konfiguracia
+    /// Staticke kurzy (EXCHANGE_RATES), platnost kurzov, rezim len na citanie, casovy limit,
+    /// maskovanie cisel uctov, limity poziadaviek a sucasnych prevodov preberie z Config
+    pub fn new(config: Config, pool: PgPool) -> Self {
+        let config = Arc::new(config);
+        let rates = Self::rate_provider(&config);
+        let repo = Arc::new(PgRepository::new(
+            pool.clone(),
+            config.clone(),
+            rates.clone(),
+        ));
+        Self::build(config, rates, repo, Some(pool))
+    }
+
+    /// Zostavi stav nad inou implementaciou pristupu k datam, bez databazy
+    ///
+    /// # Poznamka
+    /// Urcene pre testy handlerov (napr. Repository v pamati) - kontrola stavu
+    /// (`/health`) bez poolu hlasi nedostupnu databazu.
+    pub fn from_repository(config: Config, repo: Arc<dyn Repository>) -> Self {
+        let config = Arc::new(config);
+        let rates = Self::rate_provider(&config);
+        Self::build(config, rates, repo, None)
+    }
+
+    /// Staticke kurzy z konfiguracie s cache platnou RATE_CACHE_TTL_SECS
+    fn rate_provider(config: &Config) -> Arc<dyn RateProvider> {
+        Arc::new(CachingRateProvider::new(
+            config.exchange_rates.clone(),
+            config.rate_cache_ttl,
+        ))
+    }
+
+    fn build(
+        config: Arc<Config>,
+        rates: Arc<dyn RateProvider>,
+        repo: Arc<dyn Repository>,
+        pool: Option<PgPool>,
+    ) -> Self {
+        Self {
+            repo,
+            rates,
+            read_only: Arc::new(AtomicBool::new(config.read_only)),
+            request_timeout: config.request_timeout,
+            redact_account_numbers: config.redact_account_numbers,
+            rate_limiter: Arc::new(RateLimiter::new(config.rate_limits.clone())),
+            transfer_limiter: Arc::new(TransferLimiter::new(config.max_concurrent_transfers)),
+            config,
+            pool,
+        }
+    }
+
+    /// Vrati ci je server v rezime len na citanie
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Zapne alebo vypne rezim len na citanie
+    pub fn set_read_only(&self, enabled: bool) {
+        self.read_only.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Obsadi miesto pre prevod pouzivatela (MAX_CONCURRENT_TRANSFERS_PER_USER)
+    ///
+    /// # Chyby
+    /// - BankError::TooManyTransfers: pouzivatel uz ma limit beziacich prevodov
+    pub fn transfer_permit(&self, user_id: Uuid) -> Result<TransferPermit, BankError> {
+        self.transfer_limiter.acquire(user_id)
+    }
+
+    /// Obsadi miesto pre prevod z uctu - zapocita sa vlastnikovi uctu
+    ///
+    /// # Poznamka
+    /// Vlastnik sa hlada len pri zapnutom limite, inak sa databaza nevola.
+    ///
+    /// # Chyby
+    /// - BankError::AccountNotFound: ucet neexistuje
+    /// - BankError::TooManyTransfers: vlastnik uz ma limit beziacich prevodov
+    pub async fn account_transfer_permit(
+        &self,
+        account_id: Uuid,
+    ) -> Result<TransferPermit, BankError> {
+        if !self.transfer_limiter.is_enabled() {
+            return Ok(TransferPermit::unlimited());
+        }
+        let account = self.repo.get_account_by_id(account_id).await?;
+        self.transfer_permit(account.user_id)
+    }
+}
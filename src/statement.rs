@@ -0,0 +1,293 @@
+// statement.rs
+use crate::config::Config;
+use crate::error::BankError;
+use crate::models::{AccountStatement, Transaction};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool, query};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Rezerva po polnoci, kym sa vytvori kontrolny bod za predchadzajuci den
+///
+/// Transakcia dostane created_at pri svojom zaciatku, ale viditelna je az po
+/// commite. Kontrolny bod sa preto pocita az ked uz ziadna transakcia zacata
+/// pred jeho casom nemoze bezat (limit poziadavky je vyrazne kratsi).
+const SNAPSHOT_SETTLE_MINUTES: i64 = 60;
+
+/// Nastavenia kontrolnych bodov zostatkov pre vypisy
+#[derive(Debug, Clone)]
+pub struct SnapshotConfig {
+    /// Interval medzi kontrolami, ci uz existuje kontrolny bod za dnesny den
+    pub interval: Duration,
+}
+
+impl SnapshotConfig {
+    /// Prevezme nastavenia z konfiguracie
+    ///
+    /// # Konfiguracia
+    /// - Config::balance_snapshots: zapne denne kontrolne body (BALANCE_SNAPSHOTS)
+    /// - Config::balance_snapshot_interval: interval kontroly (BALANCE_SNAPSHOT_INTERVAL_SECS)
+    ///
+    /// # Navratova hodnota
+    /// Vracia None ak su kontrolne body vypnute
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.balance_snapshots.then_some(Self {
+            interval: config.balance_snapshot_interval,
+        })
+    }
+
+    /// Cas najnovsieho kontrolneho bodu, ktory uz mozno vytvorit (polnoc UTC)
+    pub fn as_of(&self) -> DateTime<Utc> {
+        (Utc::now() - ChronoDuration::minutes(SNAPSHOT_SETTLE_MINUTES))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc()
+    }
+}
+
+/// Vytvori kontrolny bod zostatku vsetkych uctov k casu `as_of`
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - as_of: cas kontrolneho bodu - zahrnu sa transakcie vytvorene pred nim
+///
+/// # Navratova hodnota
+/// Vracia pocet novych kontrolnych bodov (ucty, ktore ho uz maju, sa preskocia)
+///
+/// # Poznamka
+/// Zostatok sa pocita od predchadzajuceho kontrolneho bodu uctu, takze kazdy beh
+/// prehra len transakcie od posledneho behu. Zapocitavaju sa aktivne aj
+/// archivovane transakcie, archivacia teda kontrolne body nemeni.
+pub async fn take_balance_snapshots(pool: &PgPool, as_of: DateTime<Utc>) -> Result<u64, BankError> {
+    let result = query!(
+        r#"INSERT INTO balance_snapshots (account_id, as_of, balance)
+           SELECT a.id, $1,
+                  COALESCE(prev.balance, 0)
+                  + COALESCE((
+                      SELECT COALESCE(SUM(credited) FILTER (WHERE m.to_account = a.id), 0)
+                             - COALESCE(SUM(amount) FILTER (WHERE m.from_account = a.id), 0)
+                      FROM (
+                          SELECT from_account, to_account, amount,
+                                 COALESCE(credited_amount, amount) AS credited, created_at
+                          FROM transactions
+                          UNION ALL
+                          SELECT from_account, to_account, amount,
+                                 COALESCE(credited_amount, amount) AS credited, created_at
+                          FROM archived_transactions
+                      ) m
+                      WHERE (m.from_account = a.id OR m.to_account = a.id)
+                        AND m.created_at < $1
+                        AND m.created_at >= COALESCE(prev.as_of, '-infinity')
+                  ), 0)
+           FROM accounts a
+           LEFT JOIN LATERAL (
+               SELECT as_of, balance FROM balance_snapshots s
+               WHERE s.account_id = a.id AND s.as_of < $1
+               ORDER BY as_of DESC
+               LIMIT 1
+           ) prev ON TRUE
+           ON CONFLICT (account_id, as_of) DO NOTHING"#,
+        as_of
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Zostatok uctu k danemu casu
+///
+/// # Parametre
+/// - conn: spojenie (alebo transakcia) databazy
+/// - account_id: UUID uctu
+/// - at: cas - zahrnu sa transakcie vytvorene pred nim
+/// - use_snapshots: zacat od najblizsieho kontrolneho bodu namiesto celej historie
+///
+/// # Navratova hodnota
+/// Vracia zostatok; oba sposoby vypoctu davaju rovnaky vysledok
+pub async fn balance_at(
+    conn: &mut PgConnection,
+    account_id: Uuid,
+    at: DateTime<Utc>,
+    use_snapshots: bool,
+) -> Result<Decimal, BankError> {
+    let row = query!(
+        r#"WITH checkpoint AS (
+               SELECT as_of, balance FROM balance_snapshots
+               WHERE $3 AND account_id = $1 AND as_of <= $2
+               ORDER BY as_of DESC
+               LIMIT 1
+           )
+           SELECT COALESCE((SELECT balance FROM checkpoint), 0)
+                  + COALESCE(SUM(credited) FILTER (WHERE to_account = $1), 0)
+                  - COALESCE(SUM(amount) FILTER (WHERE from_account = $1), 0) AS "balance!"
+           FROM (
+               SELECT from_account, to_account, amount,
+                      COALESCE(credited_amount, amount) AS credited, created_at
+               FROM transactions
+               UNION ALL
+               SELECT from_account, to_account, amount,
+                      COALESCE(credited_amount, amount) AS credited, created_at
+               FROM archived_transactions
+           ) m
+           WHERE (from_account = $1 OR to_account = $1)
+             AND created_at < $2
+             AND created_at >= COALESCE((SELECT as_of FROM checkpoint), '-infinity')"#,
+        account_id,
+        at,
+        use_snapshots
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(row.balance)
+}
+
+/// Vypis uctu za obdobie
+///
+/// # Parametre
+/// - pool: connection pool databazy
+/// - account_id: UUID uctu
+/// - from: zaciatok obdobia (vratane), None = od zalozenia uctu
+/// - to: koniec obdobia (bez neho), None = po sucasnost
+///
+/// # Navratova hodnota
+/// Vracia AccountStatement s pociatocnym a konecnym zostatkom a transakciami obdobia
+///
+/// # Poznamka
+/// Pociatocny zostatok sa pri zapnutych kontrolnych bodoch (BALANCE_SNAPSHOTS)
+/// pocita od najblizsieho kontrolneho bodu pred `from`, inak z celej historie.
+/// Cely vypis sa cita z jedneho snimku databazy (REPEATABLE READ), takze
+/// konecny zostatok vzdy sedi s transakciami vo vypise.
+///
+/// # Chyby
+/// - BankError::InvalidBody: from je po to
+/// - BankError::AccountNotFound: ucet neexistuje
+pub async fn account_statement(
+    pool: &PgPool,
+    config: &Config,
+    account_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<AccountStatement, BankError> {
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(BankError::InvalidBody(
+            "from must not be after to".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    let currency = query!("SELECT currency FROM accounts WHERE id = $1", account_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(BankError::AccountNotFound)?
+        .currency;
+
+    let opening_balance = match from {
+        Some(from) => balance_at(&mut tx, account_id, from, config.balance_snapshots).await?,
+        None => Decimal::ZERO,
+    };
+
+    let rows = query!(
+        r#"SELECT id AS "id!", reference, from_account, to_account,
+                  amount AS "amount!",
+                  credited_amount, credited_currency, rate, currency, created_at,
+                  CASE WHEN from_account = $1 THEN sender_note ELSE receiver_note END AS note
+           FROM (
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM transactions
+               UNION ALL
+               SELECT id, reference, from_account, to_account, amount,
+                      credited_amount, credited_currency, rate, currency, created_at,
+                      sender_note, receiver_note
+               FROM archived_transactions
+           ) t
+           WHERE (from_account = $1 OR to_account = $1)
+             AND ($2::timestamptz IS NULL OR created_at >= $2)
+             AND ($3::timestamptz IS NULL OR created_at < $3)
+           ORDER BY created_at, id"#,
+        account_id,
+        from,
+        to
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let transactions: Vec<Transaction> = rows
+        .into_iter()
+        .map(|row| Transaction {
+            id: row.id,
+            reference: row.reference,
+            from_account: row.from_account,
+            to_account: row.to_account,
+            amount: row.amount,
+            currency: row.currency,
+            credited_amount: row.credited_amount,
+            credited_currency: row.credited_currency,
+            rate: row.rate,
+            created_at: row.created_at,
+            note: row.note,
+            parties: None,
+            splits: Vec::new(),
+        })
+        .collect();
+
+    let total_credits: Decimal = transactions
+        .iter()
+        .filter(|t| t.to_account == Some(account_id))
+        .map(|t| t.credited_amount.unwrap_or(t.amount))
+        .sum();
+    let total_debits: Decimal = transactions
+        .iter()
+        .filter(|t| t.from_account == Some(account_id))
+        .map(|t| t.amount)
+        .sum();
+
+    Ok(AccountStatement {
+        account_id,
+        currency,
+        from,
+        to,
+        opening_balance,
+        closing_balance: opening_balance + total_credits - total_debits,
+        total_credits,
+        total_debits,
+        transactions,
+    })
+}
+
+/// Spusti vytvaranie dennych kontrolnych bodov na pozadi, ak su zapnute
+///
+/// Uloha bezi v nekonecnej slucke s intervalom zo SnapshotConfig; kontrolny bod
+/// za dany den sa vytvori len raz. Chyby sa len vypisu, dalsi beh sa pokusi znovu.
+pub fn spawn_snapshot_job(config: &Config, pool: PgPool) {
+    let Some(config) = SnapshotConfig::from_config(config) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+
+        loop {
+            interval.tick().await;
+
+            match take_balance_snapshots(&pool, config.as_of()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Created {count} balance snapshots"),
+                Err(e) => tracing::error!("Balance snapshots failed: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,90 @@
+// transfer_limit.rs
+use crate::error::BankError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Pocet prave beziacich prevodov podla pouzivatela
+type InFlight = Arc<Mutex<HashMap<Uuid, usize>>>;
+
+/// Obmedzenie sucasne beziacich prevodov jedneho pouzivatela
+///
+/// Funguje ako semafor podla user_id - kazdy prevod drzi jedno miesto
+/// (TransferPermit) az do svojho konca. Stav je len v pamati procesu,
+/// pri viacerych instanciach plati limit pre kazdu zvlast.
+#[derive(Debug)]
+pub struct TransferLimiter {
+    /// Najvyssi pocet sucasnych prevodov pouzivatela, 0 = bez obmedzenia
+    max_per_user: usize,
+    in_flight: InFlight,
+}
+
+/// Obsadene miesto pre prevod - uvolni sa pri drop-e (aj pri chybe alebo timeoute)
+#[derive(Debug)]
+pub struct TransferPermit {
+    slot: Option<(Uuid, InFlight)>,
+}
+
+impl TransferLimiter {
+    pub fn new(max_per_user: usize) -> Self {
+        Self {
+            max_per_user,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Ci je obmedzenie zapnute (MAX_CONCURRENT_TRANSFERS_PER_USER > 0)
+    pub fn is_enabled(&self) -> bool {
+        self.max_per_user > 0
+    }
+
+    /// Obsadi miesto pre prevod pouzivatela
+    ///
+    /// # Navratova hodnota
+    /// Vracia TransferPermit, ktory treba drzat pocas celeho prevodu
+    ///
+    /// # Chyby
+    /// - BankError::TooManyTransfers: pouzivatel uz ma max_per_user beziacich prevodov
+    pub fn acquire(&self, user_id: Uuid) -> Result<TransferPermit, BankError> {
+        if !self.is_enabled() {
+            return Ok(TransferPermit::unlimited());
+        }
+
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .expect("transfer limiter lock poisoned");
+        let count = in_flight.entry(user_id).or_insert(0);
+        if *count >= self.max_per_user {
+            return Err(BankError::TooManyTransfers);
+        }
+        *count += 1;
+
+        Ok(TransferPermit {
+            slot: Some((user_id, Arc::clone(&self.in_flight))),
+        })
+    }
+}
+
+impl TransferPermit {
+    /// Miesto bez obmedzenia (limit je vypnuty)
+    pub fn unlimited() -> Self {
+        Self { slot: None }
+    }
+}
+
+impl Drop for TransferPermit {
+    fn drop(&mut self) {
+        let Some((user_id, in_flight)) = self.slot.take() else {
+            return;
+        };
+        let mut in_flight = in_flight.lock().expect("transfer limiter lock poisoned");
+        if let Some(count) = in_flight.get_mut(&user_id) {
+            *count -= 1;
+            // Pouzivatel bez beziacich prevodov sa zo zoznamu odstrani
+            if *count == 0 {
+                in_flight.remove(&user_id);
+            }
+        }
+    }
+}
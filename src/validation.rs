@@ -0,0 +1,69 @@
+// validation.rs
+use crate::error::BankError;
+use crate::models::TransactionRequest;
+use axum::{
+    BoxError, Json, async_trait,
+    body::HttpBody,
+    extract::FromRequest,
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Telo poziadavky s povinnymi polami
+///
+/// Zoznam sa pouziva na presnu chybu pri chybajucich poliach namiesto
+/// vseobecnej chyby deserializacie.
+pub trait RequiredFields {
+    /// Nazvy povinnych poli v JSON tele
+    const REQUIRED_FIELDS: &'static [&'static str];
+}
+
+impl RequiredFields for TransactionRequest {
+    const REQUIRED_FIELDS: &'static [&'static str] = &["from_account", "to_account", "amount"];
+}
+
+/// Zisti, ktore povinne polia v JSON objekte chybaju (alebo su null)
+pub fn missing_fields(value: &Value, required: &'static [&'static str]) -> Vec<&'static str> {
+    required
+        .iter()
+        .copied()
+        .filter(|field| value.get(field).is_none_or(Value::is_null))
+        .collect()
+}
+
+/// JSON extractor, ktory pred deserializaciou overi povinne polia
+///
+/// # Vystupy (pri chybe)
+/// - 422 Unprocessable Entity: chybajuce povinne polia (MISSING_FIELDS, zoznam v `fields`)
+/// - 422 Unprocessable Entity: pole ma neplatnu hodnotu (INVALID_BODY)
+/// - ostatne chyby citania JSON (napr. chybajuci Content-Type) ako pri `axum::Json`
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for ValidatedJson<T>
+where
+    T: DeserializeOwned + RequiredFields,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<Value>::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let missing = missing_fields(&value, T::REQUIRED_FIELDS);
+        if !missing.is_empty() {
+            return Err(BankError::MissingFields(missing).into_response());
+        }
+
+        serde_json::from_value(value)
+            .map(ValidatedJson)
+            .map_err(|e| BankError::InvalidBody(e.to_string()).into_response())
+    }
+}
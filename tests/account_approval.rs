@@ -0,0 +1,84 @@
+// Schvalovanie novych uctov podla ACCOUNT_TYPES_REQUIRING_APPROVAL (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    AccountStatus, AccountType, BankError, add_money, create_account, decide_account_approval,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+fn config() -> bank_backend::Config {
+    common::config(&[("ACCOUNT_TYPES_REQUIRING_APPROVAL", "Business")])
+}
+
+#[tokio::test]
+async fn pending_account_is_approved_once() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let owner = common::new_account(&pool, &config, Decimal::ZERO).await;
+    assert_eq!(owner.status, AccountStatus::Active);
+
+    let pending = common::another_account(
+        &pool,
+        &config,
+        owner.user_id,
+        AccountType::Business,
+        Decimal::ZERO,
+    )
+    .await;
+    assert_eq!(pending.status, AccountStatus::Pending);
+
+    // Na neschvaleny ucet sa nevklada - ani pri otvoreni
+    let deposit = add_money(&pool, &config, pending.id, Decimal::TEN, None).await;
+    assert!(matches!(deposit, Err(BankError::AccountPending)));
+    let opening = create_account(
+        &pool,
+        &config,
+        owner.user_id,
+        Some("EUR"),
+        AccountType::Business,
+        Decimal::TEN,
+    )
+    .await;
+    assert!(matches!(opening, Err(BankError::AccountPending)));
+
+    let approved = decide_account_approval(&pool, pending.id, true)
+        .await
+        .unwrap();
+    assert_eq!(approved.status, AccountStatus::Active);
+    add_money(&pool, &config, pending.id, Decimal::TEN, None)
+        .await
+        .unwrap();
+
+    let again = decide_account_approval(&pool, pending.id, false).await;
+    assert!(matches!(again, Err(BankError::AccountNotPending)));
+}
+
+#[tokio::test]
+async fn rejected_account_is_closed_and_not_pending() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let owner = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let pending = common::another_account(
+        &pool,
+        &config,
+        owner.user_id,
+        AccountType::Business,
+        Decimal::ZERO,
+    )
+    .await;
+
+    let rejected = decide_account_approval(&pool, pending.id, false)
+        .await
+        .unwrap();
+    assert_eq!(rejected.status, AccountStatus::Closed);
+
+    let approve = decide_account_approval(&pool, pending.id, true).await;
+    assert!(matches!(approve, Err(BankError::AccountNotPending)));
+
+    // Aktivny ucet ani neexistujuci ucet na schvalenie necakaju
+    let active = decide_account_approval(&pool, owner.id, true).await;
+    assert!(matches!(active, Err(BankError::AccountNotPending)));
+    let missing = decide_account_approval(&pool, Uuid::new_v4(), true).await;
+    assert!(matches!(missing, Err(BankError::AccountNotFound)));
+}
@@ -0,0 +1,71 @@
+// Limit nezatvorenych uctov pouzivatela (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{AccountType, BankError, close_account, create_account};
+use futures_util::future::join_all;
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn account_over_limit_is_rejected_until_one_is_closed() {
+    let config = common::config(&[("MAX_ACCOUNTS_PER_USER", "2")]);
+    let pool = common::pool(&config).await;
+    let first = common::new_account(&pool, &config, Decimal::ZERO).await;
+    common::another_account(
+        &pool,
+        &config,
+        first.user_id,
+        AccountType::Savings,
+        Decimal::ZERO,
+    )
+    .await;
+
+    let create = || {
+        create_account(
+            &pool,
+            &config,
+            first.user_id,
+            Some("EUR"),
+            AccountType::Checking,
+            Decimal::ZERO,
+        )
+    };
+    let error = create().await.expect_err("limit reached");
+    assert!(matches!(error, BankError::LimitExceeded));
+    assert_eq!(error.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Zatvoreny ucet sa do limitu nepocita
+    close_account(&pool, &config, first.id, first.user_id, None, false)
+        .await
+        .unwrap();
+    create().await.expect("slot freed by closing");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_creation_does_not_exceed_limit() {
+    let config = common::config(&[("MAX_ACCOUNTS_PER_USER", "3")]);
+    let pool = common::pool(&config).await;
+    let user_id = common::new_account(&pool, &config, Decimal::ZERO)
+        .await
+        .user_id;
+
+    let results = join_all((0..6).map(|_| {
+        create_account(
+            &pool,
+            &config,
+            user_id,
+            Some("EUR"),
+            AccountType::Checking,
+            Decimal::ZERO,
+        )
+    }))
+    .await;
+
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+    assert!(
+        results
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .all(|e| matches!(e, BankError::LimitExceeded))
+    );
+}
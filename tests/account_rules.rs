@@ -0,0 +1,117 @@
+// Pravidla typov uctov - limit vyberov a precerpanie (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    AccountType, BankError, StaticRateProvider, close_account, make_transaction, merge_accounts,
+};
+use rust_decimal::Decimal;
+
+fn config() -> bank_backend::Config {
+    common::config(&[(
+        "ACCOUNT_TYPE_RULES",
+        "savings.monthly_withdrawals=2,checking.overdraft=100",
+    )])
+}
+
+#[tokio::test]
+async fn savings_withdrawals_are_capped_per_month_checking_is_not() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let savings =
+        common::new_account_of_type(&pool, &config, AccountType::Savings, Decimal::new(100, 0))
+            .await;
+    let checking = common::another_account(
+        &pool,
+        &config,
+        savings.user_id,
+        AccountType::Checking,
+        Decimal::new(100, 0),
+    )
+    .await;
+    let one = Decimal::ONE;
+
+    for _ in 0..2 {
+        make_transaction(
+            &pool,
+            &config,
+            &rates,
+            &common::transfer(savings.id, checking.id, one),
+        )
+        .await
+        .unwrap();
+    }
+    let third = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(savings.id, checking.id, one),
+    )
+    .await;
+    assert!(matches!(
+        third,
+        Err(BankError::WithdrawalLimitReached { .. })
+    ));
+
+    for _ in 0..5 {
+        make_transaction(
+            &pool,
+            &config,
+            &rates,
+            &common::transfer(checking.id, savings.id, one),
+        )
+        .await
+        .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn overdrawn_account_cannot_be_closed_or_merged() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let overdrawn = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let target = common::another_account(
+        &pool,
+        &config,
+        overdrawn.user_id,
+        AccountType::Checking,
+        Decimal::ZERO,
+    )
+    .await;
+
+    make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(overdrawn.id, target.id, Decimal::new(50, 0)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::balance(&pool, overdrawn.id).await,
+        Decimal::new(-50, 0)
+    );
+
+    let merged = merge_accounts(
+        &pool,
+        &config,
+        overdrawn.user_id,
+        overdrawn.id,
+        target.id,
+        false,
+    )
+    .await;
+    assert!(matches!(merged, Err(BankError::AccountOverdrawn)));
+
+    let closed = close_account(
+        &pool,
+        &config,
+        overdrawn.id,
+        overdrawn.user_id,
+        Some(target.id),
+        false,
+    )
+    .await;
+    assert!(matches!(closed, Err(BankError::AccountOverdrawn)));
+}
@@ -0,0 +1,69 @@
+// Statistiky uctu vratane archivovanych transakcii (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{AppState, StaticRateProvider, archive_transactions, make_transaction};
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+#[tokio::test]
+async fn stats_include_archived_transactions() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let account = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let other = common::new_account(&pool, &config, Decimal::new(50, 0)).await;
+
+    let sent = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(account.id, other.id, Decimal::new(30, 0)),
+    )
+    .await
+    .unwrap();
+    make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(other.id, account.id, Decimal::new(10, 0)),
+    )
+    .await
+    .unwrap();
+
+    // Odoslany prevod sa presunie do archivu
+    sqlx::query("UPDATE transactions SET created_at = '2000-01-01T00:00:00Z' WHERE id = $1")
+        .bind(sent.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let cutoff = Utc.with_ymd_and_hms(2000, 1, 2, 0, 0, 0).unwrap();
+    assert!(archive_transactions(&pool, cutoff, 1000).await.unwrap() >= 1);
+
+    // GET /accounts/:id/stats vracia vysledok repozitara ako JSON
+    let state = AppState::new(config, pool);
+    let stats = state
+        .repo
+        .account_stats(account.id, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        json!(stats),
+        json!({
+            "account_id": account.id,
+            "total_credits": "110",
+            "total_debits": "30",
+            "net_change": "80",
+            "transaction_count": 3,
+        })
+    );
+
+    // Okno po archivovanom prevode ho nezapocita
+    let recent = state
+        .repo
+        .account_stats(account.id, Some(cutoff), None)
+        .await
+        .unwrap();
+    assert_eq!(recent.total_debits, Decimal::ZERO);
+    assert_eq!(recent.transaction_count, 2);
+}
@@ -0,0 +1,54 @@
+// Hromadne nacitanie uctov (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{AccountType, BankError, get_accounts_batch};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn batch_splits_owned_foreign_and_missing_accounts() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let first = common::new_account(&pool, &config, Decimal::new(10, 0)).await;
+    let second = common::another_account(
+        &pool,
+        &config,
+        first.user_id,
+        AccountType::Savings,
+        Decimal::ZERO,
+    )
+    .await;
+    let foreign = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let missing = Uuid::new_v4();
+
+    let batch = get_accounts_batch(
+        &pool,
+        &config,
+        first.user_id,
+        &[second.id, foreign.id, missing, first.id, second.id],
+    )
+    .await
+    .unwrap();
+
+    // Vlastne ucty v poradi poziadavky, duplicity len raz
+    let owned: Vec<Uuid> = batch.accounts.iter().map(|a| a.id).collect();
+    assert_eq!(owned, vec![second.id, first.id]);
+    assert_eq!(batch.accounts[1].balance, Decimal::new(10, 0));
+    assert_eq!(batch.forbidden, vec![foreign.id]);
+    assert_eq!(batch.not_found, vec![missing]);
+}
+
+#[tokio::test]
+async fn batch_over_limit_is_rejected_counting_duplicates() {
+    let config = common::config(&[("MAX_ACCOUNTS_LISTED", "3")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let at_limit = get_accounts_batch(&pool, &config, account.user_id, &[account.id; 3])
+        .await
+        .unwrap();
+    assert_eq!(at_limit.accounts.len(), 1);
+
+    let over = get_accounts_batch(&pool, &config, account.user_id, &[account.id; 4]).await;
+    assert!(matches!(over, Err(BankError::LimitExceeded)));
+}
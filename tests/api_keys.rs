@@ -0,0 +1,55 @@
+// Rotacia a zrusenie API klucov (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, authenticate_api_key, create_api_key, list_api_keys, revoke_api_key,
+};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn revoking_the_old_key_after_rotation_invalidates_only_it() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let user_id = common::new_account(&pool, &config, Decimal::ZERO)
+        .await
+        .user_id;
+    let other_user = common::new_account(&pool, &config, Decimal::ZERO)
+        .await
+        .user_id;
+
+    let old = create_api_key(&pool, user_id).await.unwrap();
+    let new = create_api_key(&pool, user_id).await.unwrap();
+    assert_ne!(old.key, new.key);
+    assert!(new.key.starts_with(&new.prefix));
+
+    // Po vytvoreni noveho kluca plati do zrusenia aj stary
+    assert_eq!(
+        authenticate_api_key(&pool, &old.key).await.unwrap(),
+        user_id
+    );
+    assert_eq!(
+        authenticate_api_key(&pool, &new.key).await.unwrap(),
+        user_id
+    );
+
+    let foreign = revoke_api_key(&pool, other_user, old.id).await;
+    assert!(matches!(foreign, Err(BankError::ApiKeyNotFound)));
+
+    revoke_api_key(&pool, user_id, old.id).await.unwrap();
+    let rejected = authenticate_api_key(&pool, &old.key).await;
+    assert!(matches!(rejected, Err(BankError::Unauthorized)));
+    assert_eq!(
+        authenticate_api_key(&pool, &new.key).await.unwrap(),
+        user_id
+    );
+
+    let again = revoke_api_key(&pool, user_id, old.id).await;
+    assert!(matches!(again, Err(BankError::ApiKeyNotFound)));
+
+    let keys = list_api_keys(&pool, user_id).await.unwrap();
+    assert_eq!(keys.len(), 2);
+    let revoked = keys.iter().find(|k| k.id == old.id).unwrap();
+    assert!(revoked.revoked_at.is_some());
+    let active = keys.iter().find(|k| k.id == new.id).unwrap();
+    assert!(active.revoked_at.is_none());
+}
@@ -0,0 +1,95 @@
+// Archivacia starych transakcii podla Config (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    AppState, ArchivalConfig, SortOrder, StaticRateProvider, archive_transactions, make_transaction,
+};
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+#[test]
+fn archival_is_configured_from_config() {
+    assert!(ArchivalConfig::from_config(&common::config(&[])).is_none());
+
+    let config = common::config(&[
+        ("TRANSACTION_RETENTION_DAYS", "30"),
+        ("ARCHIVE_BATCH_SIZE", "2"),
+        ("ARCHIVE_INTERVAL_SECS", "60"),
+    ]);
+    let archival = ArchivalConfig::from_config(&config).expect("archival enabled");
+    assert_eq!(archival.retention_days, 30);
+    assert_eq!(archival.batch_size, 2);
+    assert_eq!(archival.interval, Duration::from_secs(60));
+}
+
+#[tokio::test]
+async fn old_transactions_move_to_archive_and_stay_in_history() {
+    let config = common::config(&[
+        ("TRANSACTION_RETENTION_DAYS", "30"),
+        ("ARCHIVE_BATCH_SIZE", "1"),
+    ]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let account = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let other = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let old = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(account.id, other.id, Decimal::new(10, 0)),
+    )
+    .await
+    .unwrap();
+    let recent = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(account.id, other.id, Decimal::new(20, 0)),
+    )
+    .await
+    .unwrap();
+    sqlx::query("UPDATE transactions SET created_at = NOW() - INTERVAL '60 days' WHERE id = $1")
+        .bind(old.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Rovnaky beh ako uloha na pozadi (davky po ARCHIVE_BATCH_SIZE)
+    let archival = ArchivalConfig::from_config(&config).unwrap();
+    assert!(
+        archive_transactions(&pool, archival.cutoff(), archival.batch_size)
+            .await
+            .unwrap()
+            >= 1
+    );
+
+    let location: (bool, bool) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM transactions WHERE id = $1),
+                EXISTS (SELECT 1 FROM archived_transactions WHERE id = $1)",
+    )
+    .bind(old.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(location, (false, true));
+
+    let state = AppState::new(config, pool);
+    let active = state
+        .repo
+        .get_transaction_history(account.id, false, SortOrder::Desc)
+        .await
+        .unwrap();
+    assert!(active.iter().all(|t| t.id != old.id));
+    assert!(active.iter().any(|t| t.id == recent.id));
+
+    let full = state
+        .repo
+        .get_transaction_history(account.id, true, SortOrder::Desc)
+        .await
+        .unwrap();
+    let archived = full.iter().find(|t| t.id == old.id).expect("archived row");
+    assert_eq!(archived.amount, Decimal::new(10, 0));
+    assert_eq!(archived.reference, old.reference);
+    assert_eq!(full.last().map(|t| t.id), Some(old.id));
+}
@@ -0,0 +1,101 @@
+// Hromadne prevody a storno celej davky (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, BatchTransferItem, StaticRateProvider, make_batch_transfer, make_transaction,
+    reverse_batch,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Polozka davky bez poznamok
+fn item(to_account: Uuid, amount: i64) -> BatchTransferItem {
+    BatchTransferItem {
+        to_account,
+        amount: Decimal::new(amount, 0),
+        sender_note: None,
+        receiver_note: None,
+    }
+}
+
+#[tokio::test]
+async fn reversing_a_batch_restores_all_balances_once() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let first = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let second = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let batch = make_batch_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.user_id,
+        sender.id,
+        &[item(first.id, 30), item(second.id, 20)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(batch.total, Decimal::new(50, 0));
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(50, 0));
+
+    let reversal = reverse_batch(&pool, &config, batch.batch_id).await.unwrap();
+    assert_eq!(reversal.total, Decimal::new(50, 0));
+    assert_eq!(reversal.transactions.len(), 2);
+    assert!(
+        reversal
+            .transactions
+            .iter()
+            .all(|t| t.to_account == Some(sender.id))
+    );
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(100, 0)
+    );
+    assert_eq!(common::balance(&pool, first.id).await, Decimal::ZERO);
+    assert_eq!(common::balance(&pool, second.id).await, Decimal::ZERO);
+
+    let again = reverse_batch(&pool, &config, batch.batch_id).await;
+    assert!(matches!(again, Err(BankError::BatchAlreadyReversed)));
+    let missing = reverse_batch(&pool, &config, Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(BankError::BatchNotFound)));
+}
+
+#[tokio::test]
+async fn batch_reversal_is_all_or_nothing() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let first = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let second = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let elsewhere = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let batch = make_batch_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.user_id,
+        sender.id,
+        &[item(first.id, 30), item(second.id, 20)],
+    )
+    .await
+    .unwrap();
+
+    // Druhy prijemca peniaze minul - storno jeho prevodu nemoze prebehnut
+    make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(second.id, elsewhere.id, Decimal::new(15, 0)),
+    )
+    .await
+    .unwrap();
+
+    let reversal = reverse_batch(&pool, &config, batch.batch_id).await;
+    assert!(matches!(reversal, Err(BankError::InsufficientFunds)));
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(50, 0));
+    assert_eq!(common::balance(&pool, first.id).await, Decimal::new(30, 0));
+    assert_eq!(common::balance(&pool, second.id).await, Decimal::new(5, 0));
+}
@@ -0,0 +1,82 @@
+// Zrusenie cakajuceho prevodu a trvaleho prikazu (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, CancelledTransferKind, CreateRecurringTransferRequest, Frequency,
+    StaticRateProvider, TransferInitiation, TransferNotes, cancel_transfer, confirm_transfer,
+    create_recurring_transfer, initiate_transfer,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn cancelled_pending_transfer_cannot_be_confirmed() {
+    let config = common::config(&[("TRANSFER_CONFIRMATION_THRESHOLD", "50")]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let initiation = initiate_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.id,
+        recipient.id,
+        Decimal::new(80, 0),
+        TransferNotes::default(),
+    )
+    .await
+    .unwrap();
+    let TransferInitiation::Pending { id, token, .. } = initiation else {
+        panic!("transfer above threshold must wait for confirmation");
+    };
+
+    let foreign = cancel_transfer(&pool, recipient.user_id, id).await;
+    assert!(matches!(foreign, Err(BankError::Forbidden)));
+
+    let cancelled = cancel_transfer(&pool, sender.user_id, id).await.unwrap();
+    assert_eq!(cancelled.kind, CancelledTransferKind::Pending);
+    assert_eq!(cancelled.amount, Decimal::new(80, 0));
+
+    let again = cancel_transfer(&pool, sender.user_id, id).await;
+    assert!(matches!(again, Err(BankError::TransferNotPending)));
+    let confirmed = confirm_transfer(&pool, &config, &rates, &token).await;
+    assert!(matches!(confirmed, Err(BankError::TransferNotFound)));
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(100, 0)
+    );
+    assert_eq!(common::balance(&pool, recipient.id).await, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn cancelling_recurring_transfer_deactivates_it() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let request = CreateRecurringTransferRequest {
+        from_account: sender.id,
+        to_account: recipient.id,
+        amount: Decimal::new(10, 0),
+        frequency: Frequency::Weekly,
+        start_at: None,
+        sender_note: None,
+        receiver_note: None,
+    };
+    let recurring = create_recurring_transfer(&pool, &config, sender.user_id, &request)
+        .await
+        .unwrap();
+
+    let cancelled = cancel_transfer(&pool, sender.user_id, recurring.id)
+        .await
+        .unwrap();
+    assert_eq!(cancelled.kind, CancelledTransferKind::Recurring);
+    assert_eq!(cancelled.to_account, recipient.id);
+
+    let again = cancel_transfer(&pool, sender.user_id, recurring.id).await;
+    assert!(matches!(again, Err(BankError::TransferNotPending)));
+    let missing = cancel_transfer(&pool, sender.user_id, Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(BankError::TransferNotFound)));
+}
@@ -0,0 +1,102 @@
+// Spolocne pomocne funkcie integracnych testov (vyzaduju DATABASE_URL)
+#![allow(dead_code)]
+
+use bank_backend::{
+    AccountType, Config, PubAccount, TransactionRequest, create_account, create_user, pool_options,
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Konfiguracia s predvolenymi hodnotami a zadanymi premennymi
+///
+/// DATABASE_URL sa berie z prostredia (alebo .env), ostatne premenne prostredia sa ignoruju.
+pub fn config(vars: &[(&str, &str)]) -> Config {
+    dotenv::dotenv().ok();
+    Config::from_lookup(|name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+            .or_else(|| (name == "DATABASE_URL").then(|| std::env::var(name).ok())?)
+    })
+    .expect("valid test config")
+}
+
+/// Pool spojeni testu nastaveny z konfiguracie (rovnako ako pool servera)
+pub async fn pool(config: &Config) -> PgPool {
+    pool_options(config)
+        .connect(&config.database_url)
+        .await
+        .expect("connect test database")
+}
+
+/// Novy pouzivatel s jednym beznym EUR uctom a danym pociatocnym vkladom
+pub async fn new_account(pool: &PgPool, config: &Config, initial_deposit: Decimal) -> PubAccount {
+    new_account_of_type(pool, config, AccountType::Checking, initial_deposit).await
+}
+
+/// Novy pouzivatel s jednym EUR uctom daneho typu
+pub async fn new_account_of_type(
+    pool: &PgPool,
+    config: &Config,
+    account_type: AccountType,
+    initial_deposit: Decimal,
+) -> PubAccount {
+    let name = format!("test_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let user = create_user(pool, config, &name, "Test-password-1")
+        .await
+        .expect("create test user");
+    create_account(
+        pool,
+        config,
+        user.id,
+        Some("EUR"),
+        account_type,
+        initial_deposit,
+    )
+    .await
+    .expect("create test account")
+}
+
+/// Dalsi EUR ucet daneho typu pre existujuceho pouzivatela
+pub async fn another_account(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    account_type: AccountType,
+    initial_deposit: Decimal,
+) -> PubAccount {
+    create_account(
+        pool,
+        config,
+        user_id,
+        Some("EUR"),
+        account_type,
+        initial_deposit,
+    )
+    .await
+    .expect("create test account")
+}
+
+/// Poziadavka na priamy prevod bez poznamok a kluca idempotencie
+pub fn transfer(from_account: Uuid, to_account: Uuid, amount: Decimal) -> TransactionRequest {
+    TransactionRequest {
+        from_account,
+        to_account,
+        amount,
+        sender_note: None,
+        receiver_note: None,
+        idempotency_key: None,
+        splits: Vec::new(),
+        pin: None,
+    }
+}
+
+/// Aktualny zostatok uctu
+pub async fn balance(pool: &PgPool, account_id: Uuid) -> Decimal {
+    sqlx::query_scalar("SELECT balance FROM accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .expect("account balance")
+}
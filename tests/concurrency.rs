@@ -0,0 +1,123 @@
+// Subezne vklady a prevody na tych istych uctoch (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{BankError, StaticRateProvider, add_money, delete_user, make_transaction};
+use futures_util::{StreamExt, stream};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Pocet operacii kazdeho druhu
+const ROUNDS: usize = 25;
+/// Najviac sucasne beziacich operacii
+const PARALLELISM: usize = 16;
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Deposit(Uuid, Decimal),
+    Transfer(Uuid, Uuid, Decimal),
+}
+
+/// Zostatok podla zaznamov transakcii (prijmy minus odoslane platby vratane archivu)
+async fn ledger_balance(pool: &PgPool, account_id: Uuid) -> Decimal {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(CASE WHEN to_account = $1
+                                  THEN COALESCE(credited_amount, amount) ELSE 0 END), 0)
+              - COALESCE(SUM(CASE WHEN from_account = $1 THEN amount ELSE 0 END), 0)
+         FROM (SELECT from_account, to_account, amount, credited_amount FROM transactions
+               UNION ALL
+               SELECT from_account, to_account, amount, credited_amount
+               FROM archived_transactions) t
+         WHERE from_account = $1 OR to_account = $1",
+    )
+    .bind(account_id)
+    .fetch_one(pool)
+    .await
+    .expect("ledger balance")
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn parallel_deposits_and_transfers_keep_balances_consistent() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let a = common::new_account(&pool, &config, Decimal::new(1000, 0)).await;
+    let b = common::new_account(&pool, &config, Decimal::new(1000, 0)).await;
+
+    let mut operations = Vec::new();
+    for _ in 0..ROUNDS {
+        operations.push(Operation::Deposit(a.id, Decimal::new(5, 0)));
+        operations.push(Operation::Deposit(b.id, Decimal::new(5, 0)));
+        operations.push(Operation::Transfer(a.id, b.id, Decimal::new(3, 0)));
+        operations.push(Operation::Transfer(b.id, a.id, Decimal::new(2, 0)));
+    }
+
+    let results: Vec<_> = stream::iter(operations)
+        .map(|operation| {
+            let (pool, config, rates) = (&pool, &config, &rates);
+            async move {
+                match operation {
+                    Operation::Deposit(account, amount) => {
+                        add_money(pool, config, account, amount, None)
+                            .await
+                            .map(|_| ())
+                    }
+                    Operation::Transfer(from, to, amount) => {
+                        let request = common::transfer(from, to, amount);
+                        make_transaction(pool, config, rates, &request)
+                            .await
+                            .map(|_| ())
+                    }
+                }
+            }
+        })
+        .buffer_unordered(PARALLELISM)
+        .collect()
+        .await;
+    for result in results {
+        result.expect("concurrent operation");
+    }
+
+    let rounds = Decimal::from(ROUNDS);
+    let expected_a = Decimal::new(1000, 0) + rounds * Decimal::new(5 - 3 + 2, 0);
+    let expected_b = Decimal::new(1000, 0) + rounds * Decimal::new(5 + 3 - 2, 0);
+
+    assert_eq!(common::balance(&pool, a.id).await, expected_a);
+    assert_eq!(common::balance(&pool, b.id).await, expected_b);
+    assert_eq!(ledger_balance(&pool, a.id).await, expected_a);
+    assert_eq!(ledger_balance(&pool, b.id).await, expected_b);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn delete_user_racing_incoming_transfer_never_loses_money() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(1000, 0)).await;
+    let amount = Decimal::new(10, 0);
+
+    let mut expected_sender = Decimal::new(1000, 0);
+    for _ in 0..ROUNDS {
+        let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+        let request = common::transfer(sender.id, recipient.id, amount);
+
+        let (deleted, transferred) = tokio::join!(
+            delete_user(&pool, recipient.user_id, false),
+            make_transaction(&pool, &config, &rates, &request),
+        );
+
+        // Bud prevod dobehne a zmazanie odmietne ucet s peniazmi, alebo naopak
+        match (deleted, transferred) {
+            (Err(BankError::AccountHasFunds), Ok(_)) => {
+                expected_sender -= amount;
+                assert_eq!(common::balance(&pool, recipient.id).await, amount);
+            }
+            (Ok(1), Err(BankError::AccountNotFound)) => {}
+            (deleted, transferred) => {
+                panic!("unexpected outcome: {deleted:?} / {transferred:?}")
+            }
+        }
+        assert_eq!(common::balance(&pool, sender.id).await, expected_sender);
+    }
+    assert_eq!(ledger_balance(&pool, sender.id).await, expected_sender);
+}
@@ -0,0 +1,189 @@
+// Prevod s potvrdenim od TRANSFER_CONFIRMATION_THRESHOLD (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, BatchTransferItem, MultiSourceTransferRequest, StaticRateProvider,
+    TransferInitiation, TransferNotes, confirm_transfer, initiate_transfer, make_batch_transfer,
+    make_transaction, make_transaction_from_multiple,
+};
+use rust_decimal::Decimal;
+
+fn config() -> bank_backend::Config {
+    common::config(&[("TRANSFER_CONFIRMATION_THRESHOLD", "100")])
+}
+
+fn pending_token(initiation: TransferInitiation) -> String {
+    match initiation {
+        TransferInitiation::Pending { token, .. } => token,
+        TransferInitiation::Completed { .. } => panic!("transfer should wait for confirmation"),
+    }
+}
+
+#[tokio::test]
+async fn direct_transfers_at_threshold_require_confirmation() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(500, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let amount = Decimal::new(100, 0);
+
+    let direct = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(sender.id, recipient.id, amount),
+    )
+    .await;
+    assert!(matches!(direct, Err(BankError::ConfirmationRequired)));
+
+    let multi = make_transaction_from_multiple(
+        &pool,
+        &config,
+        &rates,
+        sender.user_id,
+        &MultiSourceTransferRequest {
+            sources: vec![sender.id],
+            to_account: recipient.id,
+            amount,
+            sender_note: None,
+            receiver_note: None,
+            pin: None,
+        },
+    )
+    .await;
+    assert!(matches!(multi, Err(BankError::ConfirmationRequired)));
+
+    let item = BatchTransferItem {
+        to_account: recipient.id,
+        amount,
+        sender_note: None,
+        receiver_note: None,
+    };
+    let batch =
+        make_batch_transfer(&pool, &config, &rates, sender.user_id, sender.id, &[item]).await;
+    assert!(matches!(batch, Err(BankError::ConfirmationRequired)));
+
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(500, 0)
+    );
+}
+
+#[tokio::test]
+async fn initiated_transfer_moves_money_only_after_confirmation() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(500, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let initiation = initiate_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.id,
+        recipient.id,
+        Decimal::new(150, 0),
+        TransferNotes::default(),
+    )
+    .await
+    .unwrap();
+    let token = pending_token(initiation);
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(500, 0)
+    );
+
+    let transaction = confirm_transfer(&pool, &config, &rates, &token)
+        .await
+        .unwrap();
+    assert_eq!(transaction.amount, Decimal::new(150, 0));
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(350, 0)
+    );
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(150, 0)
+    );
+
+    let reused = confirm_transfer(&pool, &config, &rates, &token).await;
+    assert!(matches!(reused, Err(BankError::TransferNotFound)));
+}
+
+#[tokio::test]
+async fn expired_confirmation_token_is_rejected() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(500, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let initiation = initiate_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.id,
+        recipient.id,
+        Decimal::new(150, 0),
+        TransferNotes::default(),
+    )
+    .await
+    .unwrap();
+    let token = pending_token(initiation);
+
+    sqlx::query(
+        "UPDATE pending_transfers SET expires_at = NOW() - INTERVAL '1 second' WHERE token = $1",
+    )
+    .bind(&token)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let confirmed = confirm_transfer(&pool, &config, &rates, &token).await;
+    assert!(matches!(confirmed, Err(BankError::TransferExpired)));
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(500, 0)
+    );
+}
+
+#[tokio::test]
+async fn confirmation_rechecks_funds() {
+    let config = config();
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(200, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let initiation = initiate_transfer(
+        &pool,
+        &config,
+        &rates,
+        sender.id,
+        recipient.id,
+        Decimal::new(150, 0),
+        TransferNotes::default(),
+    )
+    .await
+    .unwrap();
+    let token = pending_token(initiation);
+
+    // Mensi prevod pod hranicou medzitym znizi zostatok
+    make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(sender.id, recipient.id, Decimal::new(99, 0)),
+    )
+    .await
+    .unwrap();
+
+    let confirmed = confirm_transfer(&pool, &config, &rates, &token).await;
+    assert!(matches!(confirmed, Err(BankError::InsufficientFunds)));
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(101, 0)
+    );
+}
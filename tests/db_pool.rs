@@ -0,0 +1,77 @@
+// Nastavenia zdielaneho poolu z Config (vyzaduje DATABASE_URL)
+mod common;
+
+use sqlx::PgPool;
+
+/// PID serverovej relacie jedineho spojenia poolu
+async fn backend_pid(pool: &PgPool) -> i32 {
+    sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(pool)
+        .await
+        .expect("backend pid")
+}
+
+/// Ukonci relaciu z ineho spojenia (ako restart databazy pre toto spojenie)
+async fn terminate(pid: i32) {
+    let other = common::pool(&common::config(&[])).await;
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&other)
+        .await
+        .expect("terminate backend");
+
+    // Relacia konci asynchronne - pocka sa, kym zmizne z pg_stat_activity
+    loop {
+        let alive: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_stat_activity WHERE pid = $1)")
+                .bind(pid)
+                .fetch_one(&other)
+                .await
+                .expect("backend status");
+        if !alive {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn pool_honours_max_connections() {
+    let config = common::config(&[("DB_MAX_CONNECTIONS", "2")]);
+    let pool = common::pool(&config).await;
+
+    let _first = pool.acquire().await.unwrap();
+    let _second = pool.acquire().await.unwrap();
+    assert_eq!(pool.size(), 2);
+    assert!(pool.try_acquire().is_none());
+}
+
+#[tokio::test]
+async fn broken_connection_is_replaced_before_use() {
+    let config = common::config(&[("DB_MAX_CONNECTIONS", "1")]);
+    let pool = common::pool(&config).await;
+
+    let pid = backend_pid(&pool).await;
+    terminate(pid).await;
+
+    // DB_TEST_BEFORE_ACQUIRE (predvolene zapnute) nefunkcne spojenie zahodi
+    let replaced = backend_pid(&pool).await;
+    assert_ne!(replaced, pid);
+}
+
+#[tokio::test]
+async fn without_test_before_acquire_broken_connection_fails() {
+    let config = common::config(&[
+        ("DB_MAX_CONNECTIONS", "1"),
+        ("DB_TEST_BEFORE_ACQUIRE", "false"),
+    ]);
+    let pool = common::pool(&config).await;
+
+    let pid = backend_pid(&pool).await;
+    terminate(pid).await;
+
+    let result = sqlx::query_scalar::<_, i32>("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await;
+    assert!(result.is_err());
+}
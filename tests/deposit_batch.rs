@@ -0,0 +1,96 @@
+// Hromadne vklady v jednej databazovej transakcii (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{BankError, add_money_batch};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn batch_credits_every_deposit_and_sums_repeated_accounts() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let a = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let b = common::new_account(&pool, &config, Decimal::new(5, 0)).await;
+
+    let batch = add_money_batch(
+        &pool,
+        &config,
+        vec![
+            (a.id, Decimal::new(10, 0)),
+            (b.id, Decimal::new(20, 0)),
+            (a.id, Decimal::new(5, 0)),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let deposits: Vec<_> = batch
+        .deposits
+        .iter()
+        .map(|d| (d.account_id, d.amount))
+        .collect();
+    assert_eq!(
+        deposits,
+        vec![
+            (a.id, Decimal::new(10, 0)),
+            (b.id, Decimal::new(20, 0)),
+            (a.id, Decimal::new(5, 0)),
+        ]
+    );
+    let accounts: Vec<_> = batch.accounts.iter().map(|a| (a.id, a.balance)).collect();
+    assert_eq!(
+        accounts,
+        vec![(a.id, Decimal::new(15, 0)), (b.id, Decimal::new(25, 0))]
+    );
+    assert_eq!(common::balance(&pool, a.id).await, Decimal::new(15, 0));
+
+    // Kazdy vklad ma vlastny zaznam transakcie
+    let recorded: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM transactions WHERE id = ANY($1) AND from_account IS NULL",
+    )
+    .bind(
+        batch
+            .deposits
+            .iter()
+            .map(|d| d.transaction_id)
+            .collect::<Vec<_>>(),
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(recorded, 3);
+}
+
+#[tokio::test]
+async fn batch_with_invalid_item_credits_nothing() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let missing = add_money_batch(
+        &pool,
+        &config,
+        vec![
+            (account.id, Decimal::new(10, 0)),
+            (Uuid::new_v4(), Decimal::new(10, 0)),
+        ],
+    )
+    .await;
+    assert!(matches!(missing, Err(BankError::AccountNotFound)));
+
+    let negative = add_money_batch(
+        &pool,
+        &config,
+        vec![
+            (account.id, Decimal::new(10, 0)),
+            (account.id, Decimal::new(-1, 0)),
+        ],
+    )
+    .await;
+    assert!(matches!(negative, Err(BankError::InvalidAmount)));
+
+    let empty = add_money_batch(&pool, &config, Vec::new()).await;
+    assert!(matches!(empty, Err(BankError::InvalidBody(_))));
+
+    assert_eq!(common::balance(&pool, account.id).await, Decimal::ZERO);
+}
@@ -0,0 +1,69 @@
+// Blokacia velkych vkladov podla DEPOSIT_HOLD_THRESHOLD (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, StaticRateProvider, add_money, get_account_balance, make_transaction,
+};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn large_deposit_is_unavailable_until_available_at() {
+    let config = common::config(&[
+        ("DEPOSIT_HOLD_THRESHOLD", "1000"),
+        ("DEPOSIT_HOLD_SECS", "3600"),
+    ]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    add_money(&pool, &config, account.id, Decimal::new(500, 0), None)
+        .await
+        .unwrap();
+    add_money(&pool, &config, account.id, Decimal::new(2000, 0), None)
+        .await
+        .unwrap();
+
+    let balance = get_account_balance(&pool, account.id).await.unwrap();
+    assert_eq!(balance.balance, Decimal::new(2500, 0));
+    assert_eq!(balance.available_balance, Decimal::new(500, 0));
+    assert_eq!(balance.holds.len(), 1);
+    assert_eq!(balance.holds[0].amount, Decimal::new(2000, 0));
+    let release = balance.holds[0].available_at - Utc::now();
+    assert!(release > Duration::minutes(59) && release <= Duration::minutes(60));
+
+    // Prevod sa odcita len z dostupneho zostatku
+    let transfer = make_transaction(
+        &pool,
+        &config,
+        &StaticRateProvider::new(),
+        &common::transfer(account.id, recipient.id, Decimal::new(600, 0)),
+    )
+    .await;
+    assert!(matches!(transfer, Err(BankError::InsufficientFunds)));
+
+    // Po available_at je cely vklad k dispozicii
+    sqlx::query("UPDATE transactions SET available_at = NOW() - INTERVAL '1 second' WHERE id = $1")
+        .bind(balance.holds[0].transaction_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let released = get_account_balance(&pool, account.id).await.unwrap();
+    assert_eq!(released.available_balance, Decimal::new(2500, 0));
+    assert!(released.holds.is_empty());
+}
+
+#[tokio::test]
+async fn deposits_are_available_without_threshold() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    add_money(&pool, &config, account.id, Decimal::new(1_000_000, 0), None)
+        .await
+        .unwrap();
+
+    let balance = get_account_balance(&pool, account.id).await.unwrap();
+    assert_eq!(balance.available_balance, Decimal::new(1_000_000, 0));
+    assert!(balance.holds.is_empty());
+}
@@ -0,0 +1,54 @@
+// Ochrana pred dvojklikom cez DUPLICATE_TRANSFER_WINDOW_SECS (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{BankError, StaticRateProvider, make_transaction};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn identical_transfer_inside_window_is_409_duplicate() {
+    let config = common::config(&[("DUPLICATE_TRANSFER_WINDOW_SECS", "60")]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let request = common::transfer(sender.id, recipient.id, Decimal::new(10, 0));
+
+    make_transaction(&pool, &config, &rates, &request)
+        .await
+        .expect("first transfer");
+    let error = make_transaction(&pool, &config, &rates, &request)
+        .await
+        .expect_err("second transfer is a duplicate");
+
+    assert!(matches!(error, BankError::DuplicateTransfer));
+    assert_eq!(error.status(), StatusCode::CONFLICT);
+    assert_eq!(error.code(), "DUPLICATE_TRANSFER");
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(90, 0));
+
+    // Ina suma nie je duplikat
+    let other = common::transfer(sender.id, recipient.id, Decimal::new(11, 0));
+    make_transaction(&pool, &config, &rates, &other)
+        .await
+        .expect("different amount");
+}
+
+#[tokio::test]
+async fn identical_transfers_are_allowed_without_window() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let request = common::transfer(sender.id, recipient.id, Decimal::new(10, 0));
+
+    for _ in 0..2 {
+        make_transaction(&pool, &config, &rates, &request)
+            .await
+            .expect("transfer");
+    }
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(20, 0)
+    );
+}
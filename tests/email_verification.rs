@@ -0,0 +1,74 @@
+// Prevody len z uctov s overenym e-mailom podla REQUIRE_VERIFIED_EMAIL (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{
+    BankError, NotificationEvent, StaticRateProvider, list_notifications, make_transaction,
+    request_email_verification, verify_email,
+};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn unverified_sender_is_rejected_until_email_is_verified() {
+    let config = common::config(&[("REQUIRE_VERIFIED_EMAIL", "true")]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let request = common::transfer(sender.id, recipient.id, Decimal::new(10, 0));
+
+    let error = make_transaction(&pool, &config, &rates, &request)
+        .await
+        .expect_err("sender email is not verified");
+    assert!(matches!(error, BankError::EmailNotVerified));
+    assert_eq!(error.status(), StatusCode::FORBIDDEN);
+    assert_eq!(error.code(), "EMAIL_NOT_VERIFIED");
+    assert_eq!(
+        common::balance(&pool, sender.id).await,
+        Decimal::new(100, 0)
+    );
+
+    // Token prichadza ako notifikacia, nie v odpovedi
+    request_email_verification(&pool, &config, sender.user_id, "Sender@Example.com")
+        .await
+        .unwrap();
+    let token = list_notifications(&pool, sender.user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|n| n.event == NotificationEvent::EmailVerification)
+        .and_then(|n| n.detail)
+        .expect("verification token");
+    let status = verify_email(&pool, &token).await.unwrap();
+    assert!(status.email_verified);
+    assert!(matches!(
+        verify_email(&pool, &token).await,
+        Err(BankError::InvalidVerificationToken)
+    ));
+
+    // Prijemca overeny e-mail mat nemusi
+    make_transaction(&pool, &config, &rates, &request)
+        .await
+        .unwrap();
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(10, 0)
+    );
+}
+
+#[tokio::test]
+async fn unverified_sender_can_transfer_by_default() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    make_transaction(
+        &pool,
+        &config,
+        &StaticRateProvider::new(),
+        &common::transfer(sender.id, recipient.id, Decimal::new(10, 0)),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,48 @@
+// Porusenie cudzieho kluca sa vracia ako 400 REFERENCE_NOT_FOUND (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{
+    BankError, CreateRecurringTransferRequest, Frequency, NotificationPrefsUpdate,
+    create_recurring_transfer, update_notification_prefs,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn recurring_transfer_to_missing_account_is_400() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let request = CreateRecurringTransferRequest {
+        from_account: sender.id,
+        to_account: Uuid::new_v4(),
+        amount: Decimal::new(10, 0),
+        frequency: Frequency::Monthly,
+        start_at: None,
+        sender_note: None,
+        receiver_note: None,
+    };
+
+    let error = create_recurring_transfer(&pool, &config, sender.user_id, &request)
+        .await
+        .expect_err("recipient does not exist");
+
+    assert!(matches!(error, BankError::ReferenceNotFound("account")));
+    assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.code(), "REFERENCE_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn notification_prefs_of_missing_user_are_400() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+
+    let error =
+        update_notification_prefs(&pool, Uuid::new_v4(), &NotificationPrefsUpdate::default())
+            .await
+            .expect_err("user does not exist");
+
+    assert!(matches!(error, BankError::ReferenceNotFound("user")));
+    assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+}
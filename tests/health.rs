@@ -0,0 +1,29 @@
+// Stav zdielaneho poolu v /health/detailed (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{AppState, BankError, health_report, pool_options};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn pool_stats_reflect_repository_traffic() {
+    let config = common::config(&[("DB_MAX_CONNECTIONS", "3")]);
+    let pool = pool_options(&config)
+        .connect_lazy(&config.database_url)
+        .unwrap();
+    let state = AppState::new(config, pool.clone());
+
+    // Handlery pristupuju k datam cez AppState::repo nad tym istym poolom
+    let missing = state.repo.get_account_by_id(Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(BankError::AccountNotFound)));
+
+    let stats = health_report(&pool, 3).await.unwrap().pool;
+    assert_eq!(stats.size, 1);
+    assert_eq!(stats.max_connections, 3);
+
+    // Spojenie drzane rozpracovanou operaciou sa ukaze ako pouzivane
+    let busy = pool.acquire().await.unwrap();
+    let report = health_report(&pool, 3).await.unwrap();
+    assert!(report.pool.in_use >= 1);
+    assert!(report.connections.total >= 1);
+    drop(busy);
+}
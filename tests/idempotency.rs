@@ -0,0 +1,209 @@
+// Platnost klucov idempotencie (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, StaticRateProvider, TransactionRequest, add_money, archive_transactions,
+    create_user, make_transaction, purge_expired_idempotency_keys,
+};
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn expired_deposit_key_is_purged_and_reusable() {
+    let config = common::config(&[("IDEMPOTENCY_KEY_TTL_SECS", "60")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let key = "deposit-ttl";
+
+    add_money(&pool, &config, account.id, Decimal::new(1000, 2), Some(key))
+        .await
+        .unwrap();
+    let replay = add_money(&pool, &config, account.id, Decimal::new(1000, 2), Some(key))
+        .await
+        .unwrap();
+    assert_eq!(replay.balance, Decimal::new(1000, 2));
+
+    sqlx::query(
+        "UPDATE transactions SET created_at = NOW() - INTERVAL '2 hours'
+         WHERE to_account = $1 AND idempotency_key = $2",
+    )
+    .bind(account.id)
+    .bind(key)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let purged = purge_expired_idempotency_keys(&pool, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(purged >= 1);
+
+    let fresh = add_money(&pool, &config, account.id, Decimal::new(1000, 2), Some(key))
+        .await
+        .unwrap();
+    assert_eq!(fresh.balance, Decimal::new(2000, 2));
+}
+
+#[tokio::test]
+async fn archived_deposit_key_still_blocks_replay() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let key = format!("deposit-archived-{}", Uuid::new_v4());
+
+    add_money(&pool, &config, account.id, Decimal::new(500, 2), Some(&key))
+        .await
+        .unwrap();
+
+    // Len tento vklad je starsi ako hranica archivacie
+    sqlx::query(
+        "UPDATE transactions SET created_at = '2000-01-01T00:00:00Z'
+         WHERE to_account = $1 AND idempotency_key = $2",
+    )
+    .bind(account.id)
+    .bind(&key)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let cutoff = Utc.with_ymd_and_hms(2000, 1, 2, 0, 0, 0).unwrap();
+    assert!(archive_transactions(&pool, cutoff, 1000).await.unwrap() >= 1);
+
+    let replay = add_money(&pool, &config, account.id, Decimal::new(500, 2), Some(&key))
+        .await
+        .unwrap();
+    assert_eq!(replay.balance, Decimal::new(500, 2));
+}
+
+#[tokio::test]
+async fn unique_violations_name_the_duplicated_field() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+
+    // Obsadene meno
+    let name = format!("dup_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    create_user(&pool, &config, &name, "Test-password-1")
+        .await
+        .unwrap();
+    let username = create_user(&pool, &config, &name, "Test-password-1")
+        .await
+        .unwrap_err();
+    assert!(matches!(username, BankError::AlreadyExists("username")));
+
+    // Kluc idempotencie druheho prevodu z toho isteho uctu
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let request = TransactionRequest {
+        idempotency_key: Some("dup-key".to_string()),
+        ..common::transfer(sender.id, recipient.id, Decimal::ONE)
+    };
+    make_transaction(&pool, &config, &rates, &request)
+        .await
+        .unwrap();
+    let second = make_transaction(
+        &pool,
+        &config,
+        &rates,
+        &common::transfer(sender.id, recipient.id, Decimal::ONE),
+    )
+    .await
+    .unwrap();
+    let key = sqlx::query("UPDATE transactions SET idempotency_key = 'dup-key' WHERE id = $1")
+        .bind(second.id)
+        .execute(&pool)
+        .await
+        .map_err(BankError::from)
+        .unwrap_err();
+    assert!(matches!(key, BankError::AlreadyExists("idempotency key")));
+
+    assert_eq!(username.to_string(), "username already exists");
+    assert_eq!(key.to_string(), "idempotency key already exists");
+}
+
+#[tokio::test]
+async fn concurrent_transfers_with_same_key_replay_the_first() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let key = format!("concurrent-{}", Uuid::new_v4());
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let (pool, config) = (pool.clone(), config.clone());
+            let request = TransactionRequest {
+                idempotency_key: Some(key.clone()),
+                ..common::transfer(sender.id, recipient.id, Decimal::new(10, 0))
+            };
+            tokio::spawn(async move {
+                make_transaction(&pool, &config, &StaticRateProvider::new(), &request).await
+            })
+        })
+        .collect();
+
+    let mut ids = Vec::new();
+    for task in tasks {
+        ids.push(task.await.unwrap().expect("replayed transfer").id);
+    }
+    assert!(ids.iter().all(|id| *id == ids[0]), "{ids:?}");
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(90, 0));
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(10, 0)
+    );
+}
+
+/// Pocet vkladov (transakcii bez odosielatela, okrem otvorenia uctu) na ucet
+async fn deposit_count(pool: &sqlx::PgPool, account_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM transactions
+         WHERE to_account = $1 AND from_account IS NULL AND NOT account_opening",
+    )
+    .bind(account_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn deposit_replay_inside_ttl_is_ignored_and_after_ttl_is_new() {
+    let config = common::config(&[("IDEMPOTENCY_KEY_TTL_SECS", "60")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let key = "deposit-window";
+    let amount = Decimal::new(2500, 2);
+
+    let first = add_money(&pool, &config, account.id, amount, Some(key))
+        .await
+        .unwrap();
+    assert_eq!(first.balance, amount);
+
+    // Opakovanie v ramci platnosti kluca - bez druheho pripisania
+    let replay = add_money(&pool, &config, account.id, amount, Some(key))
+        .await
+        .unwrap();
+    assert_eq!(replay.balance, amount);
+    assert_eq!(deposit_count(&pool, account.id).await, 1);
+
+    // Po uplynuti platnosti (bez behu cistiacej ulohy) je to novy vklad
+    sqlx::query(
+        "UPDATE transactions SET created_at = NOW() - INTERVAL '61 seconds'
+         WHERE to_account = $1 AND idempotency_key = $2",
+    )
+    .bind(account.id)
+    .bind(key)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let fresh = add_money(&pool, &config, account.id, amount, Some(key))
+        .await
+        .unwrap();
+    assert_eq!(fresh.balance, amount * Decimal::TWO);
+    assert_eq!(deposit_count(&pool, account.id).await, 2);
+    assert_eq!(
+        common::balance(&pool, account.id).await,
+        amount * Decimal::TWO
+    );
+}
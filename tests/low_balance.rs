@@ -0,0 +1,105 @@
+// Upozornenie na nizky zostatok uctu (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    BankError, NotificationEvent, NotificationPrefsUpdate, StaticRateProvider, add_money,
+    list_notifications, make_transaction, set_low_balance_threshold, update_notification_prefs,
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Detaily upozorneni LowBalance pouzivatela od najnovsieho
+async fn low_balance_alerts(pool: &PgPool, user_id: Uuid) -> Vec<String> {
+    list_notifications(pool, user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|n| n.event == NotificationEvent::LowBalance)
+        .map(|n| n.detail.unwrap_or_default())
+        .collect()
+}
+
+#[tokio::test]
+async fn alert_is_sent_once_when_balance_crosses_threshold() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    set_low_balance_threshold(&pool, sender.id, sender.user_id, Some(Decimal::new(50, 0)))
+        .await
+        .unwrap();
+    let send = |amount| common::transfer(sender.id, recipient.id, Decimal::new(amount, 0));
+
+    // 100 -> 70 je stale nad hranicou
+    make_transaction(&pool, &config, &rates, &send(30))
+        .await
+        .unwrap();
+    assert!(low_balance_alerts(&pool, sender.user_id).await.is_empty());
+
+    // 70 -> 40 prekroci hranicu, 40 -> 30 uz neupozorni znovu
+    make_transaction(&pool, &config, &rates, &send(30))
+        .await
+        .unwrap();
+    make_transaction(&pool, &config, &rates, &send(10))
+        .await
+        .unwrap();
+    assert_eq!(
+        low_balance_alerts(&pool, sender.user_id).await,
+        ["balance 40 below 50"]
+    );
+
+    // Po navrate nad hranicu sa upozornenie posle znovu
+    add_money(&pool, &config, sender.id, Decimal::new(100, 0), None)
+        .await
+        .unwrap();
+    make_transaction(&pool, &config, &rates, &send(90))
+        .await
+        .unwrap();
+    assert_eq!(
+        low_balance_alerts(&pool, sender.user_id).await,
+        ["balance 40 below 50", "balance 40 below 50"]
+    );
+    assert!(
+        low_balance_alerts(&pool, recipient.user_id)
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn alert_respects_preferences_and_ownership() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let foreign =
+        set_low_balance_threshold(&pool, sender.id, recipient.user_id, Some(Decimal::ONE)).await;
+    assert!(matches!(foreign, Err(BankError::Forbidden)));
+    let negative =
+        set_low_balance_threshold(&pool, sender.id, sender.user_id, Some(-Decimal::ONE)).await;
+    assert!(matches!(negative, Err(BankError::InvalidAmount)));
+
+    set_low_balance_threshold(&pool, sender.id, sender.user_id, Some(Decimal::new(50, 0)))
+        .await
+        .unwrap();
+    let prefs = NotificationPrefsUpdate {
+        low_balance: Some(false),
+        ..Default::default()
+    };
+    update_notification_prefs(&pool, sender.user_id, &prefs)
+        .await
+        .unwrap();
+
+    make_transaction(
+        &pool,
+        &config,
+        &StaticRateProvider::new(),
+        &common::transfer(sender.id, recipient.id, Decimal::new(80, 0)),
+    )
+    .await
+    .unwrap();
+    assert!(low_balance_alerts(&pool, sender.user_id).await.is_empty());
+}
@@ -0,0 +1,56 @@
+// Zlucenie uctov toho isteho vlastnika (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{AccountStatus, AccountType, BankError, get_account_by_id, merge_accounts};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn merge_moves_balance_and_closes_source() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let source = common::new_account(&pool, &config, Decimal::new(12550, 2)).await;
+    let target = common::another_account(
+        &pool,
+        &config,
+        source.user_id,
+        AccountType::Checking,
+        Decimal::new(10000, 2),
+    )
+    .await;
+
+    let merge = merge_accounts(&pool, &config, source.user_id, source.id, target.id, false)
+        .await
+        .unwrap();
+
+    assert_eq!(merge.target.balance, Decimal::new(22550, 2));
+    let transfer = merge.transfer.expect("balance transfer");
+    assert_eq!(transfer.amount, Decimal::new(12550, 2));
+
+    let closed = get_account_by_id(&pool, source.id).await.unwrap();
+    assert_eq!(closed.status, AccountStatus::Closed);
+    assert_eq!(closed.balance, Decimal::ZERO);
+
+    let merged_into: Option<Uuid> =
+        sqlx::query_scalar("SELECT merged_into FROM accounts WHERE id = $1")
+            .bind(source.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(merged_into, Some(target.id));
+}
+
+#[tokio::test]
+async fn merge_requires_same_owner() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let source = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let foreign = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let result = merge_accounts(&pool, &config, source.user_id, source.id, foreign.id, false).await;
+    assert!(matches!(result, Err(BankError::Forbidden)));
+    assert_eq!(
+        common::balance(&pool, source.id).await,
+        Decimal::new(100, 0)
+    );
+}
@@ -0,0 +1,128 @@
+// Prevod cerpajuci z viacerych uctov odosielatela (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    AccountType, BankError, MultiSourceTransferRequest, StaticRateProvider,
+    make_transaction_from_multiple,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Poziadavka bez poznamok a PIN-u
+fn request(sources: Vec<Uuid>, to_account: Uuid, amount: i64) -> MultiSourceTransferRequest {
+    MultiSourceTransferRequest {
+        sources,
+        to_account,
+        amount: Decimal::new(amount, 0),
+        sender_note: None,
+        receiver_note: None,
+        pin: None,
+    }
+}
+
+#[tokio::test]
+async fn sources_are_drained_in_order_until_amount_is_covered() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let first = common::new_account(&pool, &config, Decimal::new(30, 0)).await;
+    let second = common::another_account(
+        &pool,
+        &config,
+        first.user_id,
+        AccountType::Savings,
+        Decimal::new(50, 0),
+    )
+    .await;
+    let unused = common::another_account(
+        &pool,
+        &config,
+        first.user_id,
+        AccountType::Checking,
+        Decimal::new(40, 0),
+    )
+    .await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let transfer = make_transaction_from_multiple(
+        &pool,
+        &config,
+        &rates,
+        first.user_id,
+        &request(vec![first.id, second.id, unused.id], recipient.id, 60),
+    )
+    .await
+    .unwrap();
+
+    let parts: Vec<_> = transfer
+        .transactions
+        .iter()
+        .map(|t| (t.from_account, t.amount))
+        .collect();
+    assert_eq!(
+        parts,
+        vec![
+            (Some(first.id), Decimal::new(30, 0)),
+            (Some(second.id), Decimal::new(30, 0)),
+        ]
+    );
+    assert_eq!(common::balance(&pool, first.id).await, Decimal::ZERO);
+    assert_eq!(common::balance(&pool, second.id).await, Decimal::new(20, 0));
+    assert_eq!(common::balance(&pool, unused.id).await, Decimal::new(40, 0));
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(60, 0)
+    );
+}
+
+#[tokio::test]
+async fn multi_source_transfer_is_all_or_nothing() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let first = common::new_account(&pool, &config, Decimal::new(30, 0)).await;
+    let second = common::another_account(
+        &pool,
+        &config,
+        first.user_id,
+        AccountType::Savings,
+        Decimal::new(20, 0),
+    )
+    .await;
+    let foreign = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let short = make_transaction_from_multiple(
+        &pool,
+        &config,
+        &rates,
+        first.user_id,
+        &request(vec![first.id, second.id], recipient.id, 60),
+    )
+    .await;
+    assert!(matches!(short, Err(BankError::InsufficientFunds)));
+
+    let stolen = make_transaction_from_multiple(
+        &pool,
+        &config,
+        &rates,
+        first.user_id,
+        &request(vec![first.id, foreign.id], recipient.id, 60),
+    )
+    .await;
+    assert!(matches!(stolen, Err(BankError::Forbidden)));
+
+    let repeated = make_transaction_from_multiple(
+        &pool,
+        &config,
+        &rates,
+        first.user_id,
+        &request(vec![first.id, first.id], recipient.id, 10),
+    )
+    .await;
+    assert!(matches!(repeated, Err(BankError::InvalidBody(_))));
+
+    assert_eq!(common::balance(&pool, first.id).await, Decimal::new(30, 0));
+    assert_eq!(common::balance(&pool, second.id).await, Decimal::new(20, 0));
+    assert_eq!(common::balance(&pool, recipient.id).await, Decimal::ZERO);
+}
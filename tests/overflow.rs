@@ -0,0 +1,51 @@
+// Pretecenie zostatku (NUMERIC_OUT_OF_RANGE) ako AmountTooLarge (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{BankError, StaticRateProvider, add_money, make_transaction};
+use rust_decimal::Decimal;
+
+/// Najvacsia suma, ktora sa zmesti do stlpca NUMERIC(15, 2)
+fn max_amount() -> Decimal {
+    Decimal::new(999_999_999_999_999, 2)
+}
+
+#[tokio::test]
+async fn deposit_overflowing_balance_is_amount_too_large() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    add_money(&pool, &config, account.id, max_amount(), None)
+        .await
+        .unwrap();
+    let error = add_money(&pool, &config, account.id, Decimal::ONE, None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BankError::AmountTooLarge), "{error:?}");
+    assert_eq!(error.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error.code(), "AMOUNT_TOO_LARGE");
+    // Zmena zostatku sa stornovala
+    assert_eq!(common::balance(&pool, account.id).await, max_amount());
+}
+
+#[tokio::test]
+async fn transfer_overflowing_recipient_is_amount_too_large() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::TEN).await;
+    let recipient = common::new_account(&pool, &config, max_amount()).await;
+
+    let error = make_transaction(
+        &pool,
+        &config,
+        &StaticRateProvider::new(),
+        &common::transfer(sender.id, recipient.id, Decimal::ONE),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(error, BankError::AmountTooLarge), "{error:?}");
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::TEN);
+}
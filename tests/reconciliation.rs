@@ -0,0 +1,163 @@
+// Oprava zostatkov podla autoritativneho zdroja (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{BankError, ClientInfo, ReconcileEntry, reconcile_balances};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+fn entry(account_id: Uuid, expected_balance: Decimal) -> ReconcileEntry {
+    ReconcileEntry {
+        account_id,
+        expected_balance,
+    }
+}
+
+fn client() -> ClientInfo {
+    ClientInfo {
+        ip: Some("10.0.0.1".to_string()),
+        user_agent: Some("reconcile-test".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn differences_are_booked_as_audited_adjustments() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let low = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let high = common::new_account(&pool, &config, Decimal::new(50, 0)).await;
+    let exact = common::new_account(&pool, &config, Decimal::new(30, 0)).await;
+
+    let entries = [
+        entry(low.id, Decimal::new(12050, 2)),
+        entry(high.id, Decimal::new(40, 0)),
+        entry(exact.id, Decimal::new(30, 0)),
+    ];
+    let result = reconcile_balances(&pool, &config, &entries, false, Some("ops"), &client())
+        .await
+        .unwrap();
+
+    assert_eq!(result.actor.as_deref(), Some("ops"));
+    assert!(!result.forced);
+    let adjustments: Vec<_> = result.adjustments.iter().map(|a| a.adjustment).collect();
+    assert_eq!(
+        adjustments,
+        [Decimal::new(2050, 2), Decimal::new(-10, 0), Decimal::ZERO]
+    );
+    assert_eq!(result.adjustments[2].transaction_id, None);
+    assert_eq!(common::balance(&pool, low.id).await, Decimal::new(12050, 2));
+    assert_eq!(common::balance(&pool, high.id).await, Decimal::new(40, 0));
+
+    // Kladny rozdiel je vklad, zaporny odpis, oba s reconciliation_id
+    let credit: (Option<Uuid>, Option<Uuid>, Decimal, Option<Uuid>) = sqlx::query_as(
+        "SELECT from_account, to_account, amount, reconciliation_id FROM transactions WHERE id = $1",
+    )
+    .bind(result.adjustments[0].transaction_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        credit,
+        (None, Some(low.id), Decimal::new(2050, 2), Some(result.id))
+    );
+    let debit: (Option<Uuid>, Option<Uuid>, Decimal) =
+        sqlx::query_as("SELECT from_account, to_account, amount FROM transactions WHERE id = $1")
+            .bind(result.adjustments[1].transaction_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(debit, (Some(high.id), None, Decimal::new(10, 0)));
+
+    let audit: (Option<String>, Option<String>, i64) = sqlx::query_as(
+        "SELECT r.actor, r.user_agent, COUNT(i.account_id)
+         FROM reconciliations r JOIN reconciliation_items i ON i.reconciliation_id = r.id
+         WHERE r.id = $1
+         GROUP BY r.actor, r.user_agent",
+    )
+    .bind(result.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        audit,
+        (
+            Some("ops".to_string()),
+            Some("reconcile-test".to_string()),
+            3
+        )
+    );
+}
+
+#[tokio::test]
+async fn frozen_account_needs_force_and_failure_changes_nothing() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let active = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let frozen = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    sqlx::query("UPDATE accounts SET status = 'frozen' WHERE id = $1")
+        .bind(frozen.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let entries = [
+        entry(active.id, Decimal::new(90, 0)),
+        entry(frozen.id, Decimal::new(80, 0)),
+    ];
+
+    let error = reconcile_balances(&pool, &config, &entries, false, None, &client()).await;
+    assert!(matches!(error, Err(BankError::AccountFrozen)));
+    assert_eq!(
+        common::balance(&pool, active.id).await,
+        Decimal::new(100, 0)
+    );
+
+    let missing = [
+        entry(active.id, Decimal::new(90, 0)),
+        entry(Uuid::new_v4(), Decimal::ZERO),
+    ];
+    let error = reconcile_balances(&pool, &config, &missing, true, None, &client()).await;
+    assert!(matches!(error, Err(BankError::AccountNotFound)));
+    assert_eq!(
+        common::balance(&pool, active.id).await,
+        Decimal::new(100, 0)
+    );
+
+    let forced = reconcile_balances(&pool, &config, &entries, true, None, &client())
+        .await
+        .unwrap();
+    assert!(forced.forced);
+    assert_eq!(common::balance(&pool, frozen.id).await, Decimal::new(80, 0));
+}
+
+#[tokio::test]
+async fn invalid_entries_are_rejected_before_touching_accounts() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+
+    let empty = reconcile_balances(&pool, &config, &[], false, None, &client()).await;
+    assert!(matches!(empty, Err(BankError::InvalidBody(_))));
+
+    let repeated = [
+        entry(account.id, Decimal::ONE),
+        entry(account.id, Decimal::TWO),
+    ];
+    let repeated = reconcile_balances(&pool, &config, &repeated, false, None, &client()).await;
+    assert!(matches!(repeated, Err(BankError::InvalidBody(_))));
+
+    for expected in [-Decimal::ONE, Decimal::new(1001, 3)] {
+        let invalid = reconcile_balances(
+            &pool,
+            &config,
+            &[entry(account.id, expected)],
+            false,
+            None,
+            &client(),
+        )
+        .await;
+        assert!(matches!(invalid, Err(BankError::InvalidAmount)));
+    }
+    assert_eq!(
+        common::balance(&pool, account.id).await,
+        Decimal::new(100, 0)
+    );
+}
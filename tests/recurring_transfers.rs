@@ -0,0 +1,92 @@
+// Vykonavanie splatnych trvalych prikazov (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{
+    CreateRecurringTransferRequest, Frequency, StaticRateProvider, create_recurring_transfer,
+    list_recurring_transfers, run_due_recurring_transfers,
+};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+fn daily(from_account: Uuid, to_account: Uuid, amount: Decimal) -> CreateRecurringTransferRequest {
+    CreateRecurringTransferRequest {
+        from_account,
+        to_account,
+        amount,
+        frequency: Frequency::Daily,
+        start_at: Some(Utc::now() - Duration::days(3) - Duration::hours(1)),
+        sender_note: None,
+        receiver_note: None,
+    }
+}
+
+#[tokio::test]
+async fn due_order_runs_once_and_skips_missed_dates() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let order = create_recurring_transfer(
+        &pool,
+        &config,
+        sender.user_id,
+        &daily(sender.id, recipient.id, Decimal::new(30, 0)),
+    )
+    .await
+    .unwrap();
+
+    // Tri zmeskane terminy sa vykonaju len raz
+    for _ in 0..2 {
+        run_due_recurring_transfers(&pool, &config, &rates)
+            .await
+            .unwrap();
+    }
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(70, 0));
+    assert_eq!(
+        common::balance(&pool, recipient.id).await,
+        Decimal::new(30, 0)
+    );
+
+    let listed = list_recurring_transfers(&pool, sender.user_id)
+        .await
+        .unwrap();
+    let run = listed.iter().find(|r| r.id == order.id).unwrap();
+    assert!(run.active);
+    assert!(run.last_run_at.is_some());
+    assert_eq!(run.last_error, None);
+    let until_next = run.next_run_at - Utc::now();
+    assert!(until_next > Duration::hours(22) && until_next <= Duration::hours(23));
+}
+
+#[tokio::test]
+async fn failed_order_records_error_and_moves_on() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(10, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let order = create_recurring_transfer(
+        &pool,
+        &config,
+        sender.user_id,
+        &daily(sender.id, recipient.id, Decimal::new(50, 0)),
+    )
+    .await
+    .unwrap();
+
+    run_due_recurring_transfers(&pool, &config, &rates)
+        .await
+        .unwrap();
+
+    assert_eq!(common::balance(&pool, sender.id).await, Decimal::new(10, 0));
+    assert_eq!(common::balance(&pool, recipient.id).await, Decimal::ZERO);
+    let listed = list_recurring_transfers(&pool, sender.user_id)
+        .await
+        .unwrap();
+    let run = listed.iter().find(|r| r.id == order.id).unwrap();
+    assert!(run.active);
+    assert_eq!(run.last_error.as_deref(), Some("INSUFFICIENT_FUNDS"));
+    assert!(run.next_run_at > Utc::now());
+}
@@ -0,0 +1,48 @@
+// Detail transakcie len pre jej strany (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{AppState, BankError, StaticRateProvider, make_transaction};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn transaction_is_visible_only_to_its_parties() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let stranger = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let transfer = make_transaction(
+        &pool,
+        &config,
+        &StaticRateProvider::new(),
+        &common::transfer(sender.id, recipient.id, Decimal::new(25, 0)),
+    )
+    .await
+    .unwrap();
+    let state = AppState::new(config, pool);
+
+    for user in [sender.user_id, recipient.user_id] {
+        let found = state.repo.get_transaction(transfer.id, user).await.unwrap();
+        assert_eq!(found.id, transfer.id);
+        assert_eq!(found.amount, Decimal::new(25, 0));
+        assert_eq!(found.reference, transfer.reference);
+    }
+
+    let error = state
+        .repo
+        .get_transaction(transfer.id, stranger.user_id)
+        .await
+        .expect_err("stranger is not a party");
+    assert!(matches!(error, BankError::Forbidden));
+    assert_eq!(error.status(), StatusCode::FORBIDDEN);
+
+    let missing = state
+        .repo
+        .get_transaction(Uuid::new_v4(), sender.user_id)
+        .await
+        .expect_err("unknown transaction");
+    assert!(matches!(missing, BankError::TransactionNotFound));
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
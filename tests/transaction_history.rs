@@ -0,0 +1,60 @@
+// Historia transakcii uctu (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{AppState, BankError, SortOrder, archive_transactions};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn account_without_active_transactions_has_empty_history() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    // Jediny zaznam (otvorenie uctu) sa presunie do archivu
+    sqlx::query(
+        "UPDATE transactions SET created_at = NOW() - INTERVAL '1 day' WHERE to_account = $1",
+    )
+    .bind(account.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    archive_transactions(&pool, Utc::now() - Duration::hours(1), 1000)
+        .await
+        .unwrap();
+
+    let state = AppState::new(config, pool);
+    let history = state
+        .repo
+        .get_transaction_history(account.id, false, SortOrder::Desc)
+        .await
+        .unwrap();
+    assert_eq!(json!(history), json!([]));
+
+    let full = state
+        .repo
+        .get_transaction_history(account.id, true, SortOrder::Desc)
+        .await
+        .unwrap();
+    assert_eq!(full.len(), 1);
+}
+
+#[tokio::test]
+async fn history_of_missing_account_is_404() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let state = AppState::new(config, pool);
+
+    for include_archived in [false, true] {
+        let error = state
+            .repo
+            .get_transaction_history(Uuid::new_v4(), include_archived, SortOrder::Desc)
+            .await
+            .expect_err("account does not exist");
+        assert!(matches!(error, BankError::AccountNotFound));
+        assert_eq!(error.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,50 @@
+// Obmedzenie sucasne beziacich prevodov pouzivatela (vyzaduje DATABASE_URL)
+mod common;
+
+use axum::http::StatusCode;
+use bank_backend::{AppState, BankError};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn transfers_over_per_user_limit_are_429_until_a_permit_is_released() {
+    let config = common::config(&[("MAX_CONCURRENT_TRANSFERS_PER_USER", "2")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let other = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let state = AppState::new(config, pool);
+
+    let first = state.account_transfer_permit(account.id).await.unwrap();
+    let _second = state.account_transfer_permit(account.id).await.unwrap();
+
+    let error = state
+        .account_transfer_permit(account.id)
+        .await
+        .expect_err("limit reached");
+    assert!(matches!(error, BankError::TooManyTransfers));
+    assert_eq!(error.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(error.code(), "TOO_MANY_CONCURRENT_TRANSFERS");
+
+    // Limit plati pre kazdeho pouzivatela zvlast
+    state.account_transfer_permit(other.id).await.unwrap();
+
+    // Skonceny prevod uvolni miesto
+    drop(first);
+    state.account_transfer_permit(account.id).await.unwrap();
+
+    let missing = state.account_transfer_permit(Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(BankError::AccountNotFound)));
+}
+
+#[tokio::test]
+async fn transfers_are_unlimited_by_default() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::ZERO).await;
+    let state = AppState::new(config, pool);
+
+    let permits: Vec<_> = (0..10)
+        .map(|_| state.transfer_permit(account.user_id).unwrap())
+        .collect();
+    assert_eq!(permits.len(), 10);
+}
@@ -0,0 +1,57 @@
+// Sukromne poznamky odosielatela a prijemcu (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{AppState, SortOrder, StaticRateProvider, make_transaction};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn each_party_sees_only_its_own_note() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let mut request = common::transfer(sender.id, recipient.id, Decimal::new(10, 0));
+    request.sender_note = Some("rent\nfor May".to_string());
+    request.receiver_note = Some("thanks from Alice".to_string());
+    let transfer = make_transaction(&pool, &config, &StaticRateProvider::new(), &request)
+        .await
+        .unwrap();
+
+    let state = AppState::new(config, pool);
+    let note_in_history = |account_id| {
+        let state = state.clone();
+        async move {
+            state
+                .repo
+                .get_transaction_history(account_id, false, SortOrder::Desc)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|t| t.id == transfer.id)
+                .expect("transfer in history")
+                .note
+        }
+    };
+    assert_eq!(
+        note_in_history(sender.id).await.as_deref(),
+        Some("rent for May")
+    );
+    assert_eq!(
+        note_in_history(recipient.id).await.as_deref(),
+        Some("thanks from Alice")
+    );
+
+    let as_sender = state
+        .repo
+        .get_transaction(transfer.id, sender.user_id)
+        .await
+        .unwrap();
+    assert_eq!(as_sender.note.as_deref(), Some("rent for May"));
+    let as_recipient = state
+        .repo
+        .get_transaction(transfer.id, recipient.user_id)
+        .await
+        .unwrap();
+    assert_eq!(as_recipient.note.as_deref(), Some("thanks from Alice"));
+}
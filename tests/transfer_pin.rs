@@ -0,0 +1,51 @@
+// PIN uctu pri prevodoch od TRANSFER_PIN_THRESHOLD (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{BankError, set_account_pin, verify_transfer_pin};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn pin_is_checked_only_from_threshold() {
+    let config = common::config(&[("TRANSFER_PIN_THRESHOLD", "100")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::new(500, 0)).await;
+    set_account_pin(&pool, &config, account.id, account.user_id, Some("4321"))
+        .await
+        .unwrap();
+
+    let below = verify_transfer_pin(&pool, &config, account.id, Decimal::new(99, 0), None).await;
+    assert!(below.is_ok());
+
+    let missing = verify_transfer_pin(&pool, &config, account.id, Decimal::new(100, 0), None).await;
+    assert!(matches!(missing, Err(BankError::PinRequired)));
+
+    let wrong = verify_transfer_pin(
+        &pool,
+        &config,
+        account.id,
+        Decimal::new(100, 0),
+        Some("1234"),
+    )
+    .await;
+    assert!(matches!(wrong, Err(BankError::InvalidPin)));
+
+    let correct = verify_transfer_pin(
+        &pool,
+        &config,
+        account.id,
+        Decimal::new(100, 0),
+        Some("4321"),
+    )
+    .await;
+    assert!(correct.is_ok());
+}
+
+#[tokio::test]
+async fn account_without_pin_needs_none() {
+    let config = common::config(&[("TRANSFER_PIN_THRESHOLD", "100")]);
+    let pool = common::pool(&config).await;
+    let account = common::new_account(&pool, &config, Decimal::new(500, 0)).await;
+
+    let result = verify_transfer_pin(&pool, &config, account.id, Decimal::new(400, 0), None).await;
+    assert!(result.is_ok());
+}
@@ -0,0 +1,42 @@
+// Referencne cisla prevodov (vyzaduje DATABASE_URL)
+mod common;
+
+use bank_backend::{BankError, StaticRateProvider, get_transaction_by_reference, make_transaction};
+use chrono::{Datelike, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn transfers_get_unique_references_in_trx_year_format() {
+    let config = common::config(&[]);
+    let pool = common::pool(&config).await;
+    let rates = StaticRateProvider::new();
+    let sender = common::new_account(&pool, &config, Decimal::new(100, 0)).await;
+    let recipient = common::new_account(&pool, &config, Decimal::ZERO).await;
+
+    let mut references = HashSet::new();
+    for amount in 1..=5 {
+        let transfer = make_transaction(
+            &pool,
+            &config,
+            &rates,
+            &common::transfer(sender.id, recipient.id, Decimal::new(amount, 0)),
+        )
+        .await
+        .unwrap();
+        let reference = transfer.reference.expect("transfer has a reference");
+
+        let prefix = format!("TRX-{}-", Utc::now().year());
+        let seq = reference.strip_prefix(&prefix).expect("TRX-<year>- prefix");
+        assert!(seq.len() >= 6 && seq.chars().all(|c| c.is_ascii_digit()));
+
+        let found = get_transaction_by_reference(&pool, &reference)
+            .await
+            .unwrap();
+        assert_eq!(found.id, transfer.id);
+        assert!(references.insert(reference), "duplicate reference");
+    }
+
+    let missing = get_transaction_by_reference(&pool, "TRX-1999-000000").await;
+    assert!(matches!(missing, Err(BankError::TransactionNotFound)));
+}